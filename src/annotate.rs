@@ -0,0 +1,321 @@
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeZone as _, Utc};
+use regex::{Captures, Regex};
+use std::io::{self, BufRead, Write};
+
+use crate::time::TimeZone;
+
+/// Timestamp shapes recognised in log lines
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TimestampKind {
+    Iso8601,  // 2025-07-22T16:00:00Z, 2025-07-22 16:00:00,123 +0000
+    Rfc2822,  // Tue, 22 Jul 2025 16:00:00 +0000
+    Syslog,   // Jul 22 16:00:00
+    UnixTime, // 1753200000, 1753200000123 (millis)
+}
+
+const MONTHS: &str = "Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec";
+
+/// A timestamp found in a line, as a byte range plus the instant it names
+#[derive(Debug, Clone, PartialEq)]
+pub struct Detected {
+    pub start: usize,
+    pub end: usize,
+    pub instant: DateTime<Utc>,
+}
+
+/// Rewrites log lines so UTC timestamps also read in other zones
+///
+/// Timestamps without an explicit offset (and syslog's year-less stamps) are taken to be UTC.
+pub struct Annotator {
+    zones: Vec<TimeZone>,
+    replace: bool,
+    use_12_hour: bool,
+    now: DateTime<Utc>,
+    patterns: Vec<(TimestampKind, Regex)>,
+}
+
+impl Annotator {
+    pub fn new(zones: Vec<TimeZone>, replace: bool, use_12_hour: bool) -> Self {
+        let patterns = vec![
+            (
+                TimestampKind::Rfc2822,
+                format!(
+                    r"\b(?:Mon|Tue|Wed|Thu|Fri|Sat|Sun), \d{{1,2}} (?:{MONTHS}) \d{{4}} \d{{2}}:\d{{2}}(?::\d{{2}})? (?:[+-]\d{{4}}|GMT|UTC|UT|[ECMP][SD]T|Z)"
+                ),
+            ),
+            (
+                TimestampKind::Iso8601,
+                r"\b(\d{4}-\d{2}-\d{2})[T ](\d{2}:\d{2}(?::\d{2})?)(?:[.,](\d{1,9}))?(Z|\ ?[+-]\d{2}:?\d{2})?"
+                    .to_string(),
+            ),
+            (
+                TimestampKind::Syslog,
+                format!(r"\b((?:{MONTHS}) [ \d]\d \d{{2}}:\d{{2}}:\d{{2}})\b"),
+            ),
+            (
+                TimestampKind::UnixTime,
+                r"\b(\d{10}|\d{13})(?:\.(\d{1,9}))?\b".to_string(),
+            ),
+        ]
+        .into_iter()
+        .map(|(kind, pattern)| (kind, Regex::new(&pattern).expect("valid timestamp pattern")))
+        .collect();
+
+        Self {
+            zones,
+            replace,
+            use_12_hour,
+            now: Utc::now(),
+            patterns,
+        }
+    }
+
+    /// Finds non-overlapping timestamps in `line`, earliest and longest first
+    pub fn detect(&self, line: &str) -> Vec<Detected> {
+        let mut found: Vec<Detected> = self
+            .patterns
+            .iter()
+            .flat_map(|(kind, regex)| {
+                regex.captures_iter(line).filter_map(move |caps| {
+                    let whole = caps.get(0)?;
+                    Some(Detected {
+                        start: whole.start(),
+                        end: whole.end(),
+                        instant: self.parse(*kind, &caps)?,
+                    })
+                })
+            })
+            .collect();
+
+        found.sort_by_key(|d| (d.start, std::cmp::Reverse(d.end)));
+        let mut result: Vec<Detected> = Vec::new();
+        for detected in found {
+            if result.last().is_none_or(|last| detected.start >= last.end) {
+                result.push(detected);
+            }
+        }
+        result
+    }
+
+    fn parse(&self, kind: TimestampKind, caps: &Captures) -> Option<DateTime<Utc>> {
+        match kind {
+            TimestampKind::Rfc2822 => DateTime::parse_from_rfc2822(&caps[0])
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc)),
+            TimestampKind::Iso8601 => {
+                let date = NaiveDate::parse_from_str(&caps[1], "%Y-%m-%d").ok()?;
+                let time = NaiveTime::parse_from_str(&caps[2], "%H:%M:%S")
+                    .or_else(|_| NaiveTime::parse_from_str(&caps[2], "%H:%M"))
+                    .ok()?;
+                let nanos = caps.get(3).map_or(0, |m| fraction_to_nanos(m.as_str()));
+                let naive = date.and_time(time) + chrono::Duration::nanoseconds(nanos);
+                let offset_seconds = match caps.get(4).map(|m| m.as_str().trim_start()) {
+                    None | Some("Z") => 0,
+                    Some(offset) => parse_offset(offset)?,
+                };
+                Some(Utc.from_utc_datetime(&naive) - chrono::Duration::seconds(offset_seconds))
+            }
+            TimestampKind::Syslog => {
+                // Syslog omits the year: assume the current one unless that lands well in the future
+                let stamp = caps[1].split_whitespace().collect::<Vec<_>>().join(" ");
+                let year = self.now.year();
+                [year, year - 1].into_iter().find_map(|year| {
+                    let naive = NaiveDateTime::parse_from_str(
+                        &format!("{year} {stamp}"),
+                        "%Y %b %d %H:%M:%S",
+                    )
+                    .ok()?;
+                    let instant = Utc.from_utc_datetime(&naive);
+                    (instant <= self.now + chrono::Duration::days(1)).then_some(instant)
+                })
+            }
+            TimestampKind::UnixTime => {
+                let digits = &caps[1];
+                let value: i64 = digits.parse().ok()?;
+                if digits.len() == 13 {
+                    DateTime::from_timestamp_millis(value)
+                } else {
+                    let nanos = caps.get(2).map_or(0, |m| fraction_to_nanos(m.as_str()));
+                    DateTime::from_timestamp(value, nanos as u32)
+                }
+            }
+        }
+    }
+
+    /// Formats an instant in the chosen zones, e.g. "2025-07-22 17:00:00 BST" for one zone
+    /// or "LAX 09:00:00 / LON 17:00:00 / TYO Wed 01:00:00" for several
+    pub fn format_instant(&self, instant: DateTime<Utc>) -> String {
+        let time_format = if self.use_12_hour {
+            "%I:%M:%S %p"
+        } else {
+            "%H:%M:%S"
+        };
+
+        if let [zone] = self.zones.as_slice() {
+            let local = zone.convert_time(instant);
+            return format!(
+                "{} {}",
+                local.format(&format!("%Y-%m-%d {time_format}")),
+                zone.get_timezone_abbreviation()
+            );
+        }
+
+        let utc_date = instant.date_naive();
+        self.zones
+            .iter()
+            .map(|zone| {
+                let local = zone.convert_time(instant);
+                // Only call out the weekday when the zone is on a different date than UTC
+                let format = if local.date_naive() == utc_date {
+                    time_format.to_string()
+                } else {
+                    format!("%a {time_format}")
+                };
+                format!(
+                    "{} {}",
+                    zone.effective_display_name(),
+                    local.format(&format)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+
+    pub fn annotate_line(&self, line: &str) -> String {
+        let mut output = String::with_capacity(line.len() + 32);
+        let mut cursor = 0;
+        for detected in self.detect(line) {
+            let converted = self.format_instant(detected.instant);
+            output.push_str(&line[cursor..detected.start]);
+            if self.replace {
+                output.push_str(&converted);
+            } else {
+                output.push_str(&line[detected.start..detected.end]);
+                output.push_str(&format!(" [{converted}]"));
+            }
+            cursor = detected.end;
+        }
+        output.push_str(&line[cursor..]);
+        output
+    }
+
+    /// Annotates `input` line by line, flushing after each so it can follow `tail -f`
+    pub fn run<R: BufRead, W: Write>(&self, mut input: R, mut output: W) -> io::Result<()> {
+        let mut buffer = Vec::new();
+        loop {
+            buffer.clear();
+            if input.read_until(b'\n', &mut buffer)? == 0 {
+                return Ok(());
+            }
+
+            let line = String::from_utf8_lossy(&buffer);
+            let (content, ending) = match line.strip_suffix("\r\n") {
+                Some(content) => (content, "\r\n"),
+                None => match line.strip_suffix('\n') {
+                    Some(content) => (content, "\n"),
+                    None => (line.as_ref(), ""),
+                },
+            };
+
+            output.write_all(self.annotate_line(content).as_bytes())?;
+            output.write_all(ending.as_bytes())?;
+            output.flush()?;
+        }
+    }
+}
+
+fn fraction_to_nanos(digits: &str) -> i64 {
+    format!("{digits:0<9}")[..9].parse().unwrap_or(0)
+}
+
+/// Parses "+01:00" / "-0530" into seconds east of UTC
+fn parse_offset(offset: &str) -> Option<i64> {
+    let sign = if offset.starts_with('-') { -1 } else { 1 };
+    let digits: String = offset[1..].chars().filter(|c| c.is_ascii_digit()).collect();
+    if digits.len() != 4 {
+        return None;
+    }
+    let hours: i64 = digits[..2].parse().ok()?;
+    let minutes: i64 = digits[2..].parse().ok()?;
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instant(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn annotator(replace: bool) -> Annotator {
+        let mut annotator = Annotator::new(
+            vec![TimeZone::from_tz(chrono_tz::Europe::London)],
+            replace,
+            false,
+        );
+        // Pin "now" so syslog stamps resolve to 2025
+        annotator.now = instant("2025-07-23T00:00:00Z");
+        annotator
+    }
+
+    #[test]
+    fn test_detect_formats() {
+        let annotator = annotator(false);
+        let expected = instant("2025-07-22T16:00:00Z");
+
+        for line in [
+            "2025-07-22T16:00:00Z GET /",
+            "2025-07-22 16:00:00,000 INFO started",
+            "[2025-07-22T17:00:00+01:00] ok",
+            "2025-07-22T11:00:00-0500 ok",
+            "Date: Tue, 22 Jul 2025 16:00:00 +0000",
+            "Jul 22 16:00:00 host sshd[42]: accepted",
+            "ts=1753200000 level=info",
+            "ts=1753200000000 level=info",
+        ] {
+            let detected = annotator.detect(line);
+            assert_eq!(detected.len(), 1, "{line}");
+            assert_eq!(detected[0].instant, expected, "{line}");
+        }
+
+        assert!(annotator.detect("request took 42ms, id 12345").is_empty());
+    }
+
+    #[test]
+    fn test_annotate_line() {
+        assert_eq!(
+            annotator(false).annotate_line("2025-07-22T16:00:00Z GET /"),
+            "2025-07-22T16:00:00Z [2025-07-22 17:00:00 BST] GET /"
+        );
+        assert_eq!(
+            annotator(true).annotate_line("Jul 22 16:00:00 host cron: run"),
+            "2025-07-22 17:00:00 BST host cron: run"
+        );
+
+        let all_zones = Annotator::new(
+            vec![
+                TimeZone::from_tz(chrono_tz::America::Los_Angeles),
+                TimeZone::from_tz(chrono_tz::Asia::Tokyo),
+            ],
+            true,
+            false,
+        );
+        let line = all_zones.annotate_line("1753200000");
+        assert!(line.contains("09:00:00"), "{line}");
+        assert!(line.contains("Wed 01:00:00"), "{line}");
+    }
+
+    #[test]
+    fn test_run_preserves_line_endings() {
+        let input = b"a 1753200000\r\nplain\nlast".as_slice();
+        let mut output = Vec::new();
+        annotator(true).run(input, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "a 2025-07-22 17:00:00 BST\r\nplain\nlast"
+        );
+    }
+}
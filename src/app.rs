@@ -1,4 +1,4 @@
-use chrono::{DateTime, Local, Offset, Timelike, Utc};
+use chrono::{DateTime, Datelike, Local, NaiveDate, Offset, SecondsFormat, Timelike, Utc};
 use ratatui::{
     layout::{Alignment, Constraint, Direction as LayoutDirection, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -6,9 +6,54 @@ use ratatui::{
     Frame,
 };
 
-use crate::config::{AppConfig, ColorTheme, TimeDisplayConfig};
-use crate::time::{TimeZone, TimeZoneManager};
-use crate::ui::TimelineWidget;
+use crate::bookmark::Bookmark;
+use crate::calendar::{expand_home, Calendar, EventOccurrence, Invite};
+use crate::command;
+use crate::config::{
+    format_ranges, AppConfig, ClockTime, ColorTheme, EventOverlay, RelativeOffsetMode, RulerMode,
+    ShadingMode, TimeActivity, TimeDisplayConfig, TimeRange, TimelineZoom, ZoneConfigCompat,
+    CONFIG_VERSION,
+};
+use crate::config_file::{ConfigFile, SaveOutcome};
+use crate::countdown::{format_remaining, Countdown};
+use crate::notify::{DbusNotifier, Notifier};
+use crate::reminder::Reminder;
+use crate::solar::SolarPhase;
+use crate::table;
+use crate::time::{format_utc_offset, TimeZone, TimeZoneManager};
+use crate::ui::week_grid::shift_local_hour;
+use crate::ui::{
+    HourRuler, OffsetReference, TimelineMarker, TimelineScale, TimelineWidget, WeekGrid,
+};
+use std::path::{Path, PathBuf};
+use std::time::{Duration as StdDuration, Instant};
+
+/// How long transient footer messages stay visible
+const STATUS_MESSAGE_DURATION: StdDuration = StdDuration::from_secs(5);
+
+/// How many zone changes `u` can step back through
+const MAX_ZONE_HISTORY: usize = 50;
+
+/// How long settings must stay unchanged before they are written to disk
+const SAVE_DELAY: StdDuration = StdDuration::from_secs(1);
+
+/// How long an expired countdown stays in the header
+const COUNTDOWN_DUE_DURATION: chrono::Duration = chrono::Duration::minutes(1);
+
+/// Reminders missed by more than this (e.g. while the machine slept) are not shown
+const REMINDER_GRACE: chrono::Duration = chrono::Duration::minutes(5);
+
+/// Width of the selected zone's detail panel, beside the timelines
+const INFO_PANEL_WIDTH: u16 = 40;
+
+/// Completions the command palette lists before scrolling
+const MAX_PALETTE_COMPLETIONS: usize = 12;
+
+/// Due reminders listed in the banner before the rest are summarised as "+N more"
+const MAX_BANNER_REMINDERS: usize = 3;
+
+/// Config problems listed in the banner before the rest are summarised as "+N more"
+const MAX_BANNER_ISSUES: usize = 3;
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum TimeFormat {
@@ -22,6 +67,70 @@ pub enum TimezoneDisplayMode {
     Full,  // Pacific Time (US) PDT UTC-7
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportField {
+    Title,
+    Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CountdownField {
+    Name,
+    When,
+}
+
+/// Which end of a range the work hours settings modal moves
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingsField {
+    Start,
+    End,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum YankFormat {
+    IsoUtc,        // 2025-07-22T16:00:00Z
+    SelectedLocal, // 2025-07-22 17:00 BST
+    UnixTimestamp, // 1753200000
+    MultiZone,     // Tue 09:00 LAX / 17:00 LON / Wed 01:00 TYO
+}
+
+impl YankFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            YankFormat::IsoUtc => "ISO UTC",
+            YankFormat::SelectedLocal => "local time",
+            YankFormat::UnixTimestamp => "Unix timestamp",
+            YankFormat::MultiZone => "all zones",
+        }
+    }
+}
+
+/// How a bookmark added with `B` repeats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BookmarkRepeat {
+    Once,   // This exact instant
+    Daily,  // This local time in the selected zone, every day
+    Weekly, // ... on this weekday
+}
+
+impl BookmarkRepeat {
+    pub fn next(self) -> Self {
+        match self {
+            BookmarkRepeat::Once => BookmarkRepeat::Daily,
+            BookmarkRepeat::Daily => BookmarkRepeat::Weekly,
+            BookmarkRepeat::Weekly => BookmarkRepeat::Once,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BookmarkRepeat::Once => "once",
+            BookmarkRepeat::Daily => "daily",
+            BookmarkRepeat::Weekly => "weekly",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Direction {
     Up,
@@ -41,6 +150,11 @@ pub enum Message {
 
     // Zone navigation
     NavigateZone(Direction),
+    StartZoneFilter,
+    UpdateZoneFilter(String),
+    ConfirmZoneFilter,
+    ClearZoneFilter,
+    NextFilterMatch(Direction), // Down for n, Up for N
 
     // Display options
     ToggleTimeFormat,
@@ -49,6 +163,45 @@ pub enum Message {
     ToggleSunTimes,
     ToggleHelp,
     CycleColorTheme,
+    CycleEventOverlay,
+    ZoomIn,
+    ZoomOut,
+    ResetZoom,
+    CycleRulerMode,
+    CycleRelativeOffsets,
+    CycleShadingMode,
+    ToggleInfoPanel,
+    ToggleWeekGrid,
+
+    // Conversion table
+    ToggleTable,
+    NavigateTable(Direction), // Up/Down an hour, Left/Right a day
+    JumpToTableRow,
+    MoveWeekGridCursor(Direction), // Left/Right an hour, Up/Down a day
+
+    // Command palette
+    StartPalette,
+    UpdatePaletteInput(String),
+    NavigatePalette(Direction),
+    CompletePalette,
+    RunPalette,
+    CancelPalette,
+    AddZoneNamed { name: String, label: Option<String> },
+    RenameZoneTo(String),
+    GotoTime(String),
+    SetColorTheme(ColorTheme),
+    SetWorkHours(Vec<TimeRange>),
+    SetAwakeHours(Vec<TimeRange>),
+
+    // Work hours settings
+    OpenSettings,
+    NavigateSettings(Direction),
+    SwitchSettingsField,
+    AdjustSetting(i32), // Minutes
+    AddSettingsRange(TimeActivity),
+    DeleteSettingsRange,
+    SaveSettings,
+    CancelSettings,
 
     // Zone management
     StartAddZone,
@@ -65,11 +218,66 @@ pub enum Message {
     ConfirmRename,
     CancelRename,
     ClearCustomName,
+    Undo,
+    Redo,
+
+    // Meeting export
+    StartExport,
+    UpdateExportInput(String),
+    SwitchExportField,
+    ConfirmExport,
+    CancelExport,
+
+    // Bookmarks
+    ToggleBookmarks,
+    NavigateBookmarks(Direction),
+    JumpToBookmark,
+    DeleteBookmark,
+    StartAddBookmark,
+    UpdateBookmarkInput(String),
+    CycleBookmarkRepeat,
+    ConfirmAddBookmark,
+    CancelAddBookmark,
+
+    // Countdowns
+    ToggleCountdowns,
+    NavigateCountdowns(Direction),
+    JumpToCountdown,
+    DeleteCountdown,
+    StartAddCountdown,
+    UpdateCountdownInput(String),
+    SwitchCountdownField,
+    ConfirmAddCountdown,
+    CancelAddCountdown,
+
+    // Clipboard
+    StartYank,
+    Yank(YankFormat),
+    CancelYank,
+
+    // Config
+    DismissConfigIssues,
+    DismissReminders,
 
     // App lifecycle
     Quit,
 }
 
+/// The zone list as it was before a change, and what the change was
+#[derive(Debug, Clone)]
+pub struct ZoneSnapshot {
+    pub zones: TimeZoneManager,
+    pub selected_zone_index: usize,
+    pub action: String, // e.g. "remove Tokyo", shown when undone
+}
+
+/// Undo/redo stacks for zone list changes
+#[derive(Debug, Clone, Default)]
+pub struct ZoneHistory {
+    pub undo: Vec<ZoneSnapshot>,
+    pub redo: Vec<ZoneSnapshot>,
+}
+
 #[derive(Debug)]
 pub struct App {
     // Time management
@@ -79,6 +287,7 @@ pub struct App {
     // Zone management
     pub timezone_manager: TimeZoneManager,
     pub selected_zone_index: usize,
+    pub zone_history: ZoneHistory,
 
     // UI state
     pub display_format: TimeFormat,
@@ -94,8 +303,82 @@ pub struct App {
     pub rename_zone_input: String,
     pub show_date: bool,
     pub show_sun_times: bool,
+    pub timeline_zoom: TimelineZoom,
+    pub ruler_mode: RulerMode,
+    pub shading_mode: ShadingMode,
+    pub relative_offsets: RelativeOffsetMode,
+    pub show_info_panel: bool,
+    pub show_week_grid: bool,
+    pub filtering_zones: bool, // Typing a `/` filter
+    pub palette_open: bool,    // Typing a `:` command
+    pub palette_input: String,
+    pub selected_completion: usize,
+    pub zone_filter: String, // Narrows the visible zones while non-empty
+
+    // Calendar overlay
+    pub calendar: Calendar,
+    pub ics_files: Vec<String>,
+    pub session_ics_files: Vec<PathBuf>, // From --ics, not saved
+    pub export_dir: Option<String>,
+    pub event_overlay: EventOverlay,
+
+    // Meeting export
+    pub exporting: bool,
+    pub export_title_input: String,
+    pub export_duration_input: String,
+    pub export_field: ExportField,
+
+    // Work hours settings, previewed live on the timelines
+    pub show_settings: bool,
+    pub settings_row: usize, // Work ranges first, then awake ranges
+    pub settings_field: SettingsField,
+    pub settings_backup: TimeDisplayConfig, // Restored when the modal is cancelled
+
+    // Conversion table
+    pub show_table: bool,
+    pub table_date: NaiveDate, // In the selected zone
+    pub selected_table_row: usize,
+
+    // Bookmarks
+    pub bookmarks: Vec<Bookmark>,
+    pub show_bookmarks: bool,
+    pub selected_bookmark: usize,
+    pub adding_bookmark: bool,
+    pub bookmark_name_input: String,
+    pub bookmark_repeat: BookmarkRepeat,
+
+    // Countdowns
+    pub countdowns: Vec<Countdown>,
+    pub countdowns_checked_at: DateTime<Utc>, // Countdowns expiring after this haven't alerted yet
+    pub show_countdowns: bool,
+    pub selected_countdown: usize,
+    pub adding_countdown: bool,
+    pub countdown_name_input: String,
+    pub countdown_when_input: String,
+    pub countdown_field: CountdownField,
+    pub pending_bell: bool,
+
+    // Reminders
+    pub reminders: Vec<Reminder>,
+    pub reminder_notifications: bool,
+    pub reminders_checked_at: DateTime<Utc>, // Reminders due after this haven't been shown yet
+    pub due_reminders: Vec<String>,          // Shown in the banner until dismissed
+    pub notifier: Box<dyn Notifier>,
+
+    // Clipboard
+    pub yanking: bool,
+    pub pending_clipboard: Option<String>,
+
+    // Config file state
+    pub config_issues: Vec<String>,
+    pub config_writable: bool, // False when the file could not be read, so saving would lose settings
+    pub unresolved_zones: Vec<(usize, ZoneConfigCompat)>, // Unknown cities by list position, kept for saving
+    pub config_file: ConfigFile,
+    pub read_only: bool, // --no-save: settings changes last for this session only
+    pub pending_save: Option<Instant>, // When the last unsaved change was made
 
     // App state
+    pub status_message: Option<(String, Instant)>,
     pub should_quit: bool,
 }
 
@@ -107,6 +390,7 @@ impl Default for App {
             timeline_position: now,
             timezone_manager: TimeZoneManager::with_default_zones(),
             selected_zone_index: 0,
+            zone_history: ZoneHistory::default(),
             display_format: TimeFormat::TwentyFourHour,
             timezone_display_mode: TimezoneDisplayMode::Short,
             time_config: TimeDisplayConfig::default(),
@@ -120,20 +404,86 @@ impl Default for App {
             rename_zone_input: String::new(),
             show_date: false,
             show_sun_times: true,
+            calendar: Calendar::default(),
+            ics_files: Vec::new(),
+            session_ics_files: Vec::new(),
+            export_dir: None,
+            event_overlay: EventOverlay::default(),
+            timeline_zoom: TimelineZoom::default(),
+            ruler_mode: RulerMode::default(),
+            shading_mode: ShadingMode::default(),
+            relative_offsets: RelativeOffsetMode::default(),
+            show_info_panel: false,
+            show_week_grid: false,
+            filtering_zones: false,
+            palette_open: false,
+            palette_input: String::new(),
+            selected_completion: 0,
+            zone_filter: String::new(),
+            exporting: false,
+            export_title_input: String::new(),
+            export_duration_input: String::new(),
+            export_field: ExportField::Title,
+            show_settings: false,
+            settings_row: 0,
+            settings_field: SettingsField::Start,
+            settings_backup: TimeDisplayConfig::default(),
+            show_table: false,
+            table_date: now.date_naive(),
+            selected_table_row: 0,
+            bookmarks: Vec::new(),
+            show_bookmarks: false,
+            selected_bookmark: 0,
+            adding_bookmark: false,
+            bookmark_name_input: String::new(),
+            bookmark_repeat: BookmarkRepeat::Once,
+            countdowns: Vec::new(),
+            countdowns_checked_at: now,
+            show_countdowns: false,
+            selected_countdown: 0,
+            adding_countdown: false,
+            countdown_name_input: String::new(),
+            countdown_when_input: String::new(),
+            countdown_field: CountdownField::Name,
+            pending_bell: false,
+            reminders: Vec::new(),
+            reminder_notifications: false,
+            reminders_checked_at: now,
+            due_reminders: Vec::new(),
+            notifier: Box::new(DbusNotifier::default()),
+            yanking: false,
+            pending_clipboard: None,
+            config_issues: Vec::new(),
+            config_writable: true,
+            unresolved_zones: Vec::new(),
+            config_file: ConfigFile::default(),
+            read_only: false,
+            pending_save: None,
+            status_message: None,
             should_quit: false,
         }
     }
 }
 
 impl App {
+    #[cfg(test)]
     pub fn new() -> Self {
-        let config = AppConfig::load();
+        Self::load(false, &[])
+    }
+
+    /// Loads settings from the config file; a `read_only` app never writes to it (`--no-save`)
+    ///
+    /// `extra_ics` are calendar files given for this session only (`--ics`).
+    pub fn load(read_only: bool, extra_ics: &[PathBuf]) -> Self {
+        let loaded = AppConfig::load_reported();
+        let config = loaded.config;
+        let mut config_file = loaded.file;
 
         // Create default config file if it doesn't exist
-        if let Some(config_path) = AppConfig::config_path() {
-            if !config_path.exists() {
+        if let Some(config_path) = config_file.path().map(PathBuf::from) {
+            if !config_path.exists() && loaded.writable && !read_only {
                 // Only create if we successfully loaded default config
-                if config.save().is_err() {
+                if config_file.save(&config).is_err() {
                     // Don't fail if we can't save config, just continue with defaults
                     eprintln!(
                         "Warning: Could not create default config file at {}",
@@ -144,15 +494,45 @@ impl App {
         }
 
         let mut app = Self::from_config(config);
+        app.config_file = config_file;
+        app.config_issues = loaded
+            .issues
+            .iter()
+            .map(|issue| issue.to_string())
+            .collect();
+        app.config_writable = loaded.writable && !read_only;
+        app.read_only = read_only;
         app.select_local_timezone();
+        for error in app.load_calendars(extra_ics) {
+            eprintln!("{}", t!("cli.calendar_load_warning", error = error));
+        }
         app
     }
 
+    /// Loads the calendar files from the config plus any extra paths given for this session
+    pub fn load_calendars(&mut self, extra_paths: &[PathBuf]) -> Vec<String> {
+        self.session_ics_files = extra_paths.to_vec();
+        let paths: Vec<PathBuf> = self
+            .ics_files
+            .iter()
+            .map(|path| expand_home(path))
+            .chain(extra_paths.iter().cloned())
+            .collect();
+        let (calendar, errors) = Calendar::load_files(&paths);
+        self.calendar = calendar;
+        errors
+    }
+
     pub fn from_config(config: AppConfig) -> Self {
         let mut timezone_manager = TimeZoneManager::new();
 
-        // Load timezones from config with custom labels
-        for zone_config in &config.zones {
+        // Load timezones from config with custom labels, holding on to cities we don't know
+        let mut unresolved_zones = Vec::new();
+        for (index, zone_config) in config.zones.iter().enumerate() {
+            if !TimeZoneManager::is_known_city(zone_config.city_name()) {
+                unresolved_zones.push((index, zone_config.clone()));
+                continue;
+            }
             timezone_manager.add_timezone_with_label(
                 zone_config.city_name(),
                 zone_config.custom_label().map(|s| s.to_string()),
@@ -165,8 +545,12 @@ impl App {
         }
 
         let now = Utc::now();
-        let selected_zone_index = config
-            .selected_zone_index
+        // The file's index counts unknown cities too
+        let unresolved_before = unresolved_zones
+            .iter()
+            .filter(|(index, _)| *index < config.selected_zone_index)
+            .count();
+        let selected_zone_index = (config.selected_zone_index - unresolved_before)
             .min(timezone_manager.zone_count().saturating_sub(1));
 
         Self {
@@ -174,6 +558,7 @@ impl App {
             timeline_position: now,
             timezone_manager,
             selected_zone_index,
+            zone_history: ZoneHistory::default(),
             display_format: config.display_format,
             timezone_display_mode: config.timezone_display_mode,
             time_config: config.time_config,
@@ -187,56 +572,529 @@ impl App {
             rename_zone_input: String::new(),
             show_date: config.show_date,
             show_sun_times: config.show_sun_times,
+            calendar: Calendar::default(),
+            ics_files: config.ics_files,
+            session_ics_files: Vec::new(),
+            export_dir: config.export_dir,
+            event_overlay: config.event_overlay,
+            timeline_zoom: config.timeline_zoom,
+            ruler_mode: config.ruler_mode,
+            shading_mode: config.shading_mode,
+            relative_offsets: config.relative_offsets,
+            show_info_panel: config.show_info_panel,
+            show_week_grid: false,
+            filtering_zones: false,
+            palette_open: false,
+            palette_input: String::new(),
+            selected_completion: 0,
+            zone_filter: String::new(),
+            exporting: false,
+            export_title_input: String::new(),
+            export_duration_input: String::new(),
+            export_field: ExportField::Title,
+            show_settings: false,
+            settings_row: 0,
+            settings_field: SettingsField::Start,
+            settings_backup: TimeDisplayConfig::default(),
+            show_table: false,
+            table_date: now.date_naive(),
+            selected_table_row: 0,
+            bookmarks: config.bookmarks,
+            show_bookmarks: false,
+            selected_bookmark: 0,
+            adding_bookmark: false,
+            bookmark_name_input: String::new(),
+            bookmark_repeat: BookmarkRepeat::Once,
+            countdowns: config.countdowns,
+            countdowns_checked_at: now,
+            show_countdowns: false,
+            selected_countdown: 0,
+            adding_countdown: false,
+            countdown_name_input: String::new(),
+            countdown_when_input: String::new(),
+            countdown_field: CountdownField::Name,
+            pending_bell: false,
+            reminders: config.reminders,
+            reminder_notifications: config.reminder_notifications,
+            reminders_checked_at: now,
+            due_reminders: Vec::new(),
+            notifier: Box::new(DbusNotifier::default()),
+            yanking: false,
+            pending_clipboard: None,
+            config_issues: Vec::new(),
+            config_writable: true,
+            unresolved_zones,
+            config_file: ConfigFile::default(),
+            read_only: false,
+            pending_save: None,
+            status_message: None,
             should_quit: false,
         }
     }
 
     pub fn to_config(&self) -> AppConfig {
-        AppConfig {
-            zones: self
-                .timezone_manager
-                .zones()
-                .iter()
-                .map(|zone| {
-                    // Use the source_city if available, otherwise find the original search name
-                    let city_name = if let Some(source_city) = &zone.source_city {
-                        source_city.clone()
-                    } else {
-                        let available = TimeZoneManager::get_all_available_timezones();
-                        available
-                            .iter()
-                            .find(|(tz, _, _, _, _)| *tz == zone.tz)
-                            .map(|(_, search_name, _, _, _)| search_name.clone())
-                            .unwrap_or_else(|| zone.tz.to_string())
-                    };
+        let mut zones: Vec<ZoneConfigCompat> = self
+            .timezone_manager
+            .zones()
+            .iter()
+            .map(|zone| {
+                // Use the source_city if available, otherwise find the original search name
+                let city_name = if let Some(source_city) = &zone.source_city {
+                    source_city.clone()
+                } else {
+                    let available = TimeZoneManager::get_all_available_timezones();
+                    available
+                        .iter()
+                        .find(|(tz, _, _, _, _)| *tz == zone.tz)
+                        .map(|(_, search_name, _, _, _)| search_name.clone())
+                        .unwrap_or_else(|| zone.tz.to_string())
+                };
 
-                    // Save as full ZoneConfig if custom label is present, otherwise as simple string
-                    match &zone.custom_label {
-                        Some(label) => {
-                            crate::config::ZoneConfigCompat::Full(crate::config::ZoneConfig {
-                                city_name,
-                                custom_label: Some(label.clone()),
-                            })
-                        }
-                        None => crate::config::ZoneConfigCompat::Simple(city_name),
+                // Save as full ZoneConfig if custom label is present, otherwise as simple string
+                match &zone.custom_label {
+                    Some(label) => {
+                        crate::config::ZoneConfigCompat::Full(crate::config::ZoneConfig {
+                            city_name,
+                            custom_label: Some(label.clone()),
+                        })
                     }
-                })
-                .collect(),
-            selected_zone_index: self.selected_zone_index,
+                    None => crate::config::ZoneConfigCompat::Simple(city_name),
+                }
+            })
+            .collect();
+        // Unknown cities go back where they were, so saving neither reorders the list nor
+        // moves the selection to another zone
+        let mut selected_zone_index = self.selected_zone_index;
+        for (index, zone) in &self.unresolved_zones {
+            let index = (*index).min(zones.len());
+            if index <= selected_zone_index {
+                selected_zone_index += 1;
+            }
+            zones.insert(index, zone.clone());
+        }
+
+        AppConfig {
+            version: CONFIG_VERSION,
+            zones,
+            selected_zone_index,
             display_format: self.display_format.clone(),
             timezone_display_mode: self.timezone_display_mode.clone(),
             time_config: self.time_config.clone(),
             color_theme: self.color_theme,
             show_date: self.show_date,
             show_sun_times: self.show_sun_times,
+            ics_files: self.ics_files.clone(),
+            export_dir: self.export_dir.clone(),
+            event_overlay: self.event_overlay,
+            timeline_zoom: self.timeline_zoom,
+            ruler_mode: self.ruler_mode,
+            shading_mode: self.shading_mode,
+            relative_offsets: self.relative_offsets,
+            show_info_panel: self.show_info_panel,
+            bookmarks: self.bookmarks.clone(),
+            countdowns: self.countdowns.clone(),
+            reminders: self.reminders.clone(),
+            reminder_notifications: self.reminder_notifications,
         }
     }
 
-    pub fn save_config(&self) {
+    /// Schedules a save; changes are written once keys stop arriving for `SAVE_DELAY`
+    pub fn save_config(&mut self) {
+        if self.config_writable {
+            self.pending_save = Some(Instant::now());
+        }
+    }
+
+    /// Writes any scheduled save now
+    pub fn flush_config(&mut self) {
+        if self.pending_save.take().is_none() {
+            return;
+        }
         let config = self.to_config();
-        if let Err(e) = config.save() {
-            // In a real app, you might want to show an error message to the user
-            eprintln!("Failed to save config: {e}");
+        match self.config_file.save(&config) {
+            Ok(SaveOutcome::Merged) => {
+                self.set_status("Config file was changed elsewhere; merged your changes into it")
+            }
+            Ok(_) => {}
+            Err(e) => self.set_status(format!("Failed to save config: {e}")),
+        }
+    }
+
+    /// Records the zone list from before a change so `u` can bring it back
+    fn record_zone_change(
+        &mut self,
+        zones: TimeZoneManager,
+        selected_zone_index: usize,
+        action: String,
+    ) {
+        let history = &mut self.zone_history;
+        history.undo.push(ZoneSnapshot {
+            zones,
+            selected_zone_index,
+            action,
+        });
+        if history.undo.len() > MAX_ZONE_HISTORY {
+            history.undo.remove(0);
+        }
+        history.redo.clear();
+    }
+
+    /// Steps back (or forward again) through zone changes
+    fn step_zone_history(&mut self, undo: bool) {
+        let (from, to) = if undo {
+            (&mut self.zone_history.undo, &mut self.zone_history.redo)
+        } else {
+            (&mut self.zone_history.redo, &mut self.zone_history.undo)
+        };
+        let Some(snapshot) = from.pop() else {
+            self.set_status(if undo {
+                "Nothing to undo"
+            } else {
+                "Nothing to redo"
+            });
+            return;
+        };
+
+        to.push(ZoneSnapshot {
+            zones: std::mem::replace(&mut self.timezone_manager, snapshot.zones),
+            selected_zone_index: self.selected_zone_index,
+            action: snapshot.action.clone(),
+        });
+        self.selected_zone_index = snapshot
+            .selected_zone_index
+            .min(self.timezone_manager.zone_count().saturating_sub(1));
+        self.keep_selection_visible();
+        self.save_config();
+        let verb = if undo { "Undid" } else { "Redid" };
+        self.set_status(format!("{verb} {}", snapshot.action));
+    }
+
+    /// Indices of the zones the `/` filter leaves visible, in list order
+    pub fn visible_zone_indices(&self) -> Vec<usize> {
+        self.timezone_manager
+            .zones()
+            .iter()
+            .enumerate()
+            .filter(|(_, zone)| self.zone_filter.is_empty() || zone.matches(&self.zone_filter))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Moves the selection to the next visible zone if the filter hides it
+    fn keep_selection_visible(&mut self) {
+        let visible = self.visible_zone_indices();
+        if visible.is_empty() || visible.contains(&self.selected_zone_index) {
+            return;
+        }
+        self.selected_zone_index = visible
+            .iter()
+            .find(|index| **index > self.selected_zone_index)
+            .or(visible.last())
+            .copied()
+            .unwrap_or(self.selected_zone_index);
+    }
+
+    /// Shows a transient message in the footer
+    pub fn set_status(&mut self, message: impl Into<String>) {
+        self.status_message = Some((message.into(), Instant::now()));
+    }
+
+    fn export_invite(&mut self) {
+        let Some(minutes) = self
+            .export_duration_input
+            .trim()
+            .parse::<i64>()
+            .ok()
+            .filter(|minutes| *minutes > 0)
+        else {
+            self.set_status(format!(
+                "Invalid duration '{}' (minutes)",
+                self.export_duration_input
+            ));
+            return;
+        };
+
+        let use_12_hour = matches!(self.display_format, TimeFormat::TwelveHour);
+        let start = self
+            .timeline_position
+            .with_second(0)
+            .and_then(|t| t.with_nanosecond(0))
+            .unwrap_or(self.timeline_position);
+        let summary = self
+            .timezone_manager
+            .format_multi_zone_summary(start, use_12_hour);
+        let title = self.export_title_input.trim();
+        let invite = Invite {
+            title: if title.is_empty() { "Meeting" } else { title }.to_string(),
+            start,
+            duration: chrono::Duration::minutes(minutes),
+            description: Some(summary.clone()),
+        };
+
+        let dir = self.export_directory();
+        match std::fs::create_dir_all(&dir).and_then(|()| invite.write_new(&dir)) {
+            Ok(path) => {
+                self.set_status(format!("Saved {} · copied {summary}", path.display()));
+                self.pending_clipboard = Some(summary);
+            }
+            Err(e) => self.set_status(format!(
+                "Could not write {}: {e}",
+                dir.join(invite.default_file_name()).display()
+            )),
+        }
+        self.exporting = false;
+    }
+
+    /// Where `x` writes invites: `export_dir`, else beside the config file, else the home
+    /// directory (never the working directory, which for a TUI is wherever it was started)
+    fn export_directory(&self) -> PathBuf {
+        self.export_dir
+            .as_deref()
+            .map(expand_home)
+            .or_else(|| {
+                self.config_file
+                    .path()
+                    .and_then(Path::parent)
+                    .map(Path::to_path_buf)
+            })
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    /// Alerts for countdowns that expired since the last check
+    fn check_countdowns(&mut self) {
+        let (from, to) = (self.countdowns_checked_at, self.current_time);
+        let expired: Vec<(String, bool)> = self
+            .countdowns
+            .iter()
+            .filter(|countdown| countdown.instant().is_some_and(|at| from < at && at <= to))
+            .map(|countdown| (countdown.name.clone(), countdown.bell))
+            .collect();
+        self.countdowns_checked_at = to;
+
+        if let Some((name, _)) = expired.first() {
+            self.set_status(format!("⏰ {name} is due"));
+        }
+        if expired.iter().any(|(_, bell)| *bell) {
+            self.pending_bell = true;
+        }
+    }
+
+    /// Shows (and optionally sends as notifications) reminders that came due since the last check
+    fn check_reminders(&mut self) {
+        let to = self.current_time;
+        let from = self.reminders_checked_at.max(to - REMINDER_GRACE);
+        self.reminders_checked_at = to;
+
+        let due: Vec<&Reminder> = self
+            .reminders
+            .iter()
+            .filter(|reminder| {
+                reminder
+                    .occurrences_between(from, to)
+                    .iter()
+                    .any(|at| *at > from)
+            })
+            .collect();
+        let mut failures = Vec::new();
+        for reminder in &due {
+            let when = format!("{} {}", reminder.time.format("%H:%M"), reminder.zone);
+            if self.reminder_notifications {
+                if let Err(e) = self.notifier.notify(&reminder.message, &when) {
+                    failures.push(e);
+                }
+            }
+            self.due_reminders
+                .push(format!("⏰ {when} · {}", reminder.message));
+        }
+        if let Some(e) = failures.first() {
+            self.set_status(format!("Could not send notification: {e}"));
+        }
+    }
+
+    /// Adds a countdown from the modal, at a local time in the selected zone
+    fn add_countdown(&mut self) {
+        let name = self.countdown_name_input.trim().to_string();
+        if name.is_empty() {
+            self.set_status("Countdown needs a name");
+            return;
+        }
+
+        let zone = self.timezone_manager.zones().get(self.selected_zone_index);
+        let tz = zone.map_or(chrono_tz::UTC, |zone| zone.tz);
+        let today = self.current_time.with_timezone(&tz).date_naive();
+        let Some(at) =
+            crate::time::parse_datetime_in_zone(&self.countdown_when_input, Some(tz), today)
+        else {
+            self.set_status(format!(
+                "Invalid time '{}' (YYYY-MM-DD HH:MM)",
+                self.countdown_when_input
+            ));
+            return;
+        };
+
+        let zone_name = zone.map_or_else(|| "UTC".to_string(), |zone| zone.config_zone_name());
+        let countdown = Countdown::in_zone(name, at.with_timezone(&tz).naive_local(), zone_name);
+        self.set_status(format!(
+            "Counting down to '{}' in {}",
+            countdown.name,
+            format_remaining(at - self.current_time)
+        ));
+        self.countdowns.push(countdown);
+        self.adding_countdown = false;
+        self.save_config();
+    }
+
+    /// A bookmark for the scrubbed minute, repeating in the selected zone as chosen in the modal
+    fn bookmark_at_scrub(&self, name: String) -> Option<Bookmark> {
+        let at = self
+            .timeline_position
+            .with_second(0)
+            .and_then(|t| t.with_nanosecond(0))
+            .unwrap_or(self.timeline_position);
+        if self.bookmark_repeat == BookmarkRepeat::Once {
+            return Some(Bookmark::once(name, at));
+        }
+
+        let zone = self
+            .timezone_manager
+            .zones()
+            .get(self.selected_zone_index)?;
+        let local = at.with_timezone(&zone.tz);
+        let days = match self.bookmark_repeat {
+            BookmarkRepeat::Weekly => vec![local.weekday()],
+            _ => Vec::new(),
+        };
+        Some(Bookmark::recurring(
+            name,
+            local.time(),
+            zone.config_zone_name(),
+            days,
+        ))
+    }
+
+    /// Formats the scrubbed instant for copying to the clipboard
+    pub fn yank_text(&self, format: YankFormat) -> String {
+        let instant = self.timeline_position;
+        let use_12_hour = matches!(self.display_format, TimeFormat::TwelveHour);
+        match format {
+            YankFormat::IsoUtc => instant.to_rfc3339_opts(SecondsFormat::Secs, true),
+            YankFormat::SelectedLocal => {
+                match self.timezone_manager.zones().get(self.selected_zone_index) {
+                    Some(zone) => {
+                        let local = instant.with_timezone(&zone.tz);
+                        if use_12_hour {
+                            local.format("%Y-%m-%d %-I:%M %p %Z").to_string()
+                        } else {
+                            local.format("%Y-%m-%d %H:%M %Z").to_string()
+                        }
+                    }
+                    None => instant.to_rfc3339_opts(SecondsFormat::Secs, true),
+                }
+            }
+            YankFormat::UnixTimestamp => instant.timestamp().to_string(),
+            YankFormat::MultiZone => self
+                .timezone_manager
+                .format_multi_zone_summary(instant, use_12_hour),
+        }
+    }
+
+    /// Whether a modal or text input is open
+    fn modal_open(&self) -> bool {
+        self.show_help
+            || self.adding_zone
+            || self.renaming_zone
+            || self.yanking
+            || self.exporting
+            || self.adding_bookmark
+            || self.palette_open
+            || self.filtering_zones
+            || self.show_settings
+            || self.show_table
+            || self.show_bookmarks
+            || self.adding_countdown
+            || self.show_countdowns
+    }
+
+    /// Picks up edits made to the config file while running
+    fn reload_config_if_changed(&mut self) {
+        let Some(content) = self.config_file.changed_on_disk() else {
+            return;
+        };
+        let Some(path) = self.config_file.path().map(PathBuf::from) else {
+            return;
+        };
+
+        let loaded = AppConfig::parse(&content, &path);
+        self.config_issues = loaded
+            .issues
+            .iter()
+            .map(|issue| issue.to_string())
+            .collect();
+        if !loaded.parsed {
+            // Probably saved half-way through an edit: keep running with what we have
+            self.config_file.acknowledge(content);
+            return;
+        }
+
+        self.apply_config(loaded.config);
+        self.config_file = loaded.file;
+        self.config_writable = loaded.writable && !self.read_only;
+        self.set_status("Reloaded config file");
+    }
+
+    /// Takes zones and settings from `config`, keeping the selection on the same zone if it's still there
+    pub fn apply_config(&mut self, config: AppConfig) {
+        let selected = self
+            .timezone_manager
+            .zones()
+            .get(self.selected_zone_index)
+            .map(|zone| zone.display_name.clone());
+        let ics_changed = config.ics_files != self.ics_files;
+        let fresh = Self::from_config(config);
+
+        self.selected_zone_index = selected
+            .and_then(|code| {
+                fresh
+                    .timezone_manager
+                    .zones()
+                    .iter()
+                    .position(|zone| zone.display_name == code)
+            })
+            .unwrap_or(fresh.selected_zone_index);
+        self.timezone_manager = fresh.timezone_manager;
+        self.unresolved_zones = fresh.unresolved_zones;
+        self.display_format = fresh.display_format;
+        self.timezone_display_mode = fresh.timezone_display_mode;
+        self.time_config = fresh.time_config;
+        self.color_theme = fresh.color_theme;
+        self.show_date = fresh.show_date;
+        self.show_sun_times = fresh.show_sun_times;
+        self.event_overlay = fresh.event_overlay;
+        self.timeline_zoom = fresh.timeline_zoom;
+        self.ruler_mode = fresh.ruler_mode;
+        self.shading_mode = fresh.shading_mode;
+        self.relative_offsets = fresh.relative_offsets;
+        self.show_info_panel = fresh.show_info_panel;
+        self.ics_files = fresh.ics_files;
+        self.export_dir = fresh.export_dir;
+        self.bookmarks = fresh.bookmarks;
+        self.countdowns = fresh.countdowns;
+        self.reminders = fresh.reminders;
+        self.reminder_notifications = fresh.reminder_notifications;
+        self.selected_countdown = self
+            .selected_countdown
+            .min(self.countdowns.len().saturating_sub(1));
+        self.selected_bookmark = self
+            .selected_bookmark
+            .min(self.bookmarks.len().saturating_sub(1));
+        // Undoing would bring back the zones from before the file changed, losing the edit
+        self.zone_history = ZoneHistory::default();
+        if ics_changed {
+            let session_files = self.session_ics_files.clone();
+            for error in self.load_calendars(&session_files) {
+                self.set_status(format!("Could not load calendar {error}"));
+            }
         }
     }
 
@@ -259,42 +1117,49 @@ impl App {
         match msg {
             Message::Tick => {
                 self.current_time = Utc::now();
+                self.check_countdowns();
+                self.check_reminders();
+                // The settings modal previews unsaved hours: nothing is saved under it
+                if !self.show_settings
+                    && self
+                        .pending_save
+                        .is_some_and(|changed_at| changed_at.elapsed() >= SAVE_DELAY)
+                {
+                    self.flush_config();
+                }
+                // Reloading would change what an open modal or input is working on
+                if self.pending_save.is_none() && !self.modal_open() {
+                    self.reload_config_if_changed();
+                }
+                if self
+                    .status_message
+                    .as_ref()
+                    .is_some_and(|(_, shown_at)| shown_at.elapsed() >= STATUS_MESSAGE_DURATION)
+                {
+                    self.status_message = None;
+                }
                 None
             }
 
             Message::ScrubTimeline(direction) => {
-                // Round to the next/previous hour boundary
+                // Round to the next/previous cell boundary (an hour unless zoomed in)
+                let step = chrono::Duration::minutes(self.timeline_zoom.cell_minutes());
+                let timestamp = self.timeline_position.timestamp();
+                let cell_start = DateTime::from_timestamp(
+                    timestamp - timestamp.rem_euclid(step.num_seconds()),
+                    0,
+                )
+                .unwrap_or(self.timeline_position);
                 let rounded_time = match direction {
                     Direction::Left => {
-                        // Go to the start of the current hour, or previous hour if already at start
-                        if self.timeline_position.minute() == 0
-                            && self.timeline_position.second() == 0
-                            && self.timeline_position.nanosecond() == 0
-                        {
-                            // Already at hour boundary, go to previous hour
-                            self.timeline_position - chrono::Duration::hours(1)
+                        if cell_start == self.timeline_position {
+                            // Already at a boundary, go to the previous one
+                            self.timeline_position - step
                         } else {
-                            // Go to start of current hour
-                            self.timeline_position
-                                .with_minute(0)
-                                .unwrap_or(self.timeline_position)
-                                .with_second(0)
-                                .unwrap_or(self.timeline_position)
-                                .with_nanosecond(0)
-                                .unwrap_or(self.timeline_position)
+                            cell_start
                         }
                     }
-                    Direction::Right => {
-                        // Go to the start of the next hour
-                        self.timeline_position
-                            .with_minute(0)
-                            .unwrap_or(self.timeline_position)
-                            .with_second(0)
-                            .unwrap_or(self.timeline_position)
-                            .with_nanosecond(0)
-                            .unwrap_or(self.timeline_position)
-                            + chrono::Duration::hours(1)
-                    }
+                    Direction::Right => cell_start + step,
                     _ => self.timeline_position,
                 };
                 self.timeline_position = rounded_time;
@@ -324,19 +1189,19 @@ impl App {
             }
 
             Message::NavigateZone(direction) => {
-                let zone_count = self.timezone_manager.zone_count();
-                if zone_count > 0 {
+                // Step through the visible zones, which are all of them unless filtered
+                let visible = self.visible_zone_indices();
+                if let Some(position) = visible
+                    .iter()
+                    .position(|index| *index == self.selected_zone_index)
+                {
                     let old_index = self.selected_zone_index;
                     match direction {
-                        Direction::Up => {
-                            if self.selected_zone_index > 0 {
-                                self.selected_zone_index -= 1;
-                            }
+                        Direction::Up if position > 0 => {
+                            self.selected_zone_index = visible[position - 1];
                         }
-                        Direction::Down => {
-                            if self.selected_zone_index < zone_count - 1 {
-                                self.selected_zone_index += 1;
-                            }
+                        Direction::Down if position + 1 < visible.len() => {
+                            self.selected_zone_index = visible[position + 1];
                         }
                         _ => {}
                     }
@@ -344,94 +1209,508 @@ impl App {
                     if old_index != self.selected_zone_index {
                         self.save_config();
                     }
+                } else if let Some(first) = visible.first() {
+                    self.selected_zone_index = *first;
                 }
                 None
             }
 
-            Message::ToggleTimeFormat => {
-                self.display_format = match self.display_format {
-                    TimeFormat::TwentyFourHour => TimeFormat::TwelveHour,
-                    TimeFormat::TwelveHour => TimeFormat::TwentyFourHour,
-                };
-                self.save_config();
+            Message::StartPalette => {
+                self.palette_open = true;
+                self.palette_input.clear();
+                self.selected_completion = 0;
                 None
             }
 
-            Message::ToggleTimezoneDisplayMode => {
-                self.timezone_display_mode = match self.timezone_display_mode {
-                    TimezoneDisplayMode::Short => TimezoneDisplayMode::Full,
-                    TimezoneDisplayMode::Full => TimezoneDisplayMode::Short,
-                };
-                self.save_config();
+            Message::UpdatePaletteInput(input) => {
+                self.palette_input = input;
+                self.selected_completion = 0;
                 None
             }
 
-            Message::ToggleDate => {
-                self.show_date = !self.show_date;
-                self.save_config();
+            Message::NavigatePalette(direction) => {
+                let count = command::completions(&self.palette_input).len();
+                match direction {
+                    Direction::Up if self.selected_completion > 0 => self.selected_completion -= 1,
+                    Direction::Down if self.selected_completion + 1 < count => {
+                        self.selected_completion += 1
+                    }
+                    _ => {}
+                }
                 None
             }
 
-            Message::ToggleSunTimes => {
-                self.show_sun_times = !self.show_sun_times;
-                self.save_config();
+            Message::CompletePalette => {
+                if let Some(spec) =
+                    command::completions(&self.palette_input).get(self.selected_completion)
+                {
+                    self.palette_input = if spec.args.is_empty() {
+                        spec.name.to_string()
+                    } else {
+                        format!("{} ", spec.name)
+                    };
+                    self.selected_completion = 0;
+                }
                 None
             }
 
-            Message::CycleColorTheme => {
-                self.color_theme = self.color_theme.next();
-                self.save_config();
+            Message::RunPalette => {
+                // A partly typed command name runs (or completes to) the highlighted command
+                let typed_name = self.palette_input.split_whitespace().next().unwrap_or("");
+                if !command::COMMANDS.iter().any(|spec| spec.name == typed_name) {
+                    let Some(spec) = command::completions(&self.palette_input)
+                        .get(self.selected_completion)
+                        .copied()
+                    else {
+                        self.set_status(format!("Unknown command '{typed_name}'"));
+                        return None;
+                    };
+                    if !spec.args.is_empty() {
+                        return self.update(Message::CompletePalette);
+                    }
+                    self.palette_input = spec.name.to_string();
+                }
+
+                match command::parse(&self.palette_input) {
+                    Ok(message) => {
+                        self.palette_open = false;
+                        self.palette_input.clear();
+                        self.update(message);
+                    }
+                    Err(error) => self.set_status(error),
+                }
                 None
             }
 
-            Message::ToggleHelp => {
-                self.show_help = !self.show_help;
+            Message::CancelPalette => {
+                self.palette_open = false;
+                self.palette_input.clear();
                 None
             }
 
-            Message::StartAddZone => {
-                // Clear other modal states
-                self.renaming_zone = false;
-                self.rename_zone_input.clear();
-
-                self.adding_zone = true;
-                self.add_zone_input.clear();
-                self.zone_search_results.clear();
-                self.selected_search_result = 0;
+            Message::AddZoneNamed { name, label } => {
+                let before = self.timezone_manager.clone();
+                let added = match label {
+                    Some(label) => self
+                        .timezone_manager
+                        .add_timezone_with_label(&name, Some(label)),
+                    None => self.timezone_manager.add_timezone_by_name(&name),
+                };
+                if added {
+                    self.record_zone_change(
+                        before,
+                        self.selected_zone_index,
+                        format!("add {name}"),
+                    );
+                    self.save_config();
+                    self.set_status(format!("Added {name}"));
+                } else {
+                    self.set_status(format!("Could not add '{name}' (unknown or already shown)"));
+                }
                 None
             }
 
-            Message::UpdateAddZoneInput(input) => {
-                self.add_zone_input = input.clone();
-                self.zone_search_results = crate::time::TimeZoneManager::search_timezones(&input);
-                self.selected_search_result = 0; // Reset selection when search changes
-                None
+            Message::RenameZoneTo(label) => {
+                self.rename_zone_input = label;
+                self.update(Message::ConfirmRename)
             }
 
-            Message::NavigateSearchResults(direction) => {
-                if !self.zone_search_results.is_empty() {
-                    match direction {
-                        Direction::Up => {
-                            if self.selected_search_result > 0 {
-                                self.selected_search_result -= 1;
-                            }
-                        }
-                        Direction::Down => {
-                            if self.selected_search_result < self.zone_search_results.len() - 1 {
-                                self.selected_search_result += 1;
-                            }
-                        }
-                        _ => {}
-                    }
+            Message::GotoTime(input) => {
+                let tz = self
+                    .timezone_manager
+                    .zones()
+                    .get(self.selected_zone_index)
+                    .map_or(chrono_tz::UTC, |zone| zone.tz);
+                match command::parse_goto(&input, tz, self.current_time, self.timeline_position) {
+                    Some(at) => self.timeline_position = at,
+                    None => self.set_status(format!("Could not understand time '{input}'")),
                 }
                 None
             }
 
-            Message::SelectSearchResult(index) => {
+            Message::SetColorTheme(theme) => {
+                self.color_theme = theme;
+                self.save_config();
+                None
+            }
+
+            Message::SetWorkHours(ranges) => {
+                self.set_status(format!("Work hours: {}", format_ranges(&ranges)));
+                self.time_config.work_hours = ranges;
+                self.save_config();
+                None
+            }
+
+            Message::SetAwakeHours(ranges) => {
+                self.set_status(format!("Awake hours: {}", format_ranges(&ranges)));
+                self.time_config.awake_hours = ranges;
+                self.save_config();
+                None
+            }
+
+            Message::OpenSettings => {
+                self.settings_backup = self.time_config.clone();
+                self.settings_row = 0;
+                self.settings_field = SettingsField::Start;
+                self.show_settings = true;
+                None
+            }
+
+            Message::NavigateSettings(direction) => {
+                let rows = self.settings_rows().len();
+                if rows == 0 {
+                    return None;
+                }
+                self.settings_row = match direction {
+                    Direction::Up | Direction::Left => (self.settings_row + rows - 1) % rows,
+                    Direction::Down | Direction::Right => (self.settings_row + 1) % rows,
+                };
+                None
+            }
+
+            Message::SwitchSettingsField => {
+                self.settings_field = match self.settings_field {
+                    SettingsField::Start => SettingsField::End,
+                    SettingsField::End => SettingsField::Start,
+                };
+                None
+            }
+
+            Message::AdjustSetting(minutes) => {
+                let field = self.settings_field;
+                let range = self.settings_range_mut()?;
+                // A range can't turn inside out or empty: daytime ranges stay daytime, overnight
+                // ones overnight, and start and end stay at least a minute apart
+                let (min, max) = match (field, range.is_overnight()) {
+                    (SettingsField::Start, false) => (ClockTime::new(0, 0), range.end.shifted(-1)),
+                    (SettingsField::End, false) => (range.start.shifted(1), ClockTime::new(24, 0)),
+                    (SettingsField::Start, true) => (range.end.shifted(1), ClockTime::new(24, 0)),
+                    (SettingsField::End, true) => (ClockTime::new(0, 0), range.start.shifted(-1)),
+                };
+                let time = match field {
+                    SettingsField::Start => &mut range.start,
+                    SettingsField::End => &mut range.end,
+                };
+                *time = time.shifted(minutes).clamp(min, max);
+                None
+            }
+
+            Message::AddSettingsRange(activity) => {
+                let config = &mut self.time_config;
+                let (ranges, first_row) = match activity {
+                    TimeActivity::Work => (&mut config.work_hours, 0),
+                    _ => (&mut config.awake_hours, config.work_hours.len()),
+                };
+                // An hour starting where the last range ends, ready to stretch into the next shift
+                let start = ranges
+                    .last()
+                    .map_or(ClockTime::new(9, 0), |range| range.end)
+                    .min(ClockTime::new(23, 0));
+                ranges.push(TimeRange::new(start, start.shifted(60)));
+                self.settings_row = first_row + ranges.len() - 1;
+                self.settings_field = SettingsField::End;
+                None
+            }
+
+            Message::DeleteSettingsRange => {
+                let config = &mut self.time_config;
+                let work = config.work_hours.len();
+                if self.settings_row < work {
+                    config.work_hours.remove(self.settings_row);
+                } else if self.settings_row - work < config.awake_hours.len() {
+                    config.awake_hours.remove(self.settings_row - work);
+                }
+                self.settings_row = self
+                    .settings_row
+                    .min(self.settings_rows().len().saturating_sub(1));
+                None
+            }
+
+            Message::SaveSettings => {
+                self.show_settings = false;
+                let config = &mut self.time_config;
+                for ranges in [&mut config.work_hours, &mut config.awake_hours] {
+                    ranges.retain(|range| range.minutes() > 0);
+                    ranges.sort_by_key(|range| range.start);
+                }
+                if self.time_config != self.settings_backup {
+                    self.save_config();
+                    self.set_status(format!(
+                        "Work hours: {}; awake: {}",
+                        format_ranges(&self.time_config.work_hours),
+                        format_ranges(&self.time_config.awake_hours)
+                    ));
+                }
+                None
+            }
+
+            Message::CancelSettings => {
+                self.time_config = self.settings_backup.clone();
+                self.show_settings = false;
+                None
+            }
+
+            Message::StartZoneFilter => {
+                self.filtering_zones = true;
+                self.zone_filter.clear();
+                None
+            }
+
+            Message::UpdateZoneFilter(filter) => {
+                self.zone_filter = filter;
+                self.keep_selection_visible();
+                None
+            }
+
+            Message::ConfirmZoneFilter => {
+                self.filtering_zones = false;
+                if !self.zone_filter.is_empty() && self.visible_zone_indices().is_empty() {
+                    self.set_status(format!("No zones match '{}'", self.zone_filter));
+                    self.zone_filter.clear();
+                }
+                None
+            }
+
+            Message::ClearZoneFilter => {
+                self.filtering_zones = false;
+                self.zone_filter.clear();
+                None
+            }
+
+            Message::NextFilterMatch(direction) => {
+                let visible = self.visible_zone_indices();
+                if visible.is_empty() {
+                    return None;
+                }
+                // Wrap around like vim's n/N
+                let next = match direction {
+                    Direction::Up => visible
+                        .iter()
+                        .rev()
+                        .find(|index| **index < self.selected_zone_index)
+                        .or(visible.last()),
+                    _ => visible
+                        .iter()
+                        .find(|index| **index > self.selected_zone_index)
+                        .or(visible.first()),
+                };
+                if let Some(next) = next {
+                    if *next != self.selected_zone_index {
+                        self.selected_zone_index = *next;
+                        self.save_config();
+                    }
+                }
+                None
+            }
+
+            Message::ToggleTimeFormat => {
+                self.display_format = match self.display_format {
+                    TimeFormat::TwentyFourHour => TimeFormat::TwelveHour,
+                    TimeFormat::TwelveHour => TimeFormat::TwentyFourHour,
+                };
+                self.save_config();
+                None
+            }
+
+            Message::ToggleTimezoneDisplayMode => {
+                self.timezone_display_mode = match self.timezone_display_mode {
+                    TimezoneDisplayMode::Short => TimezoneDisplayMode::Full,
+                    TimezoneDisplayMode::Full => TimezoneDisplayMode::Short,
+                };
+                self.save_config();
+                None
+            }
+
+            Message::ToggleDate => {
+                self.show_date = !self.show_date;
+                self.save_config();
+                None
+            }
+
+            Message::ToggleSunTimes => {
+                self.show_sun_times = !self.show_sun_times;
+                self.save_config();
+                None
+            }
+
+            Message::CycleColorTheme => {
+                self.color_theme = self.color_theme.next();
+                self.save_config();
+                None
+            }
+
+            Message::CycleEventOverlay => {
+                self.event_overlay = self.event_overlay.next();
+                self.save_config();
+                None
+            }
+
+            Message::CycleShadingMode => {
+                self.shading_mode = self.shading_mode.next();
+                self.save_config();
+                None
+            }
+
+            Message::DismissConfigIssues => {
+                self.config_issues.clear();
+                None
+            }
+
+            Message::DismissReminders => {
+                self.due_reminders.clear();
+                None
+            }
+
+            Message::CycleRelativeOffsets => {
+                self.relative_offsets = self.relative_offsets.next();
+                self.set_status(format!(
+                    "Relative offsets: {}",
+                    self.relative_offsets.label()
+                ));
+                self.save_config();
+                None
+            }
+
+            Message::ToggleInfoPanel => {
+                self.show_info_panel = !self.show_info_panel;
+                self.save_config();
+                None
+            }
+
+            Message::ToggleWeekGrid => {
+                self.show_week_grid = !self.show_week_grid;
+                None
+            }
+
+            Message::MoveWeekGridCursor(direction) => {
+                let zone = self
+                    .timezone_manager
+                    .zones()
+                    .get(self.selected_zone_index)?;
+                let (hours, days) = match direction {
+                    Direction::Left => (-1, 0),
+                    Direction::Right => (1, 0),
+                    Direction::Up => (0, -1),
+                    Direction::Down => (0, 1),
+                };
+                self.timeline_position =
+                    shift_local_hour(self.timeline_position, zone.tz, hours, days);
+                None
+            }
+
+            Message::ToggleTable => {
+                if self.show_table {
+                    self.show_table = false;
+                    return None;
+                }
+                // Open on the scrubbed hour of the selected zone's day
+                let tz = self.table_zones().first()?.tz;
+                self.table_date = self.timeline_position.with_timezone(&tz).date_naive();
+                self.selected_table_row = table::day_hours(tz, self.table_date)
+                    .iter()
+                    .rposition(|hour| *hour <= self.timeline_position)
+                    .unwrap_or(0);
+                self.show_table = true;
+                None
+            }
+
+            Message::NavigateTable(direction) => {
+                match direction {
+                    Direction::Up => {
+                        self.selected_table_row = self.selected_table_row.saturating_sub(1)
+                    }
+                    Direction::Down => self.selected_table_row += 1,
+                    Direction::Left => self.table_date = self.table_date.pred_opt()?,
+                    Direction::Right => self.table_date = self.table_date.succ_opt()?,
+                }
+                let tz = self.table_zones().first()?.tz;
+                let rows = table::day_hours(tz, self.table_date).len();
+                self.selected_table_row = self.selected_table_row.min(rows.saturating_sub(1));
+                None
+            }
+
+            Message::JumpToTableRow => {
+                let tz = self.table_zones().first()?.tz;
+                if let Some(hour) =
+                    table::day_hours(tz, self.table_date).get(self.selected_table_row)
+                {
+                    self.timeline_position = *hour;
+                    self.show_table = false;
+                }
+                None
+            }
+
+            Message::CycleRulerMode => {
+                self.ruler_mode = self.ruler_mode.next();
+                self.save_config();
+                None
+            }
+
+            Message::ZoomIn | Message::ZoomOut | Message::ResetZoom => {
+                let zoom = match msg {
+                    Message::ZoomIn => self.timeline_zoom.zoom_in(),
+                    Message::ZoomOut => self.timeline_zoom.zoom_out(),
+                    _ => TimelineZoom::Auto,
+                };
+                if zoom != self.timeline_zoom {
+                    self.timeline_zoom = zoom;
+                    self.save_config();
+                }
+                None
+            }
+
+            Message::ToggleHelp => {
+                self.show_help = !self.show_help;
+                None
+            }
+
+            Message::StartAddZone => {
+                // Clear other modal states
+                self.renaming_zone = false;
+                self.rename_zone_input.clear();
+
+                self.adding_zone = true;
+                self.add_zone_input.clear();
+                self.zone_search_results.clear();
+                self.selected_search_result = 0;
+                None
+            }
+
+            Message::UpdateAddZoneInput(input) => {
+                self.add_zone_input = input.clone();
+                self.zone_search_results = crate::time::TimeZoneManager::search_timezones(&input);
+                self.selected_search_result = 0; // Reset selection when search changes
+                None
+            }
+
+            Message::NavigateSearchResults(direction) => {
+                if !self.zone_search_results.is_empty() {
+                    match direction {
+                        Direction::Up if self.selected_search_result > 0 => {
+                            self.selected_search_result -= 1;
+                        }
+                        Direction::Down
+                            if self.selected_search_result < self.zone_search_results.len() - 1 =>
+                        {
+                            self.selected_search_result += 1;
+                        }
+                        _ => {}
+                    }
+                }
+                None
+            }
+
+            Message::SelectSearchResult(index) => {
                 if let Some(zone_name) = self.zone_search_results.get(index) {
+                    let before = self.timezone_manager.clone();
                     let success = self.timezone_manager.add_timezone_by_name(zone_name);
 
                     if success {
+                        self.record_zone_change(
+                            before,
+                            self.selected_zone_index,
+                            format!("add {zone_name}"),
+                        );
                         // Update selected index if needed
                         if self.selected_zone_index >= self.timezone_manager.zone_count() {
                             self.selected_zone_index =
@@ -452,9 +1731,15 @@ impl App {
                     if let Some(zone_name) =
                         self.zone_search_results.get(self.selected_search_result)
                     {
+                        let before = self.timezone_manager.clone();
                         let success = self.timezone_manager.add_timezone_by_name(zone_name);
 
                         if success {
+                            self.record_zone_change(
+                                before,
+                                self.selected_zone_index,
+                                format!("add {zone_name}"),
+                            );
                             // Update selected index if needed
                             if self.selected_zone_index >= self.timezone_manager.zone_count() {
                                 self.selected_zone_index =
@@ -465,11 +1750,17 @@ impl App {
                     }
                 } else if !self.add_zone_input.is_empty() {
                     // Try to add the exact input if no search results
+                    let before = self.timezone_manager.clone();
                     let success = self
                         .timezone_manager
                         .add_timezone_by_name(&self.add_zone_input);
 
                     if success {
+                        self.record_zone_change(
+                            before,
+                            self.selected_zone_index,
+                            format!("add {}", self.add_zone_input),
+                        );
                         // Update selected index if needed
                         if self.selected_zone_index >= self.timezone_manager.zone_count() {
                             self.selected_zone_index =
@@ -496,13 +1787,26 @@ impl App {
             Message::RemoveCurrentZone => {
                 if self.timezone_manager.zone_count() > 1 {
                     // Keep at least one zone
-                    self.timezone_manager.remove_zone(self.selected_zone_index);
+                    let before = self.timezone_manager.clone();
+                    if let Some(zone) = self.timezone_manager.remove_zone(self.selected_zone_index)
+                    {
+                        self.record_zone_change(
+                            before,
+                            self.selected_zone_index,
+                            format!("remove {}", zone.effective_display_name()),
+                        );
+                        self.set_status(format!(
+                            "Removed {} (u to undo)",
+                            zone.effective_display_name()
+                        ));
+                    }
 
                     // Adjust selected index if needed
                     if self.selected_zone_index >= self.timezone_manager.zone_count() {
                         self.selected_zone_index =
                             self.timezone_manager.zone_count().saturating_sub(1);
                     }
+                    self.keep_selection_visible();
                     self.save_config();
                 }
                 None
@@ -538,8 +1842,20 @@ impl App {
                     } else {
                         Some(self.rename_zone_input.trim().to_string())
                     };
-                    self.timezone_manager
-                        .update_zone_label(self.selected_zone_index, custom_label);
+                    let before = self.timezone_manager.clone();
+                    let name = before.zones()[self.selected_zone_index]
+                        .display_name
+                        .clone();
+                    if self
+                        .timezone_manager
+                        .update_zone_label(self.selected_zone_index, custom_label)
+                    {
+                        self.record_zone_change(
+                            before,
+                            self.selected_zone_index,
+                            format!("rename {name}"),
+                        );
+                    }
                     self.save_config();
                 }
                 self.renaming_zone = false;
@@ -555,71 +1871,428 @@ impl App {
 
             Message::ClearCustomName => {
                 if self.timezone_manager.zone_count() > 0 {
-                    self.timezone_manager
-                        .update_zone_label(self.selected_zone_index, None);
+                    let before = self.timezone_manager.clone();
+                    let zone = &before.zones()[self.selected_zone_index];
+                    if zone.custom_label.is_some() {
+                        let action = format!("clear label of {}", zone.display_name);
+                        self.timezone_manager
+                            .update_zone_label(self.selected_zone_index, None);
+                        self.record_zone_change(before, self.selected_zone_index, action);
+                    }
                     self.save_config();
                 }
                 None
             }
 
-            Message::Quit => {
-                self.should_quit = true;
+            Message::Undo => {
+                self.step_zone_history(true);
                 None
             }
-        }
-    }
 
-    pub fn view(&self, f: &mut Frame) {
-        let chunks = Layout::default()
-            .direction(LayoutDirection::Vertical)
-            .constraints([
-                Constraint::Length(3), // Header
-                Constraint::Length(4), // Current time display (taller)
-                Constraint::Min(1),    // Main content
-                Constraint::Length(2), // Legend
-                Constraint::Length(3), // Footer
-            ])
-            .split(f.area());
+            Message::Redo => {
+                self.step_zone_history(false);
+                None
+            }
 
-        self.render_header(f, chunks[0]);
-        self.render_current_time_display(f, chunks[1]);
-        self.render_zones(f, chunks[2]);
-        self.render_legend(f, chunks[3]);
-        self.render_footer(f, chunks[4]);
+            Message::StartExport => {
+                // Clear other modal states
+                self.adding_zone = false;
+                self.renaming_zone = false;
 
-        // Render modals on top if needed
-        if self.show_help {
-            self.render_help_modal(f);
-        } else if self.adding_zone {
-            self.render_add_zone_modal(f);
-        } else if self.renaming_zone {
-            self.render_rename_zone_modal(f);
-        }
-    }
+                self.exporting = true;
+                self.export_title_input = "Meeting".to_string();
+                self.export_duration_input = "30".to_string();
+                self.export_field = ExportField::Title;
+                None
+            }
 
-    fn render_header(&self, f: &mut Frame, area: Rect) {
-        let local_time = self.current_time.with_timezone(&Local);
+            Message::UpdateExportInput(input) => {
+                match self.export_field {
+                    ExportField::Title => self.export_title_input = input,
+                    ExportField::Duration => self.export_duration_input = input,
+                }
+                None
+            }
 
-        // Use chrono-tz names for better abbreviations, fall back to %Z
-        let tz_name = self.get_local_timezone_name();
-        let local_time_str = match self.display_format {
-            TimeFormat::TwentyFourHour => format!("{} {}", local_time.format("%H:%M:%S"), tz_name),
-            TimeFormat::TwelveHour => format!("{} {}", local_time.format("%I:%M:%S %p"), tz_name),
-        };
+            Message::SwitchExportField => {
+                self.export_field = match self.export_field {
+                    ExportField::Title => ExportField::Duration,
+                    ExportField::Duration => ExportField::Title,
+                };
+                None
+            }
 
-        let timeline_time_str = match self.display_format {
-            TimeFormat::TwentyFourHour => self.timeline_position.format("%H:%M UTC").to_string(),
-            TimeFormat::TwelveHour => self.timeline_position.format("%I:%M %p UTC").to_string(),
-        };
+            Message::ConfirmExport => {
+                self.export_invite();
+                None
+            }
 
-        // Create inner area for content
-        let inner = area.inner(ratatui::layout::Margin {
-            horizontal: 1,
-            vertical: 1,
-        });
+            Message::CancelExport => {
+                self.exporting = false;
+                None
+            }
 
-        let chunks = Layout::default()
-            .direction(LayoutDirection::Horizontal)
+            Message::ToggleBookmarks => {
+                self.show_bookmarks = !self.show_bookmarks;
+                self.selected_bookmark = self
+                    .selected_bookmark
+                    .min(self.bookmarks.len().saturating_sub(1));
+                None
+            }
+
+            Message::NavigateBookmarks(direction) => {
+                match direction {
+                    Direction::Up if self.selected_bookmark > 0 => self.selected_bookmark -= 1,
+                    Direction::Down if self.selected_bookmark + 1 < self.bookmarks.len() => {
+                        self.selected_bookmark += 1
+                    }
+                    _ => {}
+                }
+                None
+            }
+
+            Message::JumpToBookmark => {
+                if let Some(bookmark) = self.bookmarks.get(self.selected_bookmark) {
+                    match bookmark.next_after(self.timeline_position) {
+                        Some(at) => {
+                            self.timeline_position = at;
+                            self.show_bookmarks = false;
+                        }
+                        None => {
+                            let name = bookmark.name.clone();
+                            self.set_status(format!("Bookmark '{name}' has an unknown zone"));
+                        }
+                    }
+                }
+                None
+            }
+
+            Message::DeleteBookmark => {
+                if self.selected_bookmark < self.bookmarks.len() {
+                    let removed = self.bookmarks.remove(self.selected_bookmark);
+                    self.selected_bookmark = self
+                        .selected_bookmark
+                        .min(self.bookmarks.len().saturating_sub(1));
+                    self.set_status(format!("Deleted bookmark '{}'", removed.name));
+                    self.save_config();
+                }
+                None
+            }
+
+            Message::StartAddBookmark => {
+                self.adding_bookmark = true;
+                self.bookmark_name_input.clear();
+                self.bookmark_repeat = BookmarkRepeat::Once;
+                None
+            }
+
+            Message::UpdateBookmarkInput(input) => {
+                self.bookmark_name_input = input;
+                None
+            }
+
+            Message::CycleBookmarkRepeat => {
+                self.bookmark_repeat = self.bookmark_repeat.next();
+                None
+            }
+
+            Message::ConfirmAddBookmark => {
+                let name = self.bookmark_name_input.trim();
+                if name.is_empty() {
+                    self.set_status("Bookmark needs a name");
+                    return None;
+                }
+                if let Some(bookmark) = self.bookmark_at_scrub(name.to_string()) {
+                    self.set_status(format!("Bookmarked '{}'", bookmark.name));
+                    self.bookmarks.push(bookmark);
+                    self.save_config();
+                }
+                self.adding_bookmark = false;
+                None
+            }
+
+            Message::CancelAddBookmark => {
+                self.adding_bookmark = false;
+                None
+            }
+
+            Message::ToggleCountdowns => {
+                self.show_countdowns = !self.show_countdowns;
+                self.selected_countdown = self
+                    .selected_countdown
+                    .min(self.countdowns.len().saturating_sub(1));
+                None
+            }
+
+            Message::NavigateCountdowns(direction) => {
+                match direction {
+                    Direction::Up if self.selected_countdown > 0 => self.selected_countdown -= 1,
+                    Direction::Down if self.selected_countdown + 1 < self.countdowns.len() => {
+                        self.selected_countdown += 1
+                    }
+                    _ => {}
+                }
+                None
+            }
+
+            Message::JumpToCountdown => {
+                if let Some(countdown) = self.countdowns.get(self.selected_countdown) {
+                    match countdown.instant() {
+                        Some(at) => {
+                            self.timeline_position = at;
+                            self.show_countdowns = false;
+                        }
+                        None => {
+                            let name = countdown.name.clone();
+                            self.set_status(format!("Countdown '{name}' has an unknown zone"));
+                        }
+                    }
+                }
+                None
+            }
+
+            Message::DeleteCountdown => {
+                if self.selected_countdown < self.countdowns.len() {
+                    let removed = self.countdowns.remove(self.selected_countdown);
+                    self.selected_countdown = self
+                        .selected_countdown
+                        .min(self.countdowns.len().saturating_sub(1));
+                    self.set_status(format!("Deleted countdown '{}'", removed.name));
+                    self.save_config();
+                }
+                None
+            }
+
+            Message::StartAddCountdown => {
+                let when = match self.timezone_manager.zones().get(self.selected_zone_index) {
+                    Some(zone) => self.timeline_position.with_timezone(&zone.tz).naive_local(),
+                    None => self.timeline_position.naive_utc(),
+                };
+                self.adding_countdown = true;
+                self.countdown_name_input.clear();
+                self.countdown_when_input = when.format("%Y-%m-%d %H:%M").to_string();
+                self.countdown_field = CountdownField::Name;
+                None
+            }
+
+            Message::UpdateCountdownInput(input) => {
+                match self.countdown_field {
+                    CountdownField::Name => self.countdown_name_input = input,
+                    CountdownField::When => self.countdown_when_input = input,
+                }
+                None
+            }
+
+            Message::SwitchCountdownField => {
+                self.countdown_field = match self.countdown_field {
+                    CountdownField::Name => CountdownField::When,
+                    CountdownField::When => CountdownField::Name,
+                };
+                None
+            }
+
+            Message::ConfirmAddCountdown => {
+                self.add_countdown();
+                None
+            }
+
+            Message::CancelAddCountdown => {
+                self.adding_countdown = false;
+                None
+            }
+
+            Message::StartYank => {
+                self.yanking = true;
+                None
+            }
+
+            Message::Yank(format) => {
+                let text = self.yank_text(format);
+                self.set_status(format!("Copied {}: {text}", format.label()));
+                self.pending_clipboard = Some(text);
+                self.yanking = false;
+                None
+            }
+
+            Message::CancelYank => {
+                self.yanking = false;
+                None
+            }
+
+            Message::Quit => {
+                if self.show_settings {
+                    self.time_config = self.settings_backup.clone();
+                }
+                self.flush_config();
+                self.should_quit = true;
+                None
+            }
+        }
+    }
+
+    pub fn view(&self, f: &mut Frame) {
+        let banner_height = if self.config_issues.is_empty() {
+            0
+        } else {
+            self.config_issues.len().min(MAX_BANNER_ISSUES) as u16 + 2
+        };
+        let reminder_height = if self.due_reminders.is_empty() {
+            0
+        } else {
+            self.due_reminders.len().min(MAX_BANNER_REMINDERS) as u16 + 2
+        };
+        let chunks = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([
+                Constraint::Length(3),               // Header
+                Constraint::Length(4),               // Current time display (taller)
+                Constraint::Length(reminder_height), // Due reminders
+                Constraint::Length(banner_height),   // Config problems
+                Constraint::Min(1),                  // Main content
+                Constraint::Length(2),               // Legend
+                Constraint::Length(3),               // Footer
+            ])
+            .split(f.area());
+
+        self.render_header(f, chunks[0]);
+        self.render_current_time_display(f, chunks[1]);
+        if reminder_height > 0 {
+            self.render_reminder_banner(f, chunks[2]);
+        }
+        if banner_height > 0 {
+            self.render_config_banner(f, chunks[3]);
+        }
+        let main_area = if self.show_info_panel && chunks[4].width >= INFO_PANEL_WIDTH * 2 {
+            let columns = Layout::default()
+                .direction(LayoutDirection::Horizontal)
+                .constraints([Constraint::Min(1), Constraint::Length(INFO_PANEL_WIDTH)])
+                .split(chunks[4]);
+            self.render_info_panel(f, columns[1]);
+            columns[0]
+        } else {
+            chunks[4]
+        };
+        if self.show_week_grid {
+            self.render_week_grid(f, main_area);
+        } else {
+            self.render_zones(f, main_area);
+        }
+        self.render_legend(f, chunks[5]);
+        self.render_footer(f, chunks[6]);
+
+        // Render modals on top if needed
+        if self.show_help {
+            self.render_help_modal(f);
+        } else if self.adding_zone {
+            self.render_add_zone_modal(f);
+        } else if self.renaming_zone {
+            self.render_rename_zone_modal(f);
+        } else if self.exporting {
+            self.render_export_modal(f);
+        } else if self.adding_bookmark {
+            self.render_add_bookmark_modal(f);
+        } else if self.palette_open {
+            self.render_palette_modal(f);
+        } else if self.show_settings {
+            self.render_settings_modal(f);
+        } else if self.show_table {
+            self.render_table_modal(f);
+        } else if self.show_bookmarks {
+            self.render_bookmarks_modal(f);
+        } else if self.adding_countdown {
+            self.render_add_countdown_modal(f);
+        } else if self.show_countdowns {
+            self.render_countdowns_modal(f);
+        }
+    }
+
+    fn render_reminder_banner(&self, f: &mut Frame, area: Rect) {
+        use ratatui::text::{Line, Span};
+
+        // Newest first
+        let mut lines: Vec<Line> = self
+            .due_reminders
+            .iter()
+            .rev()
+            .take(MAX_BANNER_REMINDERS)
+            .map(|reminder| Line::from(reminder.as_str()))
+            .collect();
+        let hidden = self
+            .due_reminders
+            .len()
+            .saturating_sub(MAX_BANNER_REMINDERS);
+        if hidden > 0 {
+            if let Some(last) = lines.last_mut() {
+                last.push_span(Span::raw(format!(" (+{hidden} more)")));
+            }
+        }
+
+        let banner = Paragraph::new(lines)
+            .style(Style::default().fg(Color::White))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Yellow))
+                    .title(" Reminders (Esc to dismiss) "),
+            );
+        f.render_widget(banner, area);
+    }
+
+    fn render_config_banner(&self, f: &mut Frame, area: Rect) {
+        use ratatui::text::{Line, Span};
+
+        let mut lines: Vec<Line> = self
+            .config_issues
+            .iter()
+            .take(MAX_BANNER_ISSUES)
+            .map(|issue| Line::from(issue.as_str()))
+            .collect();
+        let hidden = self.config_issues.len().saturating_sub(MAX_BANNER_ISSUES);
+        if hidden > 0 {
+            if let Some(last) = lines.last_mut() {
+                last.push_span(Span::raw(format!(" (+{hidden} more)")));
+            }
+        }
+
+        let title = if self.config_writable {
+            " Config problems (Esc to dismiss) "
+        } else {
+            " Config problems: changes will not be saved (Esc to dismiss) "
+        };
+        let banner = Paragraph::new(lines)
+            .style(Style::default().fg(Color::Yellow))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Red))
+                    .title(title),
+            );
+        f.render_widget(banner, area);
+    }
+
+    fn render_header(&self, f: &mut Frame, area: Rect) {
+        let local_time = self.current_time.with_timezone(&Local);
+
+        // Use chrono-tz names for better abbreviations, fall back to %Z
+        let tz_name = self.get_local_timezone_name();
+        let local_time_str = match self.display_format {
+            TimeFormat::TwentyFourHour => format!("{} {}", local_time.format("%H:%M:%S"), tz_name),
+            TimeFormat::TwelveHour => format!("{} {}", local_time.format("%I:%M:%S %p"), tz_name),
+        };
+
+        let timeline_time_str = match self.display_format {
+            TimeFormat::TwentyFourHour => self.timeline_position.format("%H:%M UTC").to_string(),
+            TimeFormat::TwelveHour => self.timeline_position.format("%I:%M %p UTC").to_string(),
+        };
+
+        // Create inner area for content
+        let inner = area.inner(ratatui::layout::Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+
+        let chunks = Layout::default()
+            .direction(LayoutDirection::Horizontal)
             .constraints([
                 Constraint::Percentage(33), // App name (left)
                 Constraint::Percentage(34), // Local time (center)
@@ -642,10 +2315,46 @@ impl App {
             Paragraph::new(format!("Timeline: {timeline_time_str}")).alignment(Alignment::Right);
         f.render_widget(timeline_display, chunks[2]);
 
-        let border = Block::default().borders(Borders::ALL);
+        let mut border = Block::default().borders(Borders::ALL);
+        if let Some(readout) = self.countdown_readout() {
+            border = border.title(
+                ratatui::text::Line::from(format!(" {readout} "))
+                    .style(Style::default().fg(self.color_theme.get_countdown_color()))
+                    .alignment(Alignment::Center),
+            );
+        }
         f.render_widget(border, area);
     }
 
+    /// The header's countdown line: the next countdown's remaining time, or one that just expired
+    fn countdown_readout(&self) -> Option<String> {
+        let now = self.current_time;
+        let mut instants: Vec<(DateTime<Utc>, &str)> = self
+            .countdowns
+            .iter()
+            .filter_map(|countdown| Some((countdown.instant()?, countdown.name.as_str())))
+            .collect();
+        instants.sort_by_key(|(at, _)| *at);
+
+        if let Some((_, name)) = instants
+            .iter()
+            .rev()
+            .find(|(at, _)| *at <= now && now - *at < COUNTDOWN_DUE_DURATION)
+        {
+            return Some(format!("⏰ {name} is due"));
+        }
+        let upcoming: Vec<_> = instants.iter().filter(|(at, _)| *at > now).collect();
+        let (at, name) = upcoming.first()?;
+        let more = match upcoming.len() - 1 {
+            0 => String::new(),
+            n => format!(" (+{n} more)"),
+        };
+        Some(format!(
+            "⏳ {name} in {}{more}",
+            format_remaining(*at - now)
+        ))
+    }
+
     fn get_local_timezone_name(&self) -> String {
         // Try to get a better timezone name from our configured zones
         let local_time = self.current_time.with_timezone(&Local);
@@ -676,14 +2385,36 @@ impl App {
     }
 
     fn render_zones(&self, f: &mut Frame, area: Rect) {
-        let zones = self.timezone_manager.zones();
+        let all_zones = self.timezone_manager.zones();
+        let zones: Vec<(usize, &TimeZone)> = self
+            .visible_zone_indices()
+            .into_iter()
+            .map(|index| (index, &all_zones[index]))
+            .collect();
 
-        if zones.is_empty() {
+        if all_zones.is_empty() {
             let empty_msg = Paragraph::new("No timezones configured")
                 .block(Block::default().borders(Borders::ALL).title("Timezones"));
             f.render_widget(empty_msg, area);
             return;
         }
+        if zones.is_empty() {
+            let empty_msg = Paragraph::new(format!("No zones match '{}'", self.zone_filter))
+                .block(Block::default().borders(Borders::ALL).title("Timezones"));
+            f.render_widget(empty_msg, area);
+            return;
+        }
+
+        // Reserve the top row for the hour ruler when it is shown
+        let (ruler_area, area) = if self.ruler_mode == RulerMode::Off || area.height < 2 {
+            (None, area)
+        } else {
+            let rows = Layout::default()
+                .direction(LayoutDirection::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(1)])
+                .split(area);
+            (Some(rows[0]), rows[1])
+        };
 
         let zone_constraints = zones
             .iter()
@@ -695,14 +2426,116 @@ impl App {
             .constraints(zone_constraints)
             .split(area);
 
-        for (i, zone) in zones.iter().enumerate() {
+        // Every zone block maps time to columns the same way, inside its one-column border
+        let scale = TimelineScale::new(
+            self.timeline_position,
+            self.timeline_zoom,
+            area.width.saturating_sub(2),
+        );
+        if let Some(ruler_area) = ruler_area {
+            self.render_ruler(f, ruler_area, scale);
+        }
+
+        // Expand calendar events once per frame, covering the widest possible timeline window
+        let events = if self.event_overlay == EventOverlay::Off {
+            Vec::new()
+        } else {
+            self.calendar.occurrences_between(
+                self.timeline_position - chrono::Duration::days(4),
+                self.timeline_position + chrono::Duration::days(4),
+            )
+        };
+
+        let markers: Vec<TimelineMarker> = self
+            .bookmarks
+            .iter()
+            .flat_map(|bookmark| bookmark.occurrences_between(scale.start, scale.end))
+            .map(|at| TimelineMarker {
+                at,
+                symbol: '◆',
+                color: self.color_theme.get_bookmark_color(),
+            })
+            .chain(
+                self.countdowns
+                    .iter()
+                    .filter_map(Countdown::instant)
+                    .map(|at| TimelineMarker {
+                        at,
+                        symbol: '▼',
+                        color: self.color_theme.get_countdown_color(),
+                    }),
+            )
+            .collect();
+
+        for (i, (index, zone)) in zones.iter().enumerate() {
             if i < zone_chunks.len() {
-                self.render_zone(f, zone_chunks[i], zone, i == self.selected_zone_index);
+                let is_selected = *index == self.selected_zone_index;
+                let zone_events = match self.event_overlay {
+                    EventOverlay::All => events.as_slice(),
+                    EventOverlay::Selected if is_selected => events.as_slice(),
+                    _ => &[],
+                };
+                self.render_zone(f, zone_chunks[i], zone, is_selected, zone_events, &markers);
+            }
+        }
+
+        // Join the per-zone scrub marks into one line from the ruler down through the last zone
+        let top = ruler_area.map_or(area.y, |ruler| ruler.y);
+        let bottom = zone_chunks
+            .iter()
+            .take(zones.len())
+            .map(|chunk| chunk.bottom())
+            .max()
+            .unwrap_or(area.y)
+            .min(area.bottom());
+        if area.width > 2 {
+            let x = area.x + 1 + scale.position_of(self.timeline_position);
+            let style = Style::default().fg(self.color_theme.get_timeline_position_color());
+            let buf = f.buffer_mut();
+            for y in top..bottom {
+                let cell = &mut buf[(x, y)];
+                // Only cross borders and blank cells, never titles or labels
+                match cell.symbol() {
+                    " " => {
+                        cell.set_char('┃').set_style(style);
+                    }
+                    "─" => {
+                        cell.set_char('╂').set_style(style);
+                    }
+                    _ => {}
+                }
             }
         }
     }
 
-    fn render_zone(&self, f: &mut Frame, area: Rect, zone: &TimeZone, is_selected: bool) {
+    fn render_ruler(&self, f: &mut Frame, area: Rect, scale: TimelineScale) {
+        let selected_zone = self.timezone_manager.zones().get(self.selected_zone_index);
+        let (tz, tag) = match (self.ruler_mode, selected_zone) {
+            (RulerMode::SelectedZone, Some(zone)) => {
+                (zone.tz, zone.effective_display_name().to_string())
+            }
+            _ => (chrono_tz::UTC, "UTC".to_string()),
+        };
+
+        let ruler = HourRuler::new(
+            scale,
+            tz,
+            tag,
+            self.display_format.clone(),
+            self.color_theme,
+        );
+        f.render_widget(ruler, area);
+    }
+
+    fn render_zone(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        zone: &TimeZone,
+        is_selected: bool,
+        events: &[EventOccurrence],
+        markers: &[TimelineMarker],
+    ) {
         let timeline_widget = TimelineWidget::new(
             self.timeline_position,
             self.current_time,
@@ -715,34 +2548,220 @@ impl App {
             self.show_date,
             true, // DST indicators always on
             self.show_sun_times,
-        );
+        )
+        .with_events(events)
+        .with_markers(markers)
+        .with_relative_to(
+            if is_selected && self.relative_offsets == RelativeOffsetMode::SelectedZone {
+                None
+            } else {
+                self.offset_reference()
+            },
+        )
+        .with_zoom(self.timeline_zoom)
+        .with_shading(self.shading_mode);
 
         f.render_widget(timeline_widget, area);
     }
 
-    fn render_current_time_display(&self, f: &mut Frame, area: Rect) {
-        // Show what the scrubbed timeline position is in the user's local timezone
-        let local_time = self.timeline_position.with_timezone(&chrono::Local);
-
-        // Format as two lines: local timezone info with UTC offset on first line, time with day on second
-        let local_offset_seconds = local_time.offset().fix().local_minus_utc();
-        let local_offset_hours = local_offset_seconds / 3600;
-        let utc_offset_str = if local_offset_hours >= 0 {
-            format!("UTC+{local_offset_hours}")
-        } else {
-            format!("UTC{local_offset_hours}")
+    fn render_week_grid(&self, f: &mut Frame, area: Rect) {
+        let zones = self.timezone_manager.zones();
+        let Some(zone) = zones.get(self.selected_zone_index) else {
+            return;
         };
-        let timezone_line = format!("{} ({})", self.get_local_timezone_name(), utc_offset_str);
-
-        let time_line = match self.display_format {
-            TimeFormat::TwentyFourHour => local_time.format("%H:%M %a").to_string(),
-            TimeFormat::TwelveHour => local_time.format("%I:%M %p %a").to_string(),
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.color_theme.get_selected_border_color()))
+            .title(format!(
+                " Week in {} · h/l hour, j/k day, w to close ",
+                zone.effective_display_name()
+            ));
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let grid = WeekGrid {
+            tz: zone.tz,
+            others: zones
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != self.selected_zone_index)
+                .map(|(_, other)| other.tz)
+                .collect(),
+            cursor: self.timeline_position,
+            time_config: self.time_config.clone(),
+            display_format: self.display_format.clone(),
+            color_theme: self.color_theme,
         };
+        f.render_widget(grid, inner);
+    }
 
-        let display_text = format!("{timezone_line}\n{time_line}");
-
-        // Calculate the width needed for the longest line plus borders and padding
-        let max_line_width = timezone_line.len().max(time_line.len());
+    /// Label/value rows describing the selected zone at the scrubbed instant
+    fn info_panel_rows(&self) -> Vec<(&'static str, String)> {
+        let Some(zone) = self.timezone_manager.zones().get(self.selected_zone_index) else {
+            return Vec::new();
+        };
+        let instant = self.timeline_position;
+        let local = zone.convert_time(instant);
+        let use_12_hour = matches!(self.display_format, TimeFormat::TwelveHour);
+        let city = zone.get_city_name();
+
+        let mut rows = vec![
+            ("Zone", zone.effective_display_name().to_string()),
+            ("IANA", zone.tz.name().to_string()),
+            ("Country", TimeZoneManager::get_country_for_city(&city)),
+        ];
+        if let Some((lat, lng)) = zone.get_coordinates() {
+            let ns = if lat >= 0.0 { 'N' } else { 'S' };
+            let ew = if lng >= 0.0 { 'E' } else { 'W' };
+            rows.push((
+                "Location",
+                format!("{:.2}°{ns} {:.2}°{ew}", lat.abs(), lng.abs()),
+            ));
+        }
+        rows.push((
+            "Offset",
+            format!(
+                "{} ({})",
+                format_utc_offset(local.offset().fix().local_minus_utc()),
+                zone.get_timezone_abbreviation_at(instant)
+            ),
+        ));
+        rows.push((
+            "Next DST",
+            match zone.next_offset_change(instant) {
+                Some((at, before, after)) => format!(
+                    "{} {} → {}",
+                    at.with_timezone(&zone.tz).format("%a %-d %b %Y %H:%M"),
+                    format_utc_offset(before),
+                    format_utc_offset(after)
+                ),
+                None => "none".to_string(),
+            },
+        ));
+        rows.push(("Sun", zone.format_sun_times(instant, use_12_hour)));
+        if let Some(details) = zone.get_solar_details(local.date_naive()) {
+            let minutes = details.day_length.num_minutes();
+            rows.push((
+                "Day length",
+                format!("{}h {:02}m", minutes / 60, minutes % 60),
+            ));
+        }
+        rows.push((
+            "Date",
+            format!(
+                "{} · week {}",
+                local.format("%a %-d %b %Y"),
+                local.iso_week().week()
+            ),
+        ));
+        rows.push((
+            "Activity",
+            self.time_config
+                .get_time_activity_at(local.time())
+                .label()
+                .to_string(),
+        ));
+
+        // All-day calendar events covering the zone's midday: public holidays from a holiday
+        // calendar, but also the user's own days off, so they aren't labelled as holidays
+        let midday = local
+            .date_naive()
+            .and_hms_opt(12, 0, 0)
+            .and_then(|midday| midday.and_local_timezone(zone.tz).earliest())
+            .map(|midday| midday.with_timezone(&Utc))
+            .unwrap_or(instant);
+        let all_day: Vec<String> = self
+            .calendar
+            .occurrences_between(midday, midday + chrono::Duration::seconds(1))
+            .into_iter()
+            .filter(|event| event.all_day && event.contains(midday))
+            .map(|event| event.summary)
+            .collect();
+        rows.push((
+            "All-day events",
+            if all_day.is_empty() {
+                "none".to_string()
+            } else {
+                all_day.join(", ")
+            },
+        ));
+        rows
+    }
+
+    fn render_info_panel(&self, f: &mut Frame, area: Rect) {
+        use ratatui::text::{Line, Span};
+
+        let rows = self.info_panel_rows();
+        let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0) + 1;
+        let lines: Vec<Line> = rows
+            .into_iter()
+            .map(|(label, value)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{label:<label_width$}"),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(value, Style::default().fg(Color::White)),
+                ])
+            })
+            .collect();
+
+        let panel = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.color_theme.get_work_color()))
+                .title(" Zone info (i) "),
+        );
+        f.render_widget(panel, area);
+    }
+
+    /// What zone titles compare their offset to at the scrubbed instant, if anything
+    fn offset_reference(&self) -> Option<OffsetReference> {
+        let instant = self.timeline_position;
+        let (offset_seconds, date) = match self.relative_offsets {
+            RelativeOffsetMode::Off => return None,
+            RelativeOffsetMode::SelectedZone => {
+                let zone = self
+                    .timezone_manager
+                    .zones()
+                    .get(self.selected_zone_index)?;
+                let local = instant.with_timezone(&zone.tz);
+                (local.offset().fix().local_minus_utc(), local.date_naive())
+            }
+            RelativeOffsetMode::Local => {
+                let local = instant.with_timezone(&Local);
+                (local.offset().local_minus_utc(), local.date_naive())
+            }
+        };
+        Some(OffsetReference {
+            offset_seconds,
+            date,
+        })
+    }
+
+    fn render_current_time_display(&self, f: &mut Frame, area: Rect) {
+        // Show what the scrubbed timeline position is in the user's local timezone
+        let local_time = self.timeline_position.with_timezone(&chrono::Local);
+
+        // Format as two lines: local timezone info with UTC offset on first line, time with day on second
+        let local_offset_seconds = local_time.offset().fix().local_minus_utc();
+        let local_offset_hours = local_offset_seconds / 3600;
+        let utc_offset_str = if local_offset_hours >= 0 {
+            format!("UTC+{local_offset_hours}")
+        } else {
+            format!("UTC{local_offset_hours}")
+        };
+        let timezone_line = format!("{} ({})", self.get_local_timezone_name(), utc_offset_str);
+
+        let time_line = match self.display_format {
+            TimeFormat::TwentyFourHour => local_time.format("%H:%M %a").to_string(),
+            TimeFormat::TwelveHour => local_time.format("%I:%M %p %a").to_string(),
+        };
+
+        let display_text = format!("{timezone_line}\n{time_line}");
+
+        // Calculate the width needed for the longest line plus borders and padding
+        let max_line_width = timezone_line.len().max(time_line.len());
         let box_width = (max_line_width as u16 + 4).min(area.width); // +4 for borders and padding
         let center_x = (area.width.saturating_sub(box_width)) / 2;
 
@@ -767,9 +2786,7 @@ impl App {
         use ratatui::text::{Line, Span};
 
         // Create legend showing what the different timeline colors/characters mean
-        let night_char = self
-            .time_config
-            .get_activity_char(crate::config::TimeActivity::Night);
+        let night_char = self.time_config.get_activity_char(TimeActivity::Night);
         let awake_char = self
             .time_config
             .get_activity_char(crate::config::TimeActivity::Awake);
@@ -781,13 +2798,34 @@ impl App {
         let awake_color = self.color_theme.get_awake_color();
         let work_color = self.color_theme.get_work_color();
 
-        let legend_line = Line::from(vec![
-            Span::styled(format!("{night_char} "), Style::default().fg(night_color)),
-            Span::raw("Night  "),
-            Span::styled(format!("{awake_char} "), Style::default().fg(awake_color)),
-            Span::raw("Awake  "),
-            Span::styled(format!("{work_char} "), Style::default().fg(work_color)),
-            Span::raw("Work  "),
+        let mut shading_spans = match self.shading_mode {
+            ShadingMode::Schedule => vec![
+                Span::styled(format!("{night_char} "), Style::default().fg(night_color)),
+                Span::raw("Night  "),
+                Span::styled(format!("{awake_char} "), Style::default().fg(awake_color)),
+                Span::raw("Awake  "),
+                Span::styled(format!("{work_char} "), Style::default().fg(work_color)),
+                Span::raw("Work  "),
+            ],
+            ShadingMode::Daylight => [
+                SolarPhase::Night,
+                SolarPhase::AstronomicalTwilight,
+                SolarPhase::NauticalTwilight,
+                SolarPhase::CivilTwilight,
+                SolarPhase::Day,
+            ]
+            .into_iter()
+            .flat_map(|phase| {
+                let (ch, color) = self.color_theme.get_solar_phase_display(phase);
+                [
+                    Span::styled(format!("{ch} "), Style::default().fg(color)),
+                    Span::raw(format!("{}  ", phase.label())),
+                ]
+            })
+            .collect(),
+        };
+
+        shading_spans.extend([
             Span::styled("┊ ", Style::default().fg(night_color)),
             Span::raw("Midnight  "),
             Span::styled("│ ", Style::default().fg(Color::Red)),
@@ -797,7 +2835,46 @@ impl App {
                 Style::default().fg(self.color_theme.get_timeline_position_color()),
             ),
             Span::raw("Timeline"),
+            Span::raw(format!("  Zoom {}", self.timeline_zoom.label())),
         ]);
+        let legend_line = Line::from(shading_spans);
+
+        let legend_line = if self.calendar.is_empty() || self.event_overlay == EventOverlay::Off {
+            legend_line
+        } else {
+            let mut spans = legend_line.spans;
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(
+                "  ",
+                Style::default().bg(self.color_theme.get_event_color()),
+            ));
+            spans.push(Span::raw(" Event"));
+            Line::from(spans)
+        };
+
+        let legend_line = if self.bookmarks.is_empty() {
+            legend_line
+        } else {
+            let mut spans = legend_line.spans;
+            spans.push(Span::styled(
+                "  ◆ ",
+                Style::default().fg(self.color_theme.get_bookmark_color()),
+            ));
+            spans.push(Span::raw("Bookmark"));
+            Line::from(spans)
+        };
+
+        let legend_line = if self.countdowns.is_empty() {
+            legend_line
+        } else {
+            let mut spans = legend_line.spans;
+            spans.push(Span::styled(
+                "  ▼ ",
+                Style::default().fg(self.color_theme.get_countdown_color()),
+            ));
+            spans.push(Span::raw("Countdown"));
+            Line::from(spans)
+        };
 
         let legend = Paragraph::new(legend_line)
             .style(Style::default().fg(Color::DarkGray))
@@ -807,11 +2884,38 @@ impl App {
     }
 
     fn render_footer(&self, f: &mut Frame, area: Rect) {
-        let footer_text = "?: help │ a: add │ q: quit";
-
-        let footer = Paragraph::new(footer_text)
-            .style(Style::default().fg(Color::DarkGray))
-            .alignment(Alignment::Center);
+        let footer = if self.filtering_zones {
+            Paragraph::new(format!(
+                "/{}█  {} of {} zones │ Enter: keep filter │ Esc: clear",
+                self.zone_filter,
+                self.visible_zone_indices().len(),
+                self.timezone_manager.zone_count()
+            ))
+            .style(Style::default().fg(self.color_theme.get_selected_border_color()))
+        } else if self.yanking {
+            Paragraph::new(
+                "Copy: i ISO UTC │ l local time │ u Unix timestamp │ z all zones │ Esc cancel",
+            )
+            .style(Style::default().fg(self.color_theme.get_selected_border_color()))
+        } else if let Some((message, _)) = &self.status_message {
+            Paragraph::new(message.as_str())
+                .style(Style::default().fg(self.color_theme.get_selected_border_color()))
+        } else if !self.zone_filter.is_empty() {
+            Paragraph::new(format!(
+                "Filter /{}: {} of {} zones │ n/N: next/previous match │ Esc: clear",
+                self.zone_filter,
+                self.visible_zone_indices().len(),
+                self.timezone_manager.zone_count()
+            ))
+            .style(Style::default().fg(self.color_theme.get_selected_border_color()))
+        } else if self.read_only {
+            Paragraph::new("?: help │ a: add │ x: export │ q: quit │ read-only config")
+                .style(Style::default().fg(Color::DarkGray))
+        } else {
+            Paragraph::new("?: help │ a: add │ x: export │ q: quit")
+                .style(Style::default().fg(Color::DarkGray))
+        }
+        .alignment(Alignment::Center);
 
         f.render_widget(footer, area);
     }
@@ -822,7 +2926,7 @@ impl App {
         // Calculate modal size to fit content
         let modal_width = area.width * 2 / 3; // Same as add city modal
                                               // Calculate height based on content: title + max column content + footer + borders
-        let max_content_lines = 17; // Longest column has about 17 lines
+        let max_content_lines = 30; // Longest column has about 30 lines
         let modal_height = (2 + max_content_lines + 1 + 4).min(area.height.saturating_sub(2)); // title + content + footer + borders + margin
 
         let popup_area = Rect {
@@ -875,13 +2979,21 @@ impl App {
                     "[ or ]         Adjust by ±15 minutes",
                     "{ or }         Adjust by ±1 hour",
                     "t              Reset to current time",
+                    "+ / - / 0      Zoom in / out / fit width",
+                    "R              Cycle hour ruler (zone/UTC)",
+                    "b / B          Bookmarks / bookmark this time",
+                    "o              Countdowns",
                 ],
             ),
             (
                 "ZONE NAVIGATION",
                 vec![
                     "j/↓ or k/↑     Navigate between zones",
+                    "/ then n/N     Filter zones, next/previous match",
                     "               Selected has colored border",
+                    "i              Show/hide selected zone details",
+                    "w              Week grid (h/l hour, j/k day)",
+                    "T              Conversion table from selected zone",
                 ],
             ),
             (
@@ -891,7 +3003,11 @@ impl App {
                     "n              Toggle short/full names",
                     "d              Toggle date display",
                     "s              Toggle sunrise/sunset times",
+                    "S              Toggle daylight shading",
                     "c              Cycle color themes",
+                    "v              Cycle calendar events overlay",
+                    "f              Cycle offsets relative to selected/local",
+                    "W              Edit work/awake hours (live preview)",
                 ],
             ),
         ];
@@ -905,7 +3021,10 @@ impl App {
                     "r              Remove selected timezone",
                     "e              Rename selected timezone",
                     "E              Clear custom name",
+                    "u / Ctrl+r     Undo / redo zone changes",
                     "1-8            Quick-select search results",
+                    "x              Export timeline slot as .ics",
+                    "y              Copy timeline time to clipboard",
                 ],
             ),
             (
@@ -915,6 +3034,7 @@ impl App {
                     "┃              Colored line: Timeline position",
                     "⇈              DST spring forward",
                     "⇊              DST fall back",
+                    "◆ / ▼          Bookmark / countdown",
                     "░ ▒ ▓          Night, Awake, Work hours",
                 ],
             ),
@@ -922,6 +3042,7 @@ impl App {
                 "CONTROLS",
                 vec![
                     "?              Show/hide help",
+                    ":              Command palette",
                     "q              Quit",
                     "Esc            Cancel operation",
                 ],
@@ -1136,17 +3257,799 @@ impl App {
             ]
         };
 
-        let help_paragraph = Paragraph::new(help_text.join("\n"))
-            .style(ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray))
-            .alignment(Alignment::Left);
-        f.render_widget(help_paragraph, area);
+        let help_paragraph = Paragraph::new(help_text.join("\n"))
+            .style(ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray))
+            .alignment(Alignment::Left);
+        f.render_widget(help_paragraph, area);
+    }
+
+    fn render_rename_zone_modal(&self, f: &mut Frame) {
+        let area = f.area();
+
+        let modal_height = 9; // Smaller modal for rename
+        let modal_width = area.width.saturating_sub(area.width / 3).min(60);
+
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: (area.height.saturating_sub(modal_height)) / 2,
+            width: modal_width,
+            height: modal_height,
+        };
+
+        // Clear the background
+        f.render_widget(Clear, popup_area);
+
+        // Get current zone info
+        let current_zone = &self.timezone_manager.zones()[self.selected_zone_index];
+        let city_name = self
+            .timezone_manager
+            .zones()
+            .iter()
+            .enumerate()
+            .find(|(idx, zone)| *idx == self.selected_zone_index && zone.tz == current_zone.tz)
+            .and_then(|(_, zone)| {
+                let available = crate::time::TimeZoneManager::get_all_available_timezones();
+                available
+                    .iter()
+                    .find(|(tz, _, _, _, _)| *tz == zone.tz)
+                    .map(|(_, name, _, _, _)| name.clone())
+            })
+            .unwrap_or_else(|| current_zone.tz.to_string());
+
+        // Split the modal into sections
+        let inner = popup_area.inner(ratatui::layout::Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        let chunks = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([
+                Constraint::Length(2), // Zone info
+                Constraint::Length(2), // Input field
+                Constraint::Length(1), // Spacer
+                Constraint::Length(2), // Controls help
+            ])
+            .split(inner);
+
+        // Render zone info
+        let zone_info = format!("Renaming: {city_name}");
+        let zone_paragraph = Paragraph::new(zone_info)
+            .style(ratatui::style::Style::default().fg(ratatui::style::Color::Gray));
+        f.render_widget(zone_paragraph, chunks[0]);
+
+        // Render input field
+        let input_text = format!("Custom name: {}", self.rename_zone_input);
+        let input_paragraph = Paragraph::new(input_text)
+            .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
+        f.render_widget(input_paragraph, chunks[1]);
+
+        // Render controls help
+        let controls = "Enter: Save | Esc: Cancel | Empty to remove custom name";
+        let controls_paragraph = Paragraph::new(controls)
+            .style(ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(controls_paragraph, chunks[3]);
+
+        // Render the modal border
+        let border = Block::default()
+            .borders(Borders::ALL)
+            .title(" Rename Timezone ")
+            .title_style(
+                ratatui::style::Style::default()
+                    .fg(ratatui::style::Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Blue))
+            .style(ratatui::style::Style::default().bg(ratatui::style::Color::Black));
+        f.render_widget(border, popup_area);
+    }
+
+    fn render_export_modal(&self, f: &mut Frame) {
+        let area = f.area();
+
+        let modal_height = 11;
+        let modal_width = area.width.saturating_sub(area.width / 4).min(80);
+
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: (area.height.saturating_sub(modal_height)) / 2,
+            width: modal_width,
+            height: modal_height,
+        };
+
+        // Clear the background
+        f.render_widget(Clear, popup_area);
+
+        let inner = popup_area.inner(ratatui::layout::Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        let chunks = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([
+                Constraint::Length(2), // Slot summary
+                Constraint::Length(1), // Title field
+                Constraint::Length(2), // Duration field
+                Constraint::Length(1), // Spacer
+                Constraint::Length(2), // Controls help
+            ])
+            .split(inner);
+
+        let use_12_hour = matches!(self.display_format, TimeFormat::TwelveHour);
+        let summary = self
+            .timezone_manager
+            .format_multi_zone_summary(self.timeline_position, use_12_hour);
+        let summary_paragraph = Paragraph::new(summary)
+            .style(Style::default().fg(Color::Gray))
+            .wrap(Wrap { trim: true });
+        f.render_widget(summary_paragraph, chunks[0]);
+
+        let field_style = |field: ExportField| {
+            if self.export_field == field {
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            }
+        };
+        let marker = |field: ExportField| {
+            if self.export_field == field {
+                "> "
+            } else {
+                "  "
+            }
+        };
+
+        let title_paragraph = Paragraph::new(format!(
+            "{}Title: {}",
+            marker(ExportField::Title),
+            self.export_title_input
+        ))
+        .style(field_style(ExportField::Title));
+        f.render_widget(title_paragraph, chunks[1]);
+
+        let duration_paragraph = Paragraph::new(format!(
+            "{}Duration (minutes): {}",
+            marker(ExportField::Duration),
+            self.export_duration_input
+        ))
+        .style(field_style(ExportField::Duration));
+        f.render_widget(duration_paragraph, chunks[2]);
+
+        let controls = "Tab: Switch field | Enter: Write .ics | Esc: Cancel";
+        let controls_paragraph = Paragraph::new(controls)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(controls_paragraph, chunks[4]);
+
+        let border = Block::default()
+            .borders(Borders::ALL)
+            .title(" Export Meeting Slot ")
+            .title_style(
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(Style::default().fg(Color::Magenta))
+            .style(Style::default().bg(Color::Black));
+        f.render_widget(border, popup_area);
+    }
+
+    fn render_palette_modal(&self, f: &mut Frame) {
+        let area = f.area();
+        let completions = command::completions(&self.palette_input);
+
+        let list_height = completions.len().clamp(1, MAX_PALETTE_COMPLETIONS) as u16;
+        let modal_height = (1 + 1 + list_height + 2 + 2).min(area.height.saturating_sub(2)); // input + spacer + list + controls + borders
+        let modal_width = area.width.saturating_sub(area.width / 4).min(90);
+
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: (area.height.saturating_sub(modal_height)) / 3,
+            width: modal_width,
+            height: modal_height,
+        };
+
+        // Clear the background
+        f.render_widget(Clear, popup_area);
+
+        let inner = popup_area.inner(ratatui::layout::Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        let chunks = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([
+                Constraint::Length(2), // Input
+                Constraint::Min(1),    // Completions
+                Constraint::Length(2), // Controls help
+            ])
+            .split(inner);
+
+        let input = Paragraph::new(format!(":{}█", self.palette_input))
+            .style(Style::default().fg(Color::White));
+        f.render_widget(input, chunks[0]);
+
+        if completions.is_empty() {
+            let empty =
+                Paragraph::new("No matching command").style(Style::default().fg(Color::DarkGray));
+            f.render_widget(empty, chunks[1]);
+        } else {
+            let rows: Vec<Row> = completions
+                .iter()
+                .map(|spec| {
+                    Row::new(vec![
+                        Cell::from(format!("{} {}", spec.name, spec.args)),
+                        Cell::from(spec.description).style(Style::default().fg(Color::Gray)),
+                    ])
+                })
+                .collect();
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Length(28), // Command and arguments
+                    Constraint::Min(16),    // Description
+                ],
+            )
+            .style(Style::default().fg(Color::White))
+            .row_highlight_style(
+                Style::default()
+                    .fg(self.color_theme.get_work_color())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("> ")
+            .column_spacing(1);
+
+            let mut table_state = TableState::default();
+            table_state.select(Some(self.selected_completion));
+            f.render_stateful_widget(table, chunks[1], &mut table_state);
+        }
+
+        let controls = "Tab: Complete | ↑↓: Select | Enter: Run | Esc: Cancel";
+        let controls_paragraph = Paragraph::new(controls)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(controls_paragraph, chunks[2]);
+
+        let border = Block::default()
+            .borders(Borders::ALL)
+            .title(" Command ")
+            .title_style(
+                Style::default()
+                    .fg(self.color_theme.get_selected_border_color())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(Style::default().fg(self.color_theme.get_selected_border_color()))
+            .style(Style::default().bg(Color::Black));
+        f.render_widget(border, popup_area);
+    }
+
+    /// The ranges the settings modal lists, work ranges first
+    pub fn settings_rows(&self) -> Vec<(TimeActivity, TimeRange)> {
+        let config = &self.time_config;
+        let work = config
+            .work_hours
+            .iter()
+            .map(|range| (TimeActivity::Work, *range));
+        let awake = config
+            .awake_hours
+            .iter()
+            .map(|range| (TimeActivity::Awake, *range));
+        work.chain(awake).collect()
+    }
+
+    fn settings_range_mut(&mut self) -> Option<&mut TimeRange> {
+        let config = &mut self.time_config;
+        match self.settings_row.checked_sub(config.work_hours.len()) {
+            None => config.work_hours.get_mut(self.settings_row),
+            Some(row) => config.awake_hours.get_mut(row),
+        }
+    }
+
+    /// Sits low on the screen so the timelines above stay visible as a live preview
+    fn render_settings_modal(&self, f: &mut Frame) {
+        let area = f.area();
+
+        let rows = self.settings_rows();
+        let modal_height = (rows.len().max(1) as u16 + 1 + 2 + 2).min(area.height); // ranges + spacer + controls + borders
+        let modal_width = area.width.saturating_sub(4).min(76);
+
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: area.height.saturating_sub(modal_height + 5), // Above the legend and footer
+            width: modal_width,
+            height: modal_height,
+        };
+
+        // Clear the background
+        f.render_widget(Clear, popup_area);
+
+        let inner = popup_area.inner(ratatui::layout::Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        let chunks = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([
+                Constraint::Min(1),    // Ranges
+                Constraint::Length(2), // Controls help
+            ])
+            .split(inner);
+
+        if rows.is_empty() {
+            let empty = Paragraph::new("No work or awake hours: the whole day counts as night")
+                .style(Style::default().fg(Color::DarkGray));
+            f.render_widget(empty, chunks[0]);
+        } else {
+            let editing = Style::default().add_modifier(Modifier::REVERSED);
+            let table_rows: Vec<Row> = rows
+                .iter()
+                .enumerate()
+                .map(|(row, (activity, range))| {
+                    let style = |field: SettingsField| {
+                        if row == self.settings_row && field == self.settings_field {
+                            editing
+                        } else {
+                            Style::default()
+                        }
+                    };
+                    Row::new(vec![
+                        Cell::from(activity.label()),
+                        Cell::from(range.start.to_string()).style(style(SettingsField::Start)),
+                        Cell::from(range.end.to_string()).style(style(SettingsField::End)),
+                        Cell::from(if range.is_overnight() {
+                            "overnight"
+                        } else {
+                            ""
+                        })
+                        .style(Style::default().fg(Color::DarkGray)),
+                    ])
+                })
+                .collect();
+
+            let table = Table::new(
+                table_rows,
+                [
+                    Constraint::Length(10), // Activity
+                    Constraint::Length(5),  // Start
+                    Constraint::Length(5),  // End
+                    Constraint::Min(9),     // Note
+                ],
+            )
+            .style(Style::default().fg(Color::White))
+            .row_highlight_style(
+                Style::default()
+                    .fg(self.color_theme.get_work_color())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("> ")
+            .column_spacing(2);
+
+            let mut table_state = TableState::default();
+            table_state.select(Some(self.settings_row));
+            f.render_stateful_widget(table, chunks[0], &mut table_state);
+        }
+
+        let controls = "↑↓: Range | Tab: Start/End | ←→: ±15 min | H/L: ±1 min | [ ]: ±1 hour\na/A: Add work/awake range | d: Delete | Enter: Save | Esc: Cancel";
+        let controls_paragraph = Paragraph::new(controls)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(controls_paragraph, chunks[1]);
+
+        let border = Block::default()
+            .borders(Borders::ALL)
+            .title(" Work Hours ")
+            .title_style(
+                Style::default()
+                    .fg(self.color_theme.get_selected_border_color())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(Style::default().fg(self.color_theme.get_selected_border_color()))
+            .style(Style::default().bg(Color::Black));
+        f.render_widget(border, popup_area);
+    }
+
+    /// The conversion table's columns: the selected zone and the (up to) two zones after it
+    fn table_zones(&self) -> Vec<&TimeZone> {
+        let zones = self.timezone_manager.zones();
+        (0..zones.len().min(3))
+            .map(|i| &zones[(self.selected_zone_index + i) % zones.len()])
+            .collect()
+    }
+
+    fn render_table_modal(&self, f: &mut Frame) {
+        let area = f.area();
+        let zones = self.table_zones();
+        let Some(first) = zones.first() else {
+            return;
+        };
+        let hours = table::day_hours(first.tz, self.table_date);
+
+        let modal_height = (hours.len() as u16 + 1 + 2 + 2 + 1).min(area.height.saturating_sub(2)); // rows + header + controls + borders
+        let modal_width = (zones.len() as u16 * 18 + 8).min(area.width.saturating_sub(2));
+
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: (area.height.saturating_sub(modal_height)) / 2,
+            width: modal_width,
+            height: modal_height,
+        };
+
+        // Clear the background
+        f.render_widget(Clear, popup_area);
+
+        let inner = popup_area.inner(ratatui::layout::Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        let chunks = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([
+                Constraint::Min(1),    // Hours
+                Constraint::Length(2), // Controls help
+            ])
+            .split(inner);
+
+        let use_12_hour = matches!(self.display_format, TimeFormat::TwelveHour);
+        let rows: Vec<Row> = hours
+            .iter()
+            .map(|hour| {
+                Row::new(zones.iter().map(|zone| {
+                    let mut style = if table::is_work_hour(*hour, zone.tz, &self.time_config) {
+                        Style::default().fg(self.color_theme.get_work_color())
+                    } else {
+                        Style::default().fg(Color::Gray)
+                    };
+                    if table::is_day_boundary(*hour, zone.tz) {
+                        style = style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+                    }
+                    Cell::from(table::format_cell(
+                        *hour,
+                        zone.tz,
+                        self.table_date,
+                        use_12_hour,
+                    ))
+                    .style(style)
+                }))
+            })
+            .collect();
+
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let table = Table::new(rows, zones.iter().map(|_| Constraint::Length(17)))
+            .header(Row::new(zones.iter().map(|zone| {
+                Cell::from(zone.effective_display_name().to_string()).style(bold)
+            })))
+            .style(Style::default().fg(Color::White))
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ")
+            .column_spacing(1);
+
+        let mut table_state = TableState::default();
+        table_state.select(Some(self.selected_table_row));
+        f.render_stateful_widget(table, chunks[0], &mut table_state);
+
+        let controls = "↑↓: Hour | ←→: Day | Enter: Jump | Esc: Close";
+        let controls_paragraph = Paragraph::new(controls)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(controls_paragraph, chunks[1]);
+
+        let border = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(
+                " {} in {} ",
+                self.table_date.format("%a %d %b %Y"),
+                first.effective_display_name()
+            ))
+            .title_style(
+                Style::default()
+                    .fg(self.color_theme.get_selected_border_color())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(Style::default().fg(self.color_theme.get_selected_border_color()))
+            .style(Style::default().bg(Color::Black));
+        f.render_widget(border, popup_area);
+    }
+
+    fn render_bookmarks_modal(&self, f: &mut Frame) {
+        let area = f.area();
+
+        let list_height = self.bookmarks.len().max(1) as u16 + 1; // +1 for the header
+        let modal_height = (list_height + 2 + 2 + 1).min(area.height.saturating_sub(2)); // list + spacer/controls + borders
+        let modal_width = area.width.saturating_sub(area.width / 4).min(90);
+
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: (area.height.saturating_sub(modal_height)) / 2,
+            width: modal_width,
+            height: modal_height,
+        };
+
+        // Clear the background
+        f.render_widget(Clear, popup_area);
+
+        let inner = popup_area.inner(ratatui::layout::Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        let chunks = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([
+                Constraint::Min(1),    // Bookmark list
+                Constraint::Length(2), // Controls help
+            ])
+            .split(inner);
+
+        let selected_zone = self.timezone_manager.zones().get(self.selected_zone_index);
+        if self.bookmarks.is_empty() {
+            let empty = Paragraph::new("No bookmarks yet. Press B in the main view to add one.")
+                .style(Style::default().fg(Color::DarkGray));
+            f.render_widget(empty, chunks[0]);
+        } else {
+            let time_format = match self.display_format {
+                TimeFormat::TwentyFourHour => "%a %d %b %H:%M",
+                TimeFormat::TwelveHour => "%a %d %b %-I:%M %p",
+            };
+            let rows: Vec<Row> = self
+                .bookmarks
+                .iter()
+                .map(|bookmark| {
+                    let when = match (bookmark.next_after(self.timeline_position), selected_zone) {
+                        (Some(at), Some(zone)) => format!(
+                            "{} {}",
+                            at.with_timezone(&zone.tz).format(time_format),
+                            zone.effective_display_name()
+                        ),
+                        (Some(at), None) => at.format("%a %d %b %H:%M UTC").to_string(),
+                        (None, _) => "unknown zone".to_string(),
+                    };
+                    Row::new(vec![
+                        Cell::from(bookmark.name.clone()),
+                        Cell::from(when),
+                        Cell::from(bookmark.describe_recurrence()),
+                    ])
+                })
+                .collect();
+
+            let bold = Style::default().add_modifier(Modifier::BOLD);
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Min(16),    // Name
+                    Constraint::Length(24), // Time in selected zone
+                    Constraint::Min(16),    // Repeats
+                ],
+            )
+            .header(Row::new(vec![
+                Cell::from("Name").style(bold),
+                Cell::from("Time").style(bold),
+                Cell::from("Repeats").style(bold),
+            ]))
+            .style(Style::default().fg(Color::White))
+            .row_highlight_style(
+                Style::default()
+                    .fg(self.color_theme.get_work_color())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("> ")
+            .column_spacing(1);
+
+            let mut table_state = TableState::default();
+            table_state.select(Some(self.selected_bookmark));
+            f.render_stateful_widget(table, chunks[0], &mut table_state);
+        }
+
+        let controls = "↑↓: Navigate | Enter: Jump | d: Delete | Esc: Close";
+        let controls_paragraph = Paragraph::new(controls)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(controls_paragraph, chunks[1]);
+
+        let border = Block::default()
+            .borders(Borders::ALL)
+            .title(" Bookmarks ")
+            .title_style(
+                Style::default()
+                    .fg(self.color_theme.get_bookmark_color())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(Style::default().fg(self.color_theme.get_bookmark_color()))
+            .style(Style::default().bg(Color::Black));
+        f.render_widget(border, popup_area);
+    }
+
+    fn render_add_bookmark_modal(&self, f: &mut Frame) {
+        let area = f.area();
+
+        let modal_height = 9;
+        let modal_width = area.width.saturating_sub(area.width / 3).min(70);
+
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: (area.height.saturating_sub(modal_height)) / 2,
+            width: modal_width,
+            height: modal_height,
+        };
+
+        // Clear the background
+        f.render_widget(Clear, popup_area);
+
+        let inner = popup_area.inner(ratatui::layout::Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        let chunks = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([
+                Constraint::Length(1), // What gets bookmarked
+                Constraint::Length(2), // Name field
+                Constraint::Length(1), // Repeat choice
+                Constraint::Length(1), // Spacer
+                Constraint::Length(1), // Controls help
+            ])
+            .split(inner);
+
+        let target = match self.bookmark_at_scrub(String::new()) {
+            Some(bookmark) if self.bookmark_repeat != BookmarkRepeat::Once => {
+                bookmark.describe_recurrence()
+            }
+            _ => self
+                .timeline_position
+                .format("%a %d %b %H:%M UTC")
+                .to_string(),
+        };
+        let target_paragraph = Paragraph::new(target).style(Style::default().fg(Color::Gray));
+        f.render_widget(target_paragraph, chunks[0]);
+
+        let name_paragraph = Paragraph::new(format!("Name: {}", self.bookmark_name_input))
+            .style(Style::default().fg(Color::White));
+        f.render_widget(name_paragraph, chunks[1]);
+
+        let repeat_paragraph = Paragraph::new(format!("Repeat: {}", self.bookmark_repeat.label()))
+            .style(Style::default().fg(Color::Gray));
+        f.render_widget(repeat_paragraph, chunks[2]);
+
+        let controls = "Tab: Change repeat | Enter: Save | Esc: Cancel";
+        let controls_paragraph = Paragraph::new(controls)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(controls_paragraph, chunks[4]);
+
+        let border = Block::default()
+            .borders(Borders::ALL)
+            .title(" Add Bookmark ")
+            .title_style(
+                Style::default()
+                    .fg(self.color_theme.get_bookmark_color())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(Style::default().fg(self.color_theme.get_bookmark_color()))
+            .style(Style::default().bg(Color::Black));
+        f.render_widget(border, popup_area);
+    }
+
+    fn render_countdowns_modal(&self, f: &mut Frame) {
+        let area = f.area();
+
+        let list_height = self.countdowns.len().max(1) as u16 + 1; // +1 for the header
+        let modal_height = (list_height + 2 + 2 + 1).min(area.height.saturating_sub(2)); // list + spacer/controls + borders
+        let modal_width = area.width.saturating_sub(area.width / 4).min(90);
+
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: (area.height.saturating_sub(modal_height)) / 2,
+            width: modal_width,
+            height: modal_height,
+        };
+
+        // Clear the background
+        f.render_widget(Clear, popup_area);
+
+        let inner = popup_area.inner(ratatui::layout::Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        let chunks = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([
+                Constraint::Min(1),    // Countdown list
+                Constraint::Length(2), // Controls help
+            ])
+            .split(inner);
+
+        let selected_zone = self.timezone_manager.zones().get(self.selected_zone_index);
+        if self.countdowns.is_empty() {
+            let empty = Paragraph::new("No countdowns yet. Press a to add one.")
+                .style(Style::default().fg(Color::DarkGray));
+            f.render_widget(empty, chunks[0]);
+        } else {
+            let time_format = match self.display_format {
+                TimeFormat::TwentyFourHour => "%a %d %b %H:%M",
+                TimeFormat::TwelveHour => "%a %d %b %-I:%M %p",
+            };
+            let rows: Vec<Row> = self
+                .countdowns
+                .iter()
+                .map(|countdown| {
+                    let (when, remaining) = match (countdown.instant(), selected_zone) {
+                        (Some(at), zone) => (
+                            match zone {
+                                Some(zone) => format!(
+                                    "{} {}",
+                                    at.with_timezone(&zone.tz).format(time_format),
+                                    zone.effective_display_name()
+                                ),
+                                None => at.format("%a %d %b %H:%M UTC").to_string(),
+                            },
+                            if at > self.current_time {
+                                format_remaining(at - self.current_time)
+                            } else {
+                                "expired".to_string()
+                            },
+                        ),
+                        (None, _) => ("unknown zone".to_string(), String::new()),
+                    };
+                    Row::new(vec![
+                        Cell::from(countdown.name.clone()),
+                        Cell::from(when),
+                        Cell::from(remaining),
+                    ])
+                })
+                .collect();
+
+            let bold = Style::default().add_modifier(Modifier::BOLD);
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Min(16),    // Name
+                    Constraint::Length(24), // Time in selected zone
+                    Constraint::Length(14), // Remaining
+                ],
+            )
+            .header(Row::new(vec![
+                Cell::from("Name").style(bold),
+                Cell::from("Time").style(bold),
+                Cell::from("Remaining").style(bold),
+            ]))
+            .style(Style::default().fg(Color::White))
+            .row_highlight_style(
+                Style::default()
+                    .fg(self.color_theme.get_work_color())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("> ")
+            .column_spacing(1);
+
+            let mut table_state = TableState::default();
+            table_state.select(Some(self.selected_countdown));
+            f.render_stateful_widget(table, chunks[0], &mut table_state);
+        }
+
+        let controls = "↑↓: Navigate | Enter: Jump | a: Add | d: Delete | Esc: Close";
+        let controls_paragraph = Paragraph::new(controls)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(controls_paragraph, chunks[1]);
+
+        let border = Block::default()
+            .borders(Borders::ALL)
+            .title(" Countdowns ")
+            .title_style(
+                Style::default()
+                    .fg(self.color_theme.get_countdown_color())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(Style::default().fg(self.color_theme.get_countdown_color()))
+            .style(Style::default().bg(Color::Black));
+        f.render_widget(border, popup_area);
     }
 
-    fn render_rename_zone_modal(&self, f: &mut Frame) {
+    fn render_add_countdown_modal(&self, f: &mut Frame) {
         let area = f.area();
 
-        let modal_height = 9; // Smaller modal for rename
-        let modal_width = area.width.saturating_sub(area.width / 3).min(60);
+        let modal_height = 8;
+        let modal_width = area.width.saturating_sub(area.width / 3).min(70);
 
         let popup_area = Rect {
             x: (area.width.saturating_sub(modal_width)) / 2,
@@ -1158,24 +4061,6 @@ impl App {
         // Clear the background
         f.render_widget(Clear, popup_area);
 
-        // Get current zone info
-        let current_zone = &self.timezone_manager.zones()[self.selected_zone_index];
-        let city_name = self
-            .timezone_manager
-            .zones()
-            .iter()
-            .enumerate()
-            .find(|(idx, zone)| *idx == self.selected_zone_index && zone.tz == current_zone.tz)
-            .and_then(|(_, zone)| {
-                let available = crate::time::TimeZoneManager::get_all_available_timezones();
-                available
-                    .iter()
-                    .find(|(tz, _, _, _, _)| *tz == zone.tz)
-                    .map(|(_, name, _, _, _)| name.clone())
-            })
-            .unwrap_or_else(|| current_zone.tz.to_string());
-
-        // Split the modal into sections
         let inner = popup_area.inner(ratatui::layout::Margin {
             horizontal: 1,
             vertical: 1,
@@ -1183,43 +4068,67 @@ impl App {
         let chunks = Layout::default()
             .direction(LayoutDirection::Vertical)
             .constraints([
-                Constraint::Length(2), // Zone info
-                Constraint::Length(2), // Input field
+                Constraint::Length(1), // Name field
+                Constraint::Length(2), // When field
                 Constraint::Length(1), // Spacer
-                Constraint::Length(2), // Controls help
+                Constraint::Length(1), // Controls help
             ])
             .split(inner);
 
-        // Render zone info
-        let zone_info = format!("Renaming: {city_name}");
-        let zone_paragraph = Paragraph::new(zone_info)
-            .style(ratatui::style::Style::default().fg(ratatui::style::Color::Gray));
-        f.render_widget(zone_paragraph, chunks[0]);
+        let field_style = |field: CountdownField| {
+            if self.countdown_field == field {
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            }
+        };
+        let marker = |field: CountdownField| {
+            if self.countdown_field == field {
+                "> "
+            } else {
+                "  "
+            }
+        };
 
-        // Render input field
-        let input_text = format!("Custom name: {}", self.rename_zone_input);
-        let input_paragraph = Paragraph::new(input_text)
-            .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
-        f.render_widget(input_paragraph, chunks[1]);
+        let name_paragraph = Paragraph::new(format!(
+            "{}Name: {}",
+            marker(CountdownField::Name),
+            self.countdown_name_input
+        ))
+        .style(field_style(CountdownField::Name));
+        f.render_widget(name_paragraph, chunks[0]);
 
-        // Render controls help
-        let controls = "Enter: Save | Esc: Cancel | Empty to remove custom name";
+        let zone_name = self
+            .timezone_manager
+            .zones()
+            .get(self.selected_zone_index)
+            .map_or("UTC", |zone| zone.effective_display_name());
+        let when_paragraph = Paragraph::new(format!(
+            "{}When ({zone_name}): {}",
+            marker(CountdownField::When),
+            self.countdown_when_input
+        ))
+        .style(field_style(CountdownField::When));
+        f.render_widget(when_paragraph, chunks[1]);
+
+        let controls = "Tab: Switch field | Enter: Save | Esc: Cancel";
         let controls_paragraph = Paragraph::new(controls)
-            .style(ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray))
+            .style(Style::default().fg(Color::DarkGray))
             .alignment(Alignment::Center);
         f.render_widget(controls_paragraph, chunks[3]);
 
-        // Render the modal border
         let border = Block::default()
             .borders(Borders::ALL)
-            .title(" Rename Timezone ")
+            .title(" Add Countdown ")
             .title_style(
-                ratatui::style::Style::default()
-                    .fg(ratatui::style::Color::Blue)
+                Style::default()
+                    .fg(self.color_theme.get_countdown_color())
                     .add_modifier(Modifier::BOLD),
             )
-            .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Blue))
-            .style(ratatui::style::Style::default().bg(ratatui::style::Color::Black));
+            .border_style(Style::default().fg(self.color_theme.get_countdown_color()))
+            .style(Style::default().bg(Color::Black));
         f.render_widget(border, popup_area);
     }
 
@@ -1361,9 +4270,12 @@ mod tests {
 
     #[test]
     fn test_confirm_rename() {
-        let mut app = App::default(); // Use default to avoid loading config
-        app.renaming_zone = true;
-        app.rename_zone_input = "Bob (Sales)".to_string();
+        // Use default to avoid loading config
+        let mut app = App {
+            renaming_zone: true,
+            rename_zone_input: "Bob (Sales)".to_string(),
+            ..App::default()
+        };
 
         // Store initial state (may or may not have custom label)
         let _initial_label = app.timezone_manager.zones()[app.selected_zone_index]
@@ -1541,6 +4453,7 @@ mod tests {
             color_theme: crate::config::ColorTheme::default(),
             show_date: false,
             show_sun_times: true,
+            ..AppConfig::default()
         };
 
         // Create app from config
@@ -1708,4 +4621,819 @@ mod tests {
         assert!(app.adding_zone);
         assert!(!app.zone_search_results.is_empty());
     }
+
+    #[test]
+    fn test_export_modal() {
+        let mut app = App::default();
+
+        app.update(Message::StartExport);
+        assert!(app.exporting);
+        assert_eq!(app.export_field, ExportField::Title);
+
+        app.update(Message::UpdateExportInput("Roadmap".to_string()));
+        app.update(Message::SwitchExportField);
+        app.update(Message::UpdateExportInput("abc".to_string()));
+        assert_eq!(app.export_title_input, "Roadmap");
+        assert_eq!(app.export_duration_input, "abc");
+
+        // An invalid duration keeps the modal open and explains why
+        app.update(Message::ConfirmExport);
+        assert!(app.exporting);
+        assert!(app
+            .status_message
+            .as_ref()
+            .is_some_and(|(message, _)| message.contains("Invalid duration")));
+
+        app.update(Message::CancelExport);
+        assert!(!app.exporting);
+
+        // Invites go to the export directory and the summary is copied
+        let dir = std::env::temp_dir().join(format!("alltz-export-{}", std::process::id()));
+        app.export_dir = Some(dir.to_string_lossy().into_owned());
+        app.timeline_position = DateTime::parse_from_rfc3339("2025-07-22T16:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        app.update(Message::StartExport);
+        app.update(Message::ConfirmExport);
+        assert!(!app.exporting);
+        assert!(dir.join("alltz-20250722-1600.ics").exists());
+        assert_eq!(
+            app.pending_clipboard,
+            Some(app.timezone_manager.format_multi_zone_summary(
+                app.timeline_position,
+                matches!(app.display_format, TimeFormat::TwelveHour)
+            ))
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_yank_formats() {
+        let mut app = App::new();
+        app.timezone_manager = TimeZoneManager::new();
+        app.timezone_manager
+            .add_zone(TimeZone::from_tz(chrono_tz::Europe::London));
+        app.selected_zone_index = 0;
+        app.display_format = TimeFormat::TwentyFourHour;
+        app.timeline_position = DateTime::parse_from_rfc3339("2025-07-22T16:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(app.yank_text(YankFormat::IsoUtc), "2025-07-22T16:00:00Z");
+        assert_eq!(
+            app.yank_text(YankFormat::SelectedLocal),
+            "2025-07-22 17:00 BST"
+        );
+        assert_eq!(app.yank_text(YankFormat::UnixTimestamp), "1753200000");
+
+        app.update(Message::StartYank);
+        assert!(app.yanking);
+        app.update(Message::Yank(YankFormat::UnixTimestamp));
+        assert!(!app.yanking);
+        assert_eq!(app.pending_clipboard.as_deref(), Some("1753200000"));
+        assert!(app
+            .status_message
+            .as_ref()
+            .is_some_and(|(message, _)| message.contains("1753200000")));
+    }
+
+    #[test]
+    fn test_zoom_scrub_step() {
+        let mut app = App::new();
+        app.timeline_zoom = TimelineZoom::Auto;
+        app.timeline_position = DateTime::parse_from_rfc3339("2025-07-22T16:20:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        // Zoomed in, h/l step between 15-minute cells
+        app.update(Message::ZoomIn);
+        app.update(Message::ZoomIn);
+        app.update(Message::ZoomIn);
+        assert_eq!(app.timeline_zoom, TimelineZoom::Hours6);
+        assert_eq!(app.to_config().timeline_zoom, TimelineZoom::Hours6);
+
+        app.update(Message::ScrubTimeline(Direction::Right));
+        assert_eq!(app.timeline_position.minute(), 30);
+        app.update(Message::ScrubTimeline(Direction::Left));
+        assert_eq!(app.timeline_position.minute(), 15);
+        app.update(Message::ScrubTimeline(Direction::Left));
+        assert_eq!(app.timeline_position.minute(), 0);
+
+        // Back at the default zoom, steps are whole hours again
+        app.update(Message::ResetZoom);
+        assert_eq!(app.timeline_zoom, TimelineZoom::Auto);
+        app.update(Message::ScrubTimeline(Direction::Right));
+        assert_eq!(app.timeline_position.hour(), 17);
+        assert_eq!(app.timeline_position.minute(), 0);
+    }
+
+    #[test]
+    fn test_cycle_ruler_mode() {
+        let mut app = App::new();
+        app.ruler_mode = RulerMode::Off;
+
+        app.update(Message::CycleRulerMode);
+        assert_eq!(app.ruler_mode, RulerMode::SelectedZone);
+        app.update(Message::CycleRulerMode);
+        assert_eq!(app.ruler_mode, RulerMode::Utc);
+        assert_eq!(app.to_config().ruler_mode, RulerMode::Utc);
+        app.update(Message::CycleRulerMode);
+        assert_eq!(app.ruler_mode, RulerMode::Off);
+    }
+
+    #[test]
+    fn test_cycle_shading_mode() {
+        let mut app = App::new();
+        app.shading_mode = ShadingMode::Schedule;
+
+        app.update(Message::CycleShadingMode);
+        assert_eq!(app.shading_mode, ShadingMode::Daylight);
+        assert_eq!(app.to_config().shading_mode, ShadingMode::Daylight);
+        app.update(Message::CycleShadingMode);
+        assert_eq!(app.shading_mode, ShadingMode::Schedule);
+    }
+
+    #[test]
+    fn test_unknown_cities_survive_save() {
+        let config = AppConfig {
+            zones: vec![
+                ZoneConfigCompat::Simple("London".to_string()),
+                ZoneConfigCompat::Simple("Atlantis".to_string()),
+                ZoneConfigCompat::Simple("Tokyo".to_string()),
+            ],
+            selected_zone_index: 2,
+            ..AppConfig::default()
+        };
+        let mut app = App::from_config(config);
+        assert_eq!(app.timezone_manager.zone_count(), 2);
+        assert_eq!(app.unresolved_zones.len(), 1);
+        let selected = &app.timezone_manager.zones()[app.selected_zone_index];
+        assert_eq!(selected.get_city_name(), "Tokyo");
+
+        // Saving keeps the unknown city in its place and the selection on Tokyo
+        let saved = app.to_config();
+        assert_eq!(saved.version, CONFIG_VERSION);
+        let names: Vec<&str> = saved.zones.iter().map(|zone| zone.city_name()).collect();
+        assert_eq!(names, vec!["London", "Atlantis", "Tokyo"]);
+        assert_eq!(saved.selected_zone_index, 2);
+
+        app.config_issues = vec!["config.toml:2: unknown city \"Atlantis\"".to_string()];
+        app.update(Message::DismissConfigIssues);
+        assert!(app.config_issues.is_empty());
+    }
+
+    #[test]
+    fn test_apply_config_keeps_selection() {
+        let zones = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| ZoneConfigCompat::Simple(name.to_string()))
+                .collect()
+        };
+        let selected_code = |app: &App| {
+            app.timezone_manager.zones()[app.selected_zone_index]
+                .display_name
+                .clone()
+        };
+        let mut app = App::from_config(AppConfig {
+            zones: zones(&["Los Angeles", "London", "Tokyo"]),
+            ..AppConfig::default()
+        });
+        app.selected_zone_index = 1;
+        assert_eq!(selected_code(&app), "LON");
+
+        // London moves and the theme changes: the selection follows London
+        app.apply_config(AppConfig {
+            zones: zones(&["Tokyo", "Sydney", "London"]),
+            color_theme: ColorTheme::Forest,
+            ..AppConfig::default()
+        });
+        assert_eq!(selected_code(&app), "LON");
+        assert_eq!(app.color_theme, ColorTheme::Forest);
+        assert_eq!(app.timezone_manager.zone_count(), 3);
+
+        // London is gone: fall back to the file's selection
+        app.apply_config(AppConfig {
+            zones: zones(&["Tokyo", "Sydney"]),
+            selected_zone_index: 1,
+            ..AppConfig::default()
+        });
+        assert_eq!(app.selected_zone_index, 1);
+    }
+
+    #[test]
+    fn test_reload_waits_for_modals_and_clears_undo() {
+        let dir = std::env::temp_dir().join(format!("alltz-reload-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        let original = "zones = [\"London\", \"Tokyo\"]\n";
+        std::fs::write(&path, original).unwrap();
+        let loaded = AppConfig::parse(original, &path);
+        let mut app = App::from_config(loaded.config);
+        app.config_file = loaded.file;
+
+        app.update(Message::RemoveCurrentZone);
+        app.pending_save = None;
+        std::fs::write(&path, "zones = [\"Sydney\"]\n").unwrap();
+
+        // Nothing changes under an open modal
+        app.update(Message::OpenSettings);
+        app.update(Message::Tick);
+        assert_eq!(app.timezone_manager.zone_count(), 1);
+        assert_ne!(app.timezone_manager.zones()[0].display_name, "SYD");
+        app.update(Message::CancelSettings);
+
+        // Once it closes the edit is picked up, and undo can't bring the old zones back over it
+        app.update(Message::Tick);
+        assert_eq!(app.timezone_manager.zones()[0].display_name, "SYD");
+        app.update(Message::Undo);
+        assert_eq!(app.timezone_manager.zones()[0].display_name, "SYD");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_undo_redo_zone_changes() {
+        let mut app = App::from_config(AppConfig {
+            zones: vec![
+                ZoneConfigCompat::Simple("London".to_string()),
+                ZoneConfigCompat::Full(crate::config::ZoneConfig {
+                    city_name: "Tokyo".to_string(),
+                    custom_label: Some("Alice".to_string()),
+                }),
+            ],
+            ..AppConfig::default()
+        });
+        app.selected_zone_index = 1;
+
+        app.update(Message::RemoveCurrentZone);
+        assert_eq!(app.timezone_manager.zone_count(), 1);
+
+        app.update(Message::Undo);
+        assert_eq!(app.timezone_manager.zone_count(), 2);
+        assert_eq!(app.selected_zone_index, 1);
+        assert_eq!(
+            app.timezone_manager.zones()[1].custom_label.as_deref(),
+            Some("Alice")
+        );
+        assert_eq!(
+            app.status_message.as_ref().map(|(m, _)| m.as_str()),
+            Some("Undid remove Alice")
+        );
+
+        app.update(Message::Redo);
+        assert_eq!(app.timezone_manager.zone_count(), 1);
+        app.update(Message::Undo);
+
+        // Clearing a label is undoable, and a new change drops the redo stack
+        app.update(Message::ClearCustomName);
+        assert_eq!(app.timezone_manager.zones()[1].custom_label, None);
+        assert!(app.zone_history.redo.is_empty());
+        app.update(Message::Undo);
+        assert_eq!(
+            app.timezone_manager.zones()[1].custom_label.as_deref(),
+            Some("Alice")
+        );
+
+        // Quick-picking a search result with 1-9 is undoable too
+        app.update(Message::StartAddZone);
+        app.update(Message::UpdateAddZoneInput("Sydney".to_string()));
+        app.update(Message::SelectSearchResult(0));
+        assert_eq!(app.timezone_manager.zone_count(), 3);
+        app.update(Message::Undo);
+        assert_eq!(app.timezone_manager.zone_count(), 2);
+        assert!(app
+            .status_message
+            .as_ref()
+            .is_some_and(|(m, _)| m.starts_with("Undid add Sydney")));
+
+        app.zone_history = ZoneHistory::default();
+        app.update(Message::Undo);
+        assert_eq!(
+            app.status_message.as_ref().map(|(m, _)| m.as_str()),
+            Some("Nothing to undo")
+        );
+    }
+
+    #[test]
+    fn test_add_and_jump_to_bookmarks() {
+        let mut app = App::from_config(AppConfig {
+            zones: vec![ZoneConfigCompat::Simple("London".to_string())],
+            ..AppConfig::default()
+        });
+        let scrubbed = DateTime::parse_from_rfc3339("2025-10-21T08:00:30Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        app.timeline_position = scrubbed;
+
+        // A name is required
+        app.update(Message::StartAddBookmark);
+        app.update(Message::ConfirmAddBookmark);
+        assert!(app.adding_bookmark);
+        assert!(app.bookmarks.is_empty());
+
+        app.update(Message::UpdateBookmarkInput("Cutover".to_string()));
+        app.update(Message::ConfirmAddBookmark);
+        assert!(!app.adding_bookmark);
+        assert_eq!(
+            app.bookmarks[0].at,
+            Some(scrubbed - chrono::Duration::seconds(30))
+        );
+
+        // Weekly: the local time and weekday in the selected zone
+        app.update(Message::StartAddBookmark);
+        app.update(Message::UpdateBookmarkInput("Sync".to_string()));
+        app.update(Message::CycleBookmarkRepeat);
+        app.update(Message::CycleBookmarkRepeat);
+        assert_eq!(app.bookmark_repeat, BookmarkRepeat::Weekly);
+        app.update(Message::ConfirmAddBookmark);
+        assert_eq!(
+            app.bookmarks[1].describe_recurrence(),
+            "every Tue 09:00 London"
+        );
+        assert_eq!(app.to_config().bookmarks, app.bookmarks);
+
+        // Jumping to a recurring bookmark lands on its next occurrence, after the clocks change
+        app.update(Message::ToggleBookmarks);
+        app.update(Message::NavigateBookmarks(Direction::Down));
+        app.update(Message::JumpToBookmark);
+        assert!(!app.show_bookmarks);
+        assert_eq!(
+            app.timeline_position,
+            DateTime::parse_from_rfc3339("2025-10-28T09:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc)
+        );
+
+        app.update(Message::ToggleBookmarks);
+        app.update(Message::DeleteBookmark);
+        assert_eq!(app.bookmarks.len(), 1);
+        assert_eq!(app.selected_bookmark, 0);
+        app.update(Message::JumpToBookmark);
+        assert_eq!(
+            app.timeline_position,
+            scrubbed - chrono::Duration::seconds(30)
+        );
+    }
+
+    #[test]
+    fn test_countdowns() {
+        let mut app = App::from_config(AppConfig {
+            zones: vec![ZoneConfigCompat::Simple("Tokyo".to_string())],
+            ..AppConfig::default()
+        });
+        let now = DateTime::parse_from_rfc3339("2025-11-02T23:58:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        app.current_time = now;
+        app.countdowns_checked_at = now;
+
+        app.update(Message::ToggleCountdowns);
+        app.update(Message::StartAddCountdown);
+        assert_eq!(
+            app.countdown_when_input,
+            app.timeline_position
+                .with_timezone(&chrono_tz::Asia::Tokyo)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        );
+        app.update(Message::UpdateCountdownInput("Launch".to_string()));
+        app.update(Message::SwitchCountdownField);
+        app.update(Message::UpdateCountdownInput("2025-11-03 9am".to_string()));
+        app.update(Message::ConfirmAddCountdown);
+        assert!(app.adding_countdown);
+        assert!(app.countdowns.is_empty());
+
+        app.update(Message::UpdateCountdownInput(
+            "2025-11-03 09:00".to_string(),
+        ));
+        app.update(Message::ConfirmAddCountdown);
+        assert!(!app.adding_countdown);
+        assert!(app.show_countdowns);
+        assert_eq!(app.countdowns[0].zone.as_deref(), Some("Tokyo"));
+        assert_eq!(app.to_config().countdowns, app.countdowns);
+        assert_eq!(
+            app.countdown_readout().as_deref(),
+            Some("⏳ Launch in 00:02:00")
+        );
+
+        // Expiry alerts once, with the bell, and the header says so for a minute
+        app.current_time = now + chrono::Duration::minutes(2);
+        app.check_countdowns();
+        assert!(app.pending_bell);
+        assert_eq!(app.status_message.as_ref().unwrap().0, "⏰ Launch is due");
+        assert_eq!(app.countdown_readout().as_deref(), Some("⏰ Launch is due"));
+        app.pending_bell = false;
+        app.current_time = now + chrono::Duration::minutes(4);
+        app.check_countdowns();
+        assert!(!app.pending_bell);
+        assert_eq!(app.countdown_readout(), None);
+
+        app.update(Message::JumpToCountdown);
+        assert_eq!(app.timeline_position, now + chrono::Duration::minutes(2));
+        app.update(Message::ToggleCountdowns);
+        app.update(Message::DeleteCountdown);
+        assert!(app.countdowns.is_empty());
+    }
+
+    /// Stands in for the desktop notification service
+    #[derive(Debug, Default)]
+    struct RecordingNotifier {
+        sent: std::rc::Rc<std::cell::RefCell<Vec<(String, String)>>>,
+    }
+
+    impl Notifier for RecordingNotifier {
+        fn notify(&self, summary: &str, body: &str) -> Result<(), String> {
+            self.sent
+                .borrow_mut()
+                .push((summary.to_string(), body.to_string()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_reminders_fire_on_tick() {
+        let mut app = App::from_config(AppConfig {
+            reminders: vec!["every weekday 09:55 in Europe/London: standup"
+                .parse()
+                .unwrap()],
+            reminder_notifications: true,
+            ..AppConfig::default()
+        });
+        let notifier = RecordingNotifier::default();
+        let sent = notifier.sent.clone();
+        app.notifier = Box::new(notifier);
+
+        // Monday after the clocks went back: 09:55 GMT
+        let before = DateTime::parse_from_rfc3339("2025-10-27T09:54:30Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        app.reminders_checked_at = before;
+        app.current_time = before + chrono::Duration::seconds(20);
+        app.check_reminders();
+        assert!(app.due_reminders.is_empty());
+
+        app.current_time = before + chrono::Duration::seconds(40);
+        app.check_reminders();
+        assert_eq!(
+            app.due_reminders,
+            vec!["⏰ 09:55 Europe/London · standup".to_string()]
+        );
+        assert_eq!(
+            *sent.borrow(),
+            vec![("standup".to_string(), "09:55 Europe/London".to_string())]
+        );
+
+        // Only once, and Esc clears the banner
+        app.current_time = before + chrono::Duration::seconds(60);
+        app.check_reminders();
+        assert_eq!(app.due_reminders.len(), 1);
+        app.update(Message::DismissReminders);
+        assert!(app.due_reminders.is_empty());
+
+        // Coming back hours later (e.g. after sleep) doesn't replay old reminders
+        app.reminders_checked_at = before - chrono::Duration::hours(3);
+        app.current_time = before + chrono::Duration::hours(2);
+        app.check_reminders();
+        assert!(app.due_reminders.is_empty());
+        assert_eq!(sent.borrow().len(), 1);
+        assert_eq!(app.to_config().reminders, app.reminders);
+    }
+
+    #[test]
+    fn test_relative_offset_reference() {
+        let mut app = App::from_config(AppConfig {
+            zones: vec![
+                ZoneConfigCompat::Simple("London".to_string()),
+                ZoneConfigCompat::Simple("Tokyo".to_string()),
+            ],
+            ..AppConfig::default()
+        });
+        app.timeline_position = DateTime::parse_from_rfc3339("2025-07-22T20:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(app.offset_reference(), None);
+
+        app.update(Message::CycleRelativeOffsets);
+        assert_eq!(app.relative_offsets, RelativeOffsetMode::SelectedZone);
+        app.selected_zone_index = app
+            .timezone_manager
+            .zones()
+            .iter()
+            .position(|zone| zone.display_name == "LON")
+            .unwrap();
+        assert_eq!(
+            app.offset_reference(),
+            Some(OffsetReference {
+                offset_seconds: 3600,
+                date: chrono::NaiveDate::from_ymd_opt(2025, 7, 22).unwrap(),
+            })
+        );
+        assert_eq!(
+            app.to_config().relative_offsets,
+            RelativeOffsetMode::SelectedZone
+        );
+
+        app.update(Message::CycleRelativeOffsets);
+        assert!(app.offset_reference().is_some());
+        app.update(Message::CycleRelativeOffsets);
+        assert_eq!(app.relative_offsets, RelativeOffsetMode::Off);
+    }
+
+    #[test]
+    fn test_info_panel_rows() {
+        let mut app = App {
+            timezone_manager: TimeZoneManager::new(),
+            ..App::default()
+        };
+        app.timezone_manager.add_timezone_by_name("London");
+        app.selected_zone_index = 0;
+        app.timeline_position = DateTime::parse_from_rfc3339("2025-07-22T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert!(!app.show_info_panel);
+        app.update(Message::ToggleInfoPanel);
+        assert!(app.show_info_panel);
+        assert!(app.to_config().show_info_panel);
+
+        let rows: std::collections::HashMap<_, _> = app.info_panel_rows().into_iter().collect();
+        assert_eq!(rows["IANA"], "Europe/London");
+        assert_eq!(rows["Country"], "UK");
+        assert_eq!(rows["Offset"], "UTC+1 (BST)");
+        assert_eq!(rows["Next DST"], "Sun 26 Oct 2025 01:00 UTC+1 → UTC+0");
+        assert_eq!(rows["Date"], "Tue 22 Jul 2025 · week 30");
+        assert_eq!(rows["Activity"], "Work hours");
+        assert_eq!(rows["All-day events"], "none");
+        assert!(rows["Location"].ends_with("°W"));
+        assert!(rows.contains_key("Day length"));
+
+        // All-day events on the zone's date are listed; timed ones aren't
+        let (events, _) = crate::calendar::parse_ics(concat!(
+            "BEGIN:VCALENDAR\r\n",
+            "BEGIN:VEVENT\r\nSUMMARY:Offsite\r\nDTSTART;VALUE=DATE:20250722\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nSUMMARY:Lunch\r\nDTSTART:20250722T110000Z\r\nDURATION:PT2H\r\nEND:VEVENT\r\n",
+            "END:VCALENDAR\r\n",
+        ))
+        .unwrap();
+        app.calendar = Calendar { events };
+        let rows: std::collections::HashMap<_, _> = app.info_panel_rows().into_iter().collect();
+        assert_eq!(rows["All-day events"], "Offsite");
+    }
+
+    #[test]
+    fn test_week_grid_cursor_moves_timeline() {
+        let mut app = App {
+            timezone_manager: TimeZoneManager::new(),
+            ..App::default()
+        };
+        app.timezone_manager.add_timezone_by_name("Tokyo");
+        app.selected_zone_index = 0;
+        app.timeline_position = DateTime::parse_from_rfc3339("2025-07-22T03:20:00Z")
+            .unwrap()
+            .with_timezone(&Utc); // 12:20 in Tokyo
+
+        app.update(Message::ToggleWeekGrid);
+        assert!(app.show_week_grid);
+        app.update(Message::MoveWeekGridCursor(Direction::Right));
+        assert_eq!(
+            app.timeline_position.to_rfc3339(),
+            "2025-07-22T04:00:00+00:00"
+        );
+        app.update(Message::MoveWeekGridCursor(Direction::Down));
+        assert_eq!(
+            app.timeline_position.to_rfc3339(),
+            "2025-07-23T04:00:00+00:00"
+        );
+        app.update(Message::MoveWeekGridCursor(Direction::Up));
+        app.update(Message::MoveWeekGridCursor(Direction::Left));
+        assert_eq!(
+            app.timeline_position.to_rfc3339(),
+            "2025-07-22T03:00:00+00:00"
+        );
+
+        app.update(Message::ToggleWeekGrid);
+        assert!(!app.show_week_grid);
+    }
+
+    #[test]
+    fn test_conversion_table() {
+        let mut app = App::from_config(AppConfig {
+            zones: vec![
+                ZoneConfigCompat::Simple("Los Angeles".to_string()),
+                ZoneConfigCompat::Simple("London".to_string()),
+                ZoneConfigCompat::Simple("Tokyo".to_string()),
+                ZoneConfigCompat::Simple("Sydney".to_string()),
+            ],
+            ..AppConfig::default()
+        });
+        app.selected_zone_index = 2;
+        let names: Vec<_> = app
+            .table_zones()
+            .iter()
+            .map(|zone| zone.get_city_name())
+            .collect();
+        assert_eq!(names, ["Tokyo", "Sydney", "Los Angeles"]);
+
+        app.selected_zone_index = 0;
+        app.timeline_position = DateTime::parse_from_rfc3339("2025-07-22T16:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc); // 09:30 in Los Angeles
+        app.update(Message::ToggleTable);
+        assert!(app.show_table);
+        assert_eq!(
+            app.table_date,
+            NaiveDate::from_ymd_opt(2025, 7, 22).unwrap()
+        );
+        assert_eq!(app.selected_table_row, 9);
+
+        app.update(Message::NavigateTable(Direction::Down));
+        app.update(Message::NavigateTable(Direction::Right));
+        app.update(Message::JumpToTableRow);
+        assert!(!app.show_table);
+        assert_eq!(
+            app.timeline_position.to_rfc3339(),
+            "2025-07-23T17:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_zone_filter() {
+        let mut app = App::from_config(AppConfig::default());
+        let zone_count = app.timezone_manager.zone_count();
+        app.selected_zone_index = 0; // Los Angeles
+
+        app.update(Message::StartZoneFilter);
+        assert!(app.filtering_zones);
+        app.update(Message::UpdateZoneFilter("usa".to_string()));
+        assert_eq!(app.visible_zone_indices(), vec![0, 1]);
+        app.update(Message::UpdateZoneFilter("europe".to_string()));
+        // The selection moves to the first match: London
+        assert_eq!(app.visible_zone_indices(), vec![3, 4]);
+        assert_eq!(app.selected_zone_index, 3);
+        app.update(Message::ConfirmZoneFilter);
+        assert!(!app.filtering_zones);
+
+        app.update(Message::NextFilterMatch(Direction::Down));
+        assert_eq!(app.selected_zone_index, 4);
+        app.update(Message::NextFilterMatch(Direction::Down));
+        assert_eq!(app.selected_zone_index, 3);
+        app.update(Message::NextFilterMatch(Direction::Up));
+        assert_eq!(app.selected_zone_index, 4);
+        app.update(Message::NavigateZone(Direction::Up));
+        assert_eq!(app.selected_zone_index, 3);
+
+        // The saved zone list is untouched
+        assert_eq!(app.timezone_manager.zone_count(), zone_count);
+        assert_eq!(app.to_config().zones.len(), zone_count);
+
+        app.update(Message::ClearZoneFilter);
+        assert_eq!(app.visible_zone_indices().len(), zone_count);
+
+        // A filter matching nothing is dropped when confirmed
+        app.update(Message::StartZoneFilter);
+        app.update(Message::UpdateZoneFilter("atlantis".to_string()));
+        app.update(Message::ConfirmZoneFilter);
+        assert!(app.zone_filter.is_empty());
+    }
+
+    #[test]
+    fn test_command_palette() {
+        let mut app = App::from_config(AppConfig::default());
+        let zone_count = app.timezone_manager.zone_count();
+
+        app.update(Message::StartPalette);
+        assert!(app.palette_open);
+        app.update(Message::UpdatePaletteInput("the".to_string()));
+        app.update(Message::RunPalette);
+        // Commands taking arguments complete rather than run
+        assert!(app.palette_open);
+        assert_eq!(app.palette_input, "theme ");
+        app.update(Message::UpdatePaletteInput("theme forest".to_string()));
+        app.update(Message::RunPalette);
+        assert!(!app.palette_open);
+        assert_eq!(app.color_theme, ColorTheme::Forest);
+
+        app.update(Message::StartPalette);
+        app.update(Message::UpdatePaletteInput(
+            "add Lisbon as \"Ana\"".to_string(),
+        ));
+        app.update(Message::RunPalette);
+        assert_eq!(app.timezone_manager.zone_count(), zone_count + 1);
+        let lisbon = app
+            .timezone_manager
+            .zones()
+            .iter()
+            .find(|zone| zone.get_city_name() == "Lisbon")
+            .unwrap();
+        assert_eq!(lisbon.custom_label.as_deref(), Some("Ana"));
+
+        app.update(Message::StartPalette);
+        app.update(Message::UpdatePaletteInput("wee".to_string()));
+        app.update(Message::RunPalette);
+        assert!(app.show_week_grid);
+
+        app.update(Message::StartPalette);
+        app.update(Message::UpdatePaletteInput("work 7-15".to_string()));
+        app.update(Message::RunPalette);
+        assert_eq!(
+            app.time_config.work_hours,
+            [TimeRange::new(ClockTime::new(7, 0), ClockTime::new(15, 0))]
+        );
+
+        // Mistakes explain themselves in the footer, and invalid commands stay open to fix
+        app.update(Message::StartPalette);
+        app.update(Message::UpdatePaletteInput("goto someday".to_string()));
+        app.update(Message::RunPalette);
+        assert!(!app.palette_open);
+        assert!(app
+            .status_message
+            .as_ref()
+            .is_some_and(|(message, _)| message.contains("someday")));
+        app.update(Message::StartPalette);
+        app.update(Message::UpdatePaletteInput("theme plaid".to_string()));
+        app.update(Message::RunPalette);
+        assert!(app.palette_open);
+        app.update(Message::CancelPalette);
+        assert!(!app.palette_open);
+    }
+
+    #[test]
+    fn test_settings_modal() {
+        let mut app = App::from_config(AppConfig::default());
+        app.config_writable = true;
+        let range = |start, end| TimeRange::new(ClockTime::new(start, 0), ClockTime::new(end, 0));
+
+        app.update(Message::OpenSettings);
+        assert!(app.show_settings);
+        app.update(Message::AdjustSetting(30));
+        // The change is live before saving
+        assert_eq!(app.time_config.work_hours[0].start, ClockTime::new(8, 30));
+        app.update(Message::SwitchSettingsField);
+        app.update(Message::AdjustSetting(-1));
+        assert_eq!(app.time_config.work_hours[0].end, ClockTime::new(17, 59));
+
+        // Esc puts the old hours back without saving
+        app.update(Message::CancelSettings);
+        assert!(!app.show_settings);
+        assert_eq!(app.time_config, TimeDisplayConfig::default());
+        assert!(app.pending_save.is_none());
+
+        // Split the day into two shifts: shorten the first, then add a second
+        app.update(Message::OpenSettings);
+        app.update(Message::SwitchSettingsField);
+        app.update(Message::AdjustSetting(-6 * 60));
+        app.update(Message::AddSettingsRange(TimeActivity::Work));
+        assert_eq!(app.settings_row, 1);
+        app.update(Message::AdjustSetting(4 * 60));
+        app.update(Message::SwitchSettingsField);
+        app.update(Message::AdjustSetting(60));
+        // Starts stop a minute short of their range's end
+        app.update(Message::AdjustSetting(24 * 60));
+        assert_eq!(
+            app.time_config.work_hours[1],
+            TimeRange::new(ClockTime::new(16, 59), ClockTime::new(17, 0))
+        );
+        app.update(Message::AdjustSetting(-4 * 60 + 1));
+        assert_eq!(app.time_config.work_hours, [range(8, 12), range(13, 17)]);
+        assert_eq!(
+            app.time_config.get_time_activity_at(ClockTime::new(12, 30)),
+            TimeActivity::Awake
+        );
+
+        // The awake range follows the work ranges and can be deleted
+        app.update(Message::NavigateSettings(Direction::Down));
+        assert_eq!(app.settings_rows()[app.settings_row].0, TimeActivity::Awake);
+        app.update(Message::DeleteSettingsRange);
+        assert!(app.time_config.awake_hours.is_empty());
+        assert_eq!(app.settings_row, 1);
+
+        // Enter saves
+        app.update(Message::SaveSettings);
+        assert!(!app.show_settings);
+        assert!(app.pending_save.is_some());
+        assert_eq!(
+            app.to_config().time_config.work_hours,
+            [range(8, 12), range(13, 17)]
+        );
+
+        // A range stretched over the whole day is kept
+        app.update(Message::OpenSettings);
+        app.update(Message::AddSettingsRange(TimeActivity::Awake));
+        app.update(Message::AdjustSetting(24 * 60));
+        app.update(Message::SwitchSettingsField);
+        app.update(Message::AdjustSetting(-24 * 60));
+        app.update(Message::SaveSettings);
+        assert_eq!(app.time_config.awake_hours, [range(0, 24)]);
+
+        // A save due from before the modal opened waits, and quitting drops the preview
+        let saved = app.time_config.clone();
+        app.pending_save = Instant::now().checked_sub(SAVE_DELAY);
+        app.update(Message::OpenSettings);
+        app.update(Message::AdjustSetting(60));
+        app.update(Message::Tick);
+        assert!(app.pending_save.is_some());
+        app.update(Message::Quit);
+        assert!(app.pending_save.is_none());
+        assert_eq!(app.time_config, saved);
+    }
 }
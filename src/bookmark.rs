@@ -0,0 +1,203 @@
+use chrono::{DateTime, Datelike, Days, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::time::{resolve_local, TimeZoneManager};
+
+/// A named instant to come back to, e.g. "release cutover" or "weekly sync"
+///
+/// Either a fixed instant (`at`), or a wall-clock `time` in a `zone` that repeats every day
+/// or on the listed `days`, following the zone's DST changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<NaiveTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone: Option<String>, // City name or IANA zone
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<Weekday>, // Every day when empty
+}
+
+impl Bookmark {
+    pub fn once(name: String, at: DateTime<Utc>) -> Self {
+        Self {
+            name,
+            at: Some(at),
+            time: None,
+            zone: None,
+            days: Vec::new(),
+        }
+    }
+
+    pub fn recurring(name: String, time: NaiveTime, zone: String, days: Vec<Weekday>) -> Self {
+        Self {
+            name,
+            at: None,
+            time: Some(time),
+            zone: Some(zone),
+            days,
+        }
+    }
+
+    /// Every instant this bookmark names within `start..=end`
+    pub fn occurrences_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Vec<DateTime<Utc>> {
+        if let Some(at) = self.at {
+            return if (start..=end).contains(&at) {
+                vec![at]
+            } else {
+                Vec::new()
+            };
+        }
+        let tz = self.zone.as_deref().and_then(TimeZoneManager::resolve_zone);
+        let (Some(time), Some(tz)) = (self.time, tz) else {
+            return Vec::new();
+        };
+        local_occurrences(time, tz, &self.days, start, end)
+    }
+
+    /// Where jumping to this bookmark from `from` lands: the instant itself, or the next occurrence
+    pub fn next_after(&self, from: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if self.at.is_some() {
+            return self.at;
+        }
+        self.occurrences_between(from + chrono::Duration::seconds(1), from + Days::new(8))
+            .into_iter()
+            .next()
+    }
+
+    /// How it repeats, e.g. "every Tue, Thu 09:00 London"; empty for one-off bookmarks
+    pub fn describe_recurrence(&self) -> String {
+        let (Some(time), Some(zone)) = (self.time, self.zone.as_deref()) else {
+            return String::new();
+        };
+        let days = if self.days.is_empty() {
+            "day".to_string()
+        } else {
+            self.days
+                .iter()
+                .map(|day| day.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        format!("every {days} {} {zone}", time.format("%H:%M"))
+    }
+}
+
+/// Every instant within `start..=end` when the clocks in `tz` read `time` on one of `days`
+/// (every day when empty), following the zone's DST changes as `resolve_local` does
+pub fn local_occurrences(
+    time: NaiveTime,
+    tz: Tz,
+    days: &[Weekday],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Vec<DateTime<Utc>> {
+    let first = start.with_timezone(&tz).date_naive() - Days::new(1);
+    let last = end.with_timezone(&tz).date_naive() + Days::new(1);
+    first
+        .iter_days()
+        .take_while(|date| *date <= last)
+        .filter(|date| days.is_empty() || days.contains(&date.weekday()))
+        .filter_map(|date| resolve_local(tz, date.and_time(time)))
+        .filter(|instant| (start..=end).contains(instant))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instant(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_one_off_bookmark() {
+        let cutover = Bookmark::once(
+            "Release cutover".to_string(),
+            instant("2025-11-03T00:00:00Z"),
+        );
+        assert_eq!(
+            cutover.occurrences_between(
+                instant("2025-11-02T12:00:00Z"),
+                instant("2025-11-03T12:00:00Z")
+            ),
+            vec![instant("2025-11-03T00:00:00Z")]
+        );
+        assert!(cutover
+            .occurrences_between(
+                instant("2025-11-04T00:00:00Z"),
+                instant("2025-11-05T00:00:00Z")
+            )
+            .is_empty());
+        assert_eq!(
+            cutover.next_after(instant("2026-01-01T00:00:00Z")),
+            Some(instant("2025-11-03T00:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_recurring_bookmark_follows_dst() {
+        let sync = Bookmark::recurring(
+            "Weekly sync".to_string(),
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            "London".to_string(),
+            vec![Weekday::Tue],
+        );
+        assert_eq!(sync.describe_recurrence(), "every Tue 09:00 London");
+
+        // 09:00 BST before the clocks go back, 09:00 GMT after
+        assert_eq!(
+            sync.next_after(instant("2025-10-20T12:00:00Z")),
+            Some(instant("2025-10-21T08:00:00Z"))
+        );
+        assert_eq!(
+            sync.next_after(instant("2025-10-21T08:00:00Z")),
+            Some(instant("2025-10-28T09:00:00Z"))
+        );
+
+        let daily = Bookmark::recurring(
+            "Standup".to_string(),
+            NaiveTime::from_hms_opt(9, 30, 0).unwrap(),
+            "America/New_York".to_string(),
+            Vec::new(),
+        );
+        let week = daily.occurrences_between(
+            instant("2025-07-21T00:00:00Z"),
+            instant("2025-07-28T00:00:00Z"),
+        );
+        assert_eq!(week.len(), 7);
+        assert_eq!(week[0], instant("2025-07-21T13:30:00Z"));
+    }
+
+    #[test]
+    fn test_bookmark_config_format() {
+        let bookmarks: std::collections::HashMap<String, Vec<Bookmark>> = toml::from_str(
+            r#"
+[[bookmarks]]
+name = "Release cutover"
+at = "2025-11-03T00:00:00Z"
+
+[[bookmarks]]
+name = "Weekly sync"
+time = "09:00:00"
+zone = "London"
+days = ["Tue"]
+"#,
+        )
+        .unwrap();
+        let bookmarks = &bookmarks["bookmarks"];
+        assert_eq!(bookmarks[0].at, Some(instant("2025-11-03T00:00:00Z")));
+        assert_eq!(bookmarks[1].days, vec![Weekday::Tue]);
+        assert_eq!(bookmarks[1].zone.as_deref(), Some("London"));
+    }
+}
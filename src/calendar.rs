@@ -0,0 +1,1085 @@
+use chrono::{
+    DateTime, Datelike, Days, Duration, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::time::resolve_local;
+
+/// Upper bound on recurrence candidates examined per event, protects against runaway rules
+const MAX_RECURRENCE_ITERATIONS: usize = 100_000;
+
+/// The time zone an ICS date-time is expressed in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EventZone {
+    Utc,
+    Tz(Tz),
+    Floating, // No zone given, interpreted in the system local timezone
+}
+
+impl EventZone {
+    /// Resolves a wall-clock time in this zone to UTC
+    fn to_utc(self, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self {
+            EventZone::Utc => Some(local.and_utc()),
+            EventZone::Tz(tz) => resolve_local(tz, local),
+            EventZone::Floating => resolve_local(Local, local),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A subset of RFC 5545 RRULE covering what calendar exports commonly produce
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<DateTime<Utc>>,
+    pub by_day: Vec<(Option<i32>, Weekday)>, // Optional ordinal, e.g. -1FR = last Friday
+    pub by_month_day: Vec<i32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    pub summary: String,
+    pub start: NaiveDateTime,
+    pub zone: EventZone,
+    pub duration: Duration,
+    pub all_day: bool,
+    pub recurrence: Option<Recurrence>,
+    pub exdates: Vec<DateTime<Utc>>,
+}
+
+/// A single concrete instance of an event, expanded from its recurrence rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventOccurrence {
+    pub summary: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub all_day: bool,
+}
+
+impl EventOccurrence {
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        time >= self.start && time < self.end
+    }
+}
+
+impl CalendarEvent {
+    /// Returns every occurrence overlapping the `[start, end)` range
+    pub fn occurrences_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Vec<EventOccurrence> {
+        let mut occurrences = Vec::new();
+
+        let Some(rule) = &self.recurrence else {
+            if let Some(occurrence) = self.occurrence_at(self.start) {
+                if occurrence.start < end && occurrence.end > start {
+                    occurrences.push(occurrence);
+                }
+            }
+            return occurrences;
+        };
+
+        let mut emitted = 0u32;
+        for local_start in RecurrenceIter::new(self.start, rule).take(MAX_RECURRENCE_ITERATIONS) {
+            let Some(occurrence) = self.occurrence_at(local_start) else {
+                continue;
+            };
+
+            if let Some(until) = rule.until {
+                if occurrence.start > until {
+                    break;
+                }
+            }
+            if let Some(count) = rule.count {
+                if emitted >= count {
+                    break;
+                }
+            }
+            emitted += 1;
+
+            if occurrence.start >= end {
+                break;
+            }
+            if occurrence.end > start && !self.exdates.contains(&occurrence.start) {
+                occurrences.push(occurrence);
+            }
+        }
+
+        occurrences
+    }
+
+    fn occurrence_at(&self, local_start: NaiveDateTime) -> Option<EventOccurrence> {
+        let start = self.zone.to_utc(local_start)?;
+        // Resolve the end in local time too, so all-day events stay midnight to midnight across DST
+        let end = self
+            .zone
+            .to_utc(local_start + self.duration)
+            .unwrap_or(start + self.duration);
+        Some(EventOccurrence {
+            summary: self.summary.clone(),
+            start,
+            end,
+            all_day: self.all_day,
+        })
+    }
+}
+
+/// Generates the local start times of a recurring event in chronological order
+struct RecurrenceIter<'a> {
+    dtstart: NaiveDateTime,
+    rule: &'a Recurrence,
+    period: u32,
+    pending: Vec<NaiveDateTime>,
+}
+
+impl<'a> RecurrenceIter<'a> {
+    fn new(dtstart: NaiveDateTime, rule: &'a Recurrence) -> Self {
+        Self {
+            dtstart,
+            rule,
+            period: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Candidate local start times within the n-th period (day, week, month or year) of the rule
+    fn candidates_for_period(&self, period: u32) -> Vec<NaiveDateTime> {
+        let step = period.saturating_mul(self.rule.interval.max(1));
+        let start_date = self.dtstart.date();
+        let time = self.dtstart.time();
+
+        let dates: Vec<NaiveDate> = match self.rule.frequency {
+            Frequency::Daily => start_date
+                .checked_add_days(Days::new(step as u64))
+                .into_iter()
+                .collect(),
+            Frequency::Weekly => {
+                let week_start = start_date
+                    - Days::new(start_date.weekday().num_days_from_monday() as u64)
+                    + Days::new(step as u64 * 7);
+                if self.rule.by_day.is_empty() {
+                    vec![week_start + Days::new(start_date.weekday().num_days_from_monday() as u64)]
+                } else {
+                    self.rule
+                        .by_day
+                        .iter()
+                        .map(|(_, weekday)| {
+                            week_start + Days::new(weekday.num_days_from_monday() as u64)
+                        })
+                        .collect()
+                }
+            }
+            Frequency::Monthly => {
+                let Some(month_start) = start_date
+                    .with_day(1)
+                    .and_then(|d| d.checked_add_months(Months::new(step)))
+                else {
+                    return Vec::new();
+                };
+                self.month_candidates(month_start, start_date.day())
+            }
+            Frequency::Yearly => {
+                let Some(month_start) = start_date
+                    .with_day(1)
+                    .and_then(|d| d.checked_add_months(Months::new(step.saturating_mul(12))))
+                else {
+                    return Vec::new();
+                };
+                if self.rule.by_day.is_empty() && self.rule.by_month_day.is_empty() {
+                    month_start.with_day(start_date.day()).into_iter().collect()
+                } else {
+                    self.month_candidates(month_start, start_date.day())
+                }
+            }
+        };
+
+        let mut candidates: Vec<NaiveDateTime> = dates
+            .into_iter()
+            .map(|date| date.and_time(time))
+            .filter(|candidate| *candidate >= self.dtstart)
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+        candidates
+    }
+
+    fn month_candidates(&self, month_start: NaiveDate, default_day: u32) -> Vec<NaiveDate> {
+        let days_in_month = days_in_month(month_start);
+
+        if !self.rule.by_month_day.is_empty() {
+            return self
+                .rule
+                .by_month_day
+                .iter()
+                .filter_map(|&day| {
+                    let day = if day < 0 {
+                        days_in_month as i32 + day + 1
+                    } else {
+                        day
+                    };
+                    (day >= 1)
+                        .then(|| month_start.with_day(day as u32))
+                        .flatten()
+                })
+                .collect();
+        }
+
+        if !self.rule.by_day.is_empty() {
+            let mut dates = Vec::new();
+            for (ordinal, weekday) in &self.rule.by_day {
+                let matching: Vec<NaiveDate> = (1..=days_in_month)
+                    .filter_map(|day| month_start.with_day(day))
+                    .filter(|date| date.weekday() == *weekday)
+                    .collect();
+                match ordinal {
+                    Some(n) if *n > 0 => dates.extend(matching.get(*n as usize - 1)),
+                    Some(n) if *n < 0 => {
+                        let from_end = n.unsigned_abs() as usize;
+                        if from_end <= matching.len() {
+                            dates.push(matching[matching.len() - from_end]);
+                        }
+                    }
+                    _ => dates.extend(matching),
+                }
+            }
+            return dates;
+        }
+
+        // Months without the start day (e.g. the 31st) are skipped, as RFC 5545 requires
+        month_start.with_day(default_day).into_iter().collect()
+    }
+}
+
+impl Iterator for RecurrenceIter<'_> {
+    type Item = NaiveDateTime;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Empty periods are possible (e.g. a monthly rule on the 31st), so bound the search
+        let mut empty_periods = 0;
+        while self.pending.is_empty() {
+            if empty_periods > 1000 {
+                return None;
+            }
+            self.pending = self.candidates_for_period(self.period);
+            self.pending.reverse();
+            self.period = self.period.checked_add(1)?;
+            empty_periods += 1;
+        }
+        self.pending.pop()
+    }
+}
+
+fn days_in_month(month_start: NaiveDate) -> u32 {
+    month_start
+        .checked_add_months(Months::new(1))
+        .map(|next| (next - month_start).num_days() as u32)
+        .unwrap_or(31)
+}
+
+/// A parsed content line: `NAME;PARAM=VALUE:value`
+struct Property<'a> {
+    name: String,
+    params: Vec<(String, &'a str)>,
+    value: &'a str,
+}
+
+impl<'a> Property<'a> {
+    fn parse(line: &'a str) -> Option<Self> {
+        // The value starts at the first colon outside of a quoted parameter value
+        let mut in_quotes = false;
+        let colon = line.char_indices().find_map(|(i, c)| match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                None
+            }
+            ':' if !in_quotes => Some(i),
+            _ => None,
+        })?;
+
+        let (head, value) = (&line[..colon], &line[colon + 1..]);
+        let mut parts = head.split(';');
+        let name = parts.next()?.trim().to_ascii_uppercase();
+        let params = parts
+            .filter_map(|param| {
+                let (key, val) = param.split_once('=')?;
+                Some((key.trim().to_ascii_uppercase(), val.trim_matches('"')))
+            })
+            .collect();
+
+        Some(Self {
+            name,
+            params,
+            value,
+        })
+    }
+
+    fn param(&self, key: &str) -> Option<&'a str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// Joins folded lines (continuations start with a space or tab)
+fn unfold_lines(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in content.lines() {
+        let raw = raw.trim_end_matches('\r');
+        if let Some(continuation) = raw.strip_prefix([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(continuation);
+                continue;
+            }
+        }
+        lines.push(raw.to_string());
+    }
+    lines
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') | Some('N') => result.push('\n'),
+                Some(other) => result.push(other),
+                None => {}
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Parses an ICS date or date-time value; returns the local value, its zone and whether it is a date
+fn parse_date_time(
+    value: &str,
+    tzid: Option<&str>,
+) -> Result<(NaiveDateTime, EventZone, bool), String> {
+    let value = value.trim();
+
+    if value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d")
+            .map_err(|_| format!("invalid date '{value}'"))?;
+        return Ok((date.and_time(NaiveTime::MIN), EventZone::Floating, true));
+    }
+
+    let (value, is_utc) = match value.strip_suffix('Z') {
+        Some(stripped) => (stripped, true),
+        None => (value, false),
+    };
+    let local = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .map_err(|_| format!("invalid date-time '{value}'"))?;
+
+    let zone = if is_utc {
+        EventZone::Utc
+    } else {
+        match tzid {
+            // Unknown zone names (e.g. Windows names) fall back to floating time
+            Some(tzid) => Tz::from_str(tzid.trim_start_matches('/'))
+                .map(EventZone::Tz)
+                .unwrap_or(EventZone::Floating),
+            None => EventZone::Floating,
+        }
+    };
+
+    Ok((local, zone, false))
+}
+
+/// Parses an ICS duration such as `PT30M`, `P1D` or `-P1W`
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration '{value}'");
+    let (negative, rest) = match value.trim().strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.trim().trim_start_matches('+')),
+    };
+    let rest = rest.strip_prefix('P').ok_or_else(invalid)?;
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            'W' | 'D' | 'H' | 'M' | 'S' => {
+                let n: i64 = number.parse().map_err(|_| invalid())?;
+                number.clear();
+                total += match c {
+                    'W' => Duration::weeks(n),
+                    'D' => Duration::days(n),
+                    'H' => Duration::hours(n),
+                    'M' => Duration::minutes(n),
+                    _ => Duration::seconds(n),
+                };
+            }
+            _ => return Err(invalid()),
+        }
+    }
+
+    Ok(if negative { -total } else { total })
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn parse_rrule(value: &str, zone: EventZone) -> Result<Recurrence, String> {
+    let mut frequency = None;
+    let mut rule = Recurrence {
+        frequency: Frequency::Daily,
+        interval: 1,
+        count: None,
+        until: None,
+        by_day: Vec::new(),
+        by_month_day: Vec::new(),
+    };
+
+    for part in value.split(';').filter(|part| !part.is_empty()) {
+        let (key, val) = part
+            .split_once('=')
+            .ok_or_else(|| format!("invalid RRULE part '{part}'"))?;
+        match key.to_ascii_uppercase().as_str() {
+            "FREQ" => {
+                frequency = Some(match val.to_ascii_uppercase().as_str() {
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    "MONTHLY" => Frequency::Monthly,
+                    "YEARLY" => Frequency::Yearly,
+                    other => return Err(format!("unsupported RRULE frequency '{other}'")),
+                })
+            }
+            "INTERVAL" => {
+                rule.interval = val
+                    .parse()
+                    .map_err(|_| format!("invalid RRULE interval '{val}'"))?
+            }
+            "COUNT" => {
+                rule.count = Some(
+                    val.parse()
+                        .map_err(|_| format!("invalid RRULE count '{val}'"))?,
+                )
+            }
+            "UNTIL" => {
+                let (local, until_zone, _) = parse_date_time(val, None)?;
+                // A date-only or floating UNTIL is in the same zone as DTSTART
+                let until_zone = if until_zone == EventZone::Utc {
+                    EventZone::Utc
+                } else {
+                    zone
+                };
+                rule.until = until_zone.to_utc(local);
+            }
+            "BYDAY" => {
+                for day in val.split(',') {
+                    let day = day.trim().to_ascii_uppercase();
+                    let invalid = || format!("invalid RRULE weekday '{day}'");
+                    // The last two characters name the day; non-ASCII text may not split there
+                    let (ordinal, weekday) = day
+                        .split_at_checked(day.len().saturating_sub(2))
+                        .ok_or_else(invalid)?;
+                    let weekday = parse_weekday(weekday).ok_or_else(invalid)?;
+                    let ordinal = if ordinal.is_empty() {
+                        None
+                    } else {
+                        Some(ordinal.parse().map_err(|_| invalid())?)
+                    };
+                    rule.by_day.push((ordinal, weekday));
+                }
+            }
+            "BYMONTHDAY" => {
+                for day in val.split(',') {
+                    rule.by_month_day.push(
+                        day.trim()
+                            .parse()
+                            .map_err(|_| format!("invalid RRULE month day '{day}'"))?,
+                    );
+                }
+            }
+            // The week start only matters for rules this expansion doesn't support
+            "WKST" => {}
+            // Ignoring a part such as BYSETPOS or BYMONTH would show the event at wrong times
+            other => return Err(format!("unsupported RRULE part '{other}'")),
+        }
+    }
+
+    rule.frequency = frequency.ok_or("RRULE without FREQ")?;
+    Ok(rule)
+}
+
+/// Parses every VEVENT in an iCalendar document
+///
+/// An event that can't be read is skipped and reported with its line rather than hiding the
+/// rest of the calendar; only a file that isn't iCalendar at all is an error.
+pub fn parse_ics(content: &str) -> Result<(Vec<CalendarEvent>, Vec<String>), String> {
+    let lines = unfold_lines(content);
+    if !lines
+        .iter()
+        .any(|line| line.trim().eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+    {
+        return Err("not an iCalendar file (missing BEGIN:VCALENDAR)".to_string());
+    }
+
+    let mut events = Vec::new(); // With their UIDs
+    let mut overrides = Vec::new(); // UID and original start of each overridden occurrence
+    let mut skipped = Vec::new();
+    let mut current: Option<Vec<Property>> = None;
+    let mut nested = 0; // Depth of sub-components such as VALARM inside the current event
+
+    for (index, line) in lines.iter().enumerate() {
+        let Some(property) = Property::parse(line) else {
+            continue;
+        };
+        match (property.name.as_str(), property.value.trim()) {
+            ("BEGIN", "VEVENT") => {
+                current = Some(Vec::new());
+                nested = 0;
+            }
+            ("BEGIN", _) if current.is_some() => nested += 1,
+            ("END", "VEVENT") if nested == 0 => {
+                if let Some(properties) = current.take() {
+                    let uid = properties
+                        .iter()
+                        .find(|p| p.name == "UID")
+                        .map(|p| p.value.trim().to_string());
+                    overrides.extend(overridden_occurrence(&properties));
+                    match build_event(&properties) {
+                        Ok(event) => events.extend(event.map(|event| (uid, event))),
+                        Err(e) => skipped.push(format!("event ending on line {}: {e}", index + 1)),
+                    }
+                }
+            }
+            ("END", _) if nested > 0 => nested -= 1,
+            // A sub-component's properties (an alarm's DURATION, say) aren't the event's
+            _ if nested > 0 => {}
+            _ => {
+                if let Some(properties) = current.as_mut() {
+                    properties.push(property);
+                }
+            }
+        }
+    }
+
+    // An occurrence moved or cancelled by its own VEVENT no longer happens at its old time
+    for (uid, event) in &mut events {
+        if let (Some(uid), Some(_)) = (uid.as_ref(), &event.recurrence) {
+            event.exdates.extend(
+                overrides
+                    .iter()
+                    .filter(|(overridden, _)| overridden == uid)
+                    .map(|(_, start)| *start),
+            );
+        }
+    }
+
+    Ok((
+        events.into_iter().map(|(_, event)| event).collect(),
+        skipped,
+    ))
+}
+
+/// The UID and original start of the occurrence a VEVENT with a RECURRENCE-ID replaces
+fn overridden_occurrence(properties: &[Property]) -> Option<(String, DateTime<Utc>)> {
+    let find = |name: &str| properties.iter().find(|p| p.name == name);
+    let uid = find("UID")?.value.trim().to_string();
+    let id = find("RECURRENCE-ID")?;
+    let (local, zone, _) = parse_date_time(id.value, id.param("TZID")).ok()?;
+    // A floating RECURRENCE-ID is in the zone of the event's start
+    let zone = match (zone, find("DTSTART")) {
+        (EventZone::Floating, Some(dtstart)) => {
+            parse_date_time(dtstart.value, dtstart.param("TZID"))
+                .map_or(zone, |(_, start_zone, _)| start_zone)
+        }
+        _ => zone,
+    };
+    Some((uid, zone.to_utc(local)?))
+}
+
+fn build_event(properties: &[Property]) -> Result<Option<CalendarEvent>, String> {
+    let find = |name: &str| properties.iter().find(|p| p.name == name);
+
+    // Cancelled events are kept by some exporters; there is nothing to show for them
+    if find("STATUS").is_some_and(|p| p.value.trim().eq_ignore_ascii_case("CANCELLED")) {
+        return Ok(None);
+    }
+
+    let dtstart = find("DTSTART").ok_or("missing DTSTART")?;
+    let (start, zone, all_day) = parse_date_time(dtstart.value, dtstart.param("TZID"))?;
+
+    let duration = if let Some(dtend) = find("DTEND") {
+        let (end, end_zone, _) = parse_date_time(dtend.value, dtend.param("TZID"))?;
+        if end_zone == zone {
+            end - start
+        } else {
+            match (end_zone.to_utc(end), zone.to_utc(start)) {
+                (Some(end), Some(start)) => end - start,
+                _ => Duration::zero(),
+            }
+        }
+    } else if let Some(duration) = find("DURATION") {
+        parse_duration(duration.value)?
+    } else if all_day {
+        Duration::days(1)
+    } else {
+        Duration::zero()
+    };
+
+    let recurrence = find("RRULE")
+        .map(|rrule| parse_rrule(rrule.value, zone))
+        .transpose()?;
+
+    let exdates = properties
+        .iter()
+        .filter(|p| p.name == "EXDATE")
+        .flat_map(|p| {
+            p.value
+                .split(',')
+                .filter_map(|value| parse_date_time(value, p.param("TZID")).ok())
+                .filter_map(|(local, ex_zone, is_date)| {
+                    // Date-only exclusions use the start time of the event on that day
+                    let local = if is_date {
+                        local.date().and_time(start.time())
+                    } else {
+                        local
+                    };
+                    let ex_zone = if ex_zone == EventZone::Floating {
+                        zone
+                    } else {
+                        ex_zone
+                    };
+                    ex_zone.to_utc(local)
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let summary = find("SUMMARY")
+        .map(|p| unescape_text(p.value.trim()))
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "(untitled)".to_string());
+
+    Ok(Some(CalendarEvent {
+        summary,
+        start,
+        zone,
+        duration: duration.max(Duration::zero()),
+        all_day,
+        recurrence,
+        exdates,
+    }))
+}
+
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Folds a content line to at most 75 octets per line, as RFC 5545 requires
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut line_octets = 0;
+    for c in line.chars() {
+        if line_octets + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            line_octets = 1;
+        }
+        folded.push(c);
+        line_octets += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+/// A proposed meeting slot that can be written out as an iCalendar invite
+#[derive(Debug, Clone)]
+pub struct Invite {
+    pub title: String,
+    pub start: DateTime<Utc>,
+    pub duration: Duration,
+    pub description: Option<String>,
+}
+
+impl Invite {
+    /// Renders a single-event iCalendar document with UTC start and end times
+    pub fn to_ics(&self, stamp: DateTime<Utc>) -> String {
+        const UTC_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            "PRODID:-//alltz//alltz//EN".to_string(),
+            "CALSCALE:GREGORIAN".to_string(),
+            "METHOD:PUBLISH".to_string(),
+            "BEGIN:VEVENT".to_string(),
+            format!(
+                "UID:{}-{}@alltz",
+                self.start.format(UTC_FORMAT),
+                stamp.timestamp_millis()
+            ),
+            format!("DTSTAMP:{}", stamp.format(UTC_FORMAT)),
+            format!("DTSTART:{}", self.start.format(UTC_FORMAT)),
+            format!("DTEND:{}", (self.start + self.duration).format(UTC_FORMAT)),
+            format!("SUMMARY:{}", escape_text(&self.title)),
+        ];
+        if let Some(description) = &self.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        lines.push("END:VEVENT".to_string());
+        lines.push("END:VCALENDAR".to_string());
+
+        lines.iter().map(|line| fold_line(line)).collect()
+    }
+
+    /// A file name for the invite derived from its start time, e.g. `alltz-20250722-1700.ics`
+    pub fn default_file_name(&self) -> String {
+        format!("alltz-{}.ics", self.start.format("%Y%m%d-%H%M"))
+    }
+
+    pub fn write_to(&self, path: &Path) -> std::io::Result<()> {
+        fs::write(path, self.to_ics(Utc::now()))
+    }
+
+    /// Writes the invite into `dir` under its default name, numbering it (`-2`, `-3`, ...)
+    /// rather than overwriting a file that's already there; returns the path written
+    pub fn write_new(&self, dir: &Path) -> std::io::Result<PathBuf> {
+        let name = self.default_file_name();
+        let stem = name.trim_end_matches(".ics");
+        for attempt in 1.. {
+            let path = match attempt {
+                1 => dir.join(&name),
+                n => dir.join(format!("{stem}-{n}.ics")),
+            };
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    file.write_all(self.to_ics(Utc::now()).as_bytes())?;
+                    return Ok(path);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        unreachable!("ran out of invite file names")
+    }
+}
+
+/// Expands a leading `~` to the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/") {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest);
+        }
+    }
+    PathBuf::from(path)
+}
+
+/// Events loaded from one or more local `.ics` files
+#[derive(Debug, Clone, Default)]
+pub struct Calendar {
+    pub events: Vec<CalendarEvent>,
+}
+
+impl Calendar {
+    /// Loads every file, returning the events that parsed plus an error message per failed file
+    pub fn load_files(paths: &[PathBuf]) -> (Self, Vec<String>) {
+        let mut calendar = Self::default();
+        let mut errors = Vec::new();
+
+        for path in paths {
+            match Self::load_file(path) {
+                Ok((events, skipped)) => {
+                    calendar.events.extend(events);
+                    errors.extend(skipped.iter().map(|e| format!("{}: {e}", path.display())));
+                }
+                Err(e) => errors.push(format!("{}: {e}", path.display())),
+            }
+        }
+
+        (calendar, errors)
+    }
+
+    fn load_file(path: &Path) -> Result<(Vec<CalendarEvent>, Vec<String>), String> {
+        let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        parse_ics(&content)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// All occurrences overlapping `[start, end)`, sorted by start time
+    pub fn occurrences_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Vec<EventOccurrence> {
+        let mut occurrences: Vec<EventOccurrence> = self
+            .events
+            .iter()
+            .flat_map(|event| event.occurrences_between(start, end))
+            .collect();
+        occurrences.sort_by_key(|occurrence| occurrence.start);
+        occurrences
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn wrap(body: &str) -> String {
+        format!("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n{body}END:VCALENDAR\r\n")
+    }
+
+    #[test]
+    fn test_parse_single_utc_event() {
+        let ics = wrap(
+            "BEGIN:VEVENT\r\nSUMMARY:Planning\r\nDTSTART:20250722T170000Z\r\nDTEND:20250722T180000Z\r\nEND:VEVENT\r\n",
+        );
+        let events = parse_ics(&ics).unwrap().0;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary, "Planning");
+        assert_eq!(events[0].zone, EventZone::Utc);
+        assert_eq!(events[0].duration, Duration::hours(1));
+
+        let occurrences =
+            events[0].occurrences_between(utc("2025-07-22T00:00:00Z"), utc("2025-07-23T00:00:00Z"));
+        assert_eq!(occurrences.len(), 1);
+        assert_eq!(occurrences[0].start, utc("2025-07-22T17:00:00Z"));
+        assert_eq!(occurrences[0].end, utc("2025-07-22T18:00:00Z"));
+    }
+
+    #[test]
+    fn test_tzid_and_folded_summary() {
+        let ics = wrap(
+            "BEGIN:VEVENT\r\nSUMMARY:Quarterly\r\n  review\\, all hands\r\nDTSTART;TZID=Europe/London:20250115T090000\r\nDURATION:PT30M\r\nEND:VEVENT\r\n",
+        );
+        let events = parse_ics(&ics).unwrap().0;
+        assert_eq!(events[0].summary, "Quarterly review, all hands");
+
+        // 09:00 GMT in January is 09:00 UTC
+        let occurrences =
+            events[0].occurrences_between(utc("2025-01-15T00:00:00Z"), utc("2025-01-16T00:00:00Z"));
+        assert_eq!(occurrences[0].start, utc("2025-01-15T09:00:00Z"));
+        assert_eq!(occurrences[0].end, utc("2025-01-15T09:30:00Z"));
+    }
+
+    #[test]
+    fn test_weekly_rrule_follows_local_time_across_dst() {
+        let ics = wrap(
+            "BEGIN:VEVENT\r\nSUMMARY:Standup\r\nDTSTART;TZID=America/New_York:20250303T093000\r\nDURATION:PT15M\r\nRRULE:FREQ=WEEKLY;BYDAY=MO,WE\r\nEND:VEVENT\r\n",
+        );
+        let events = parse_ics(&ics).unwrap().0;
+
+        let occurrences =
+            events[0].occurrences_between(utc("2025-03-03T00:00:00Z"), utc("2025-03-13T00:00:00Z"));
+        let starts: Vec<_> = occurrences.iter().map(|o| o.start).collect();
+        assert_eq!(
+            starts,
+            vec![
+                utc("2025-03-03T14:30:00Z"), // EST
+                utc("2025-03-05T14:30:00Z"),
+                utc("2025-03-10T13:30:00Z"), // EDT after the 9 March change
+                utc("2025-03-12T13:30:00Z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rrule_count_until_and_exdate() {
+        let ics = wrap(
+            "BEGIN:VEVENT\r\nSUMMARY:Daily\r\nDTSTART:20250101T120000Z\r\nRRULE:FREQ=DAILY;COUNT=3\r\nEXDATE:20250102T120000Z\r\nEND:VEVENT\r\nBEGIN:VEVENT\r\nSUMMARY:Until\r\nDTSTART:20250101T080000Z\r\nRRULE:FREQ=DAILY;INTERVAL=2;UNTIL=20250105T080000Z\r\nEND:VEVENT\r\n",
+        );
+        let events = parse_ics(&ics).unwrap().0;
+        let range = (utc("2024-12-01T00:00:00Z"), utc("2025-02-01T00:00:00Z"));
+
+        let daily = events[0].occurrences_between(range.0, range.1);
+        let daily_starts: Vec<_> = daily.iter().map(|o| o.start).collect();
+        assert_eq!(
+            daily_starts,
+            vec![utc("2025-01-01T12:00:00Z"), utc("2025-01-03T12:00:00Z")]
+        );
+
+        let until = events[1].occurrences_between(range.0, range.1);
+        assert_eq!(until.len(), 3); // 1st, 3rd and 5th
+    }
+
+    #[test]
+    fn test_monthly_last_weekday() {
+        let ics = wrap(
+            "BEGIN:VEVENT\r\nSUMMARY:Retro\r\nDTSTART:20250131T150000Z\r\nRRULE:FREQ=MONTHLY;BYDAY=-1FR\r\nEND:VEVENT\r\n",
+        );
+        let events = parse_ics(&ics).unwrap().0;
+        let occurrences =
+            events[0].occurrences_between(utc("2025-02-01T00:00:00Z"), utc("2025-04-01T00:00:00Z"));
+        let starts: Vec<_> = occurrences.iter().map(|o| o.start).collect();
+        assert_eq!(
+            starts,
+            vec![utc("2025-02-28T15:00:00Z"), utc("2025-03-28T15:00:00Z")]
+        );
+    }
+
+    #[test]
+    fn test_overrides_and_alarms() {
+        let ics = wrap(concat!(
+            "BEGIN:VEVENT\r\nUID:sync@example.com\r\nSUMMARY:Sync\r\n",
+            "DTSTART;TZID=Europe/London:20250106T100000\r\nDTEND;TZID=Europe/London:20250106T103000\r\n",
+            "RRULE:FREQ=WEEKLY\r\n",
+            "BEGIN:VALARM\r\nACTION:DISPLAY\r\nSUMMARY:Alarm\r\nDURATION:PT5M\r\nTRIGGER:-PT10M\r\nEND:VALARM\r\n",
+            "END:VEVENT\r\n",
+            // The second week's sync moves to the afternoon
+            "BEGIN:VEVENT\r\nUID:sync@example.com\r\nSUMMARY:Sync\r\n",
+            "RECURRENCE-ID;TZID=Europe/London:20250113T100000\r\n",
+            "DTSTART;TZID=Europe/London:20250113T150000\r\nDURATION:PT30M\r\nEND:VEVENT\r\n",
+        ));
+        let (events, skipped) = parse_ics(&ics).unwrap();
+        assert!(skipped.is_empty());
+
+        // The alarm's properties don't leak into the event
+        assert_eq!(events[0].summary, "Sync");
+        assert_eq!(events[0].duration, Duration::minutes(30));
+
+        let calendar = Calendar { events };
+        let starts: Vec<_> = calendar
+            .occurrences_between(utc("2025-01-06T00:00:00Z"), utc("2025-01-21T00:00:00Z"))
+            .iter()
+            .map(|occurrence| occurrence.start)
+            .collect();
+        assert_eq!(
+            starts,
+            [
+                utc("2025-01-06T10:00:00Z"),
+                utc("2025-01-13T15:00:00Z"),
+                utc("2025-01-20T10:00:00Z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_all_day_event() {
+        let ics = wrap(
+            "BEGIN:VEVENT\r\nSUMMARY:Holiday\r\nDTSTART;VALUE=DATE:20250704\r\nEND:VEVENT\r\n",
+        );
+        let events = parse_ics(&ics).unwrap().0;
+        assert!(events[0].all_day);
+        assert_eq!(events[0].duration, Duration::days(1));
+    }
+
+    #[test]
+    fn test_invalid_files_report_errors() {
+        assert!(parse_ics("hello").is_err());
+
+        // A bad event is reported and skipped; the rest of the calendar still loads
+        let (events, skipped) = parse_ics(&wrap(
+            "BEGIN:VEVENT\r\nSUMMARY:No start\r\nEND:VEVENT\r\nBEGIN:VEVENT\r\nSUMMARY:Fine\r\nDTSTART:20250101T120000Z\r\nEND:VEVENT\r\n",
+        ))
+        .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary, "Fine");
+        assert_eq!(skipped, ["event ending on line 5: missing DTSTART"]);
+
+        // Rules with parts the expansion would ignore are rejected rather than shown wrongly
+        for rrule in [
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1",
+            "FREQ=HOURLY",
+        ] {
+            let (events, skipped) = parse_ics(&wrap(&format!(
+                "BEGIN:VEVENT\r\nSUMMARY:Odd\r\nDTSTART:20250101T120000Z\r\nRRULE:{rrule}\r\nEND:VEVENT\r\n"
+            )))
+            .unwrap();
+            assert!(events.is_empty(), "{rrule}");
+            assert!(skipped[0].contains("unsupported RRULE"), "{}", skipped[0]);
+        }
+
+        for byday in ["€", "1€", "XX", "aMO"] {
+            let rrule = format!("FREQ=WEEKLY;BYDAY={byday}");
+            assert!(parse_rrule(&rrule, EventZone::Utc).is_err(), "{rrule}");
+        }
+
+        let (calendar, errors) =
+            Calendar::load_files(&[PathBuf::from("/nonexistent/alltz-test.ics")]);
+        assert!(calendar.is_empty());
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_invite_round_trip() {
+        let invite = Invite {
+            title: "Sync; planning, Q3".to_string(),
+            start: utc("2025-07-22T17:00:00Z"),
+            duration: Duration::minutes(45),
+            description: Some(
+                "Tue 17:00 LON / 09:00 LAX / Wed 01:00 TYO and a long tail to force line folding"
+                    .to_string(),
+            ),
+        };
+        let ics = invite.to_ics(utc("2025-07-20T08:00:00Z"));
+
+        assert!(ics.contains("DTSTART:20250722T170000Z\r\n"));
+        assert!(ics.contains("DTEND:20250722T174500Z\r\n"));
+        assert!(ics.contains("SUMMARY:Sync\\; planning\\, Q3\r\n"));
+        assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+        assert_eq!(invite.default_file_name(), "alltz-20250722-1700.ics");
+
+        // The written invite parses back to the same slot
+        let events = parse_ics(&ics).unwrap().0;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].summary, "Sync; planning, Q3");
+        assert_eq!(events[0].duration, Duration::minutes(45));
+        let occurrences =
+            events[0].occurrences_between(utc("2025-07-22T00:00:00Z"), utc("2025-07-23T00:00:00Z"));
+        assert_eq!(occurrences[0].start, invite.start);
+
+        // Exporting the same slot twice keeps both files
+        let dir = std::env::temp_dir().join(format!("alltz-invite-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let first = invite.write_new(&dir).unwrap();
+        let second = invite.write_new(&dir).unwrap();
+        assert_eq!(first, dir.join("alltz-20250722-1700.ics"));
+        assert_eq!(second, dir.join("alltz-20250722-1700-2.ics"));
+        assert_eq!(
+            parse_ics(&fs::read_to_string(&first).unwrap())
+                .unwrap()
+                .0
+                .len(),
+            1
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
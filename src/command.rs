@@ -0,0 +1,300 @@
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc, Weekday};
+use chrono_tz::Tz;
+
+use crate::app::Message;
+use crate::config::{ColorTheme, TimeRange};
+use crate::time::parse_datetime_in_zone;
+
+/// One `:` palette command: its name, the arguments it takes and what it does
+#[derive(Debug, PartialEq)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub args: &'static str,
+    pub description: &'static str,
+}
+
+const fn spec(name: &'static str, args: &'static str, description: &'static str) -> CommandSpec {
+    CommandSpec {
+        name,
+        args,
+        description,
+    }
+}
+
+/// Every palette command, in the order the palette lists them
+pub const COMMANDS: &[CommandSpec] = &[
+    spec("add", "<city> [as \"label\"]", "Add a zone"),
+    spec("remove", "", "Remove the selected zone"),
+    spec("rename", "<label>", "Label the selected zone"),
+    spec("clear-name", "", "Clear the selected zone's label"),
+    spec("undo", "", "Undo the last zone change"),
+    spec("redo", "", "Redo a zone change"),
+    spec(
+        "goto",
+        "<time>",
+        "Move the timeline, e.g. fri 15:00, tomorrow 9:00, 2025-07-22 17:00, now",
+    ),
+    spec("theme", "<name>", "Switch color theme by name"),
+    spec(
+        "work",
+        "<ranges>",
+        "Set work hours, e.g. 9-17, 09:30-17:30 or 9-12,13-18",
+    ),
+    spec("awake", "<ranges>", "Set awake hours, e.g. 7-23"),
+    spec(
+        "settings",
+        "",
+        "Edit work and awake hours, including split shifts",
+    ),
+    spec("format", "", "Toggle 12/24 hour format"),
+    spec("names", "", "Toggle short/full zone names"),
+    spec("date", "", "Toggle date display"),
+    spec("sun", "", "Toggle sunrise/sunset times"),
+    spec("shading", "", "Toggle daylight shading"),
+    spec("ruler", "", "Cycle the hour ruler"),
+    spec("offsets", "", "Cycle relative offsets"),
+    spec("events", "", "Cycle the calendar events overlay"),
+    spec("info", "", "Show/hide the zone info panel"),
+    spec("week", "", "Show/hide the week grid"),
+    spec("table", "", "Conversion table from the selected zone"),
+    spec("filter", "", "Filter the zone list"),
+    spec("zoom-in", "", "Zoom the timelines in"),
+    spec("zoom-out", "", "Zoom the timelines out"),
+    spec("zoom-fit", "", "Fit the timelines to the terminal width"),
+    spec("bookmark", "", "Bookmark the scrubbed time"),
+    spec("bookmarks", "", "List bookmarks"),
+    spec("countdowns", "", "List countdowns"),
+    spec("export", "", "Export the scrubbed time as an .ics invite"),
+    spec("copy", "", "Copy the scrubbed time to the clipboard"),
+    spec("help", "", "Show keyboard shortcuts"),
+    spec("quit", "", "Quit alltz"),
+];
+
+/// Commands matching what has been typed so far: names starting with the first word while
+/// it is being typed, then the one command whose arguments are being typed
+pub fn completions(input: &str) -> Vec<&'static CommandSpec> {
+    let input = input.trim_start().to_lowercase();
+    match input.split_once(' ') {
+        Some((name, _)) => COMMANDS.iter().filter(|spec| spec.name == name).collect(),
+        None => COMMANDS
+            .iter()
+            .filter(|spec| spec.name.starts_with(&input))
+            .collect(),
+    }
+}
+
+/// Turns a palette command line into the message it runs
+pub fn parse(input: &str) -> Result<Message, String> {
+    let input = input.trim();
+    let (name, args) = match input.split_once(' ') {
+        Some((name, args)) => (name, args.trim()),
+        None => (input, ""),
+    };
+    let name = name.to_lowercase();
+    let Some(spec) = COMMANDS.iter().find(|spec| spec.name == name) else {
+        return Err(format!("Unknown command '{name}'"));
+    };
+    if spec.args.starts_with('<') && args.is_empty() {
+        return Err(format!("Usage: {} {}", spec.name, spec.args));
+    }
+
+    let message = match spec.name {
+        "add" => {
+            let (name, label) = match args.rsplit_once(" as ") {
+                Some((name, label)) => (name, Some(unquote(label))),
+                None => (args, None),
+            };
+            Message::AddZoneNamed {
+                name: unquote(name),
+                label: label.filter(|label| !label.is_empty()),
+            }
+        }
+        "remove" => Message::RemoveCurrentZone,
+        "rename" => Message::RenameZoneTo(unquote(args)),
+        "clear-name" => Message::ClearCustomName,
+        "undo" => Message::Undo,
+        "redo" => Message::Redo,
+        "goto" => Message::GotoTime(args.to_string()),
+        "theme" => Message::SetColorTheme(
+            ColorTheme::from_name(args).ok_or_else(|| format!("Unknown theme '{args}'"))?,
+        ),
+        "work" => Message::SetWorkHours(parse_ranges(args)?),
+        "awake" => Message::SetAwakeHours(parse_ranges(args)?),
+        "settings" => Message::OpenSettings,
+        "format" => Message::ToggleTimeFormat,
+        "names" => Message::ToggleTimezoneDisplayMode,
+        "date" => Message::ToggleDate,
+        "sun" => Message::ToggleSunTimes,
+        "shading" => Message::CycleShadingMode,
+        "ruler" => Message::CycleRulerMode,
+        "offsets" => Message::CycleRelativeOffsets,
+        "events" => Message::CycleEventOverlay,
+        "info" => Message::ToggleInfoPanel,
+        "week" => Message::ToggleWeekGrid,
+        "table" => Message::ToggleTable,
+        "filter" => Message::StartZoneFilter,
+        "zoom-in" => Message::ZoomIn,
+        "zoom-out" => Message::ZoomOut,
+        "zoom-fit" => Message::ResetZoom,
+        "bookmark" => Message::StartAddBookmark,
+        "bookmarks" => Message::ToggleBookmarks,
+        "countdowns" => Message::ToggleCountdowns,
+        "export" => Message::StartExport,
+        "copy" => Message::StartYank,
+        "help" => Message::ToggleHelp,
+        "quit" => Message::Quit,
+        _ => return Err(format!("Unknown command '{name}'")),
+    };
+    Ok(message)
+}
+
+fn unquote(text: &str) -> String {
+    text.trim().trim_matches('"').trim().to_string()
+}
+
+/// Parses comma-separated ranges of hours such as "9-17" or "09:00-12:00, 13:00-18:00"
+fn parse_ranges(ranges: &str) -> Result<Vec<TimeRange>, String> {
+    ranges
+        .split(',')
+        .map(|range| {
+            range
+                .trim()
+                .parse()
+                .map_err(|_| format!("Hours should look like 9-17, not '{}'", range.trim()))
+        })
+        .collect()
+}
+
+/// Where `:goto` lands, read in `tz`: "now", a date/time `parse_datetime_in_zone` accepts, or a
+/// day ("today", "tomorrow", "yesterday" or a weekday, meaning the next one) with an optional
+/// time, keeping the timeline's time of day when none is given
+pub fn parse_goto(
+    input: &str,
+    tz: Tz,
+    now: DateTime<Utc>,
+    position: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("now") {
+        return Some(now);
+    }
+    let today = now.with_timezone(&tz).date_naive();
+    let (day, time) = match input.split_once(' ') {
+        Some((day, time)) => (day, time.trim()),
+        None => (input, ""),
+    };
+    let Some(date) = parse_day(&day.to_lowercase(), today) else {
+        return parse_datetime_in_zone(input, Some(tz), today);
+    };
+    let time = if time.is_empty() {
+        position.with_timezone(&tz).format("%H:%M").to_string()
+    } else {
+        time.to_string()
+    };
+    parse_datetime_in_zone(&time, Some(tz), date)
+}
+
+fn parse_day(day: &str, today: NaiveDate) -> Option<NaiveDate> {
+    match day {
+        "today" => Some(today),
+        "tomorrow" => today.succ_opt(),
+        "yesterday" => today.pred_opt(),
+        _ => {
+            let weekday = day.parse::<Weekday>().ok()?;
+            let ahead =
+                (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+            today.checked_add_days(Days::new(ahead as u64))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instant(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert!(matches!(
+            parse("add Lisbon as \"Ana\""),
+            Ok(Message::AddZoneNamed { name, label: Some(label) }) if name == "Lisbon" && label == "Ana"
+        ));
+        assert!(matches!(
+            parse("add New York"),
+            Ok(Message::AddZoneNamed { name, label: None }) if name == "New York"
+        ));
+        assert!(matches!(
+            parse("theme Ocean"),
+            Ok(Message::SetColorTheme(ColorTheme::Ocean))
+        ));
+        assert!(matches!(
+            parse("work 9-17"),
+            Ok(Message::SetWorkHours(ranges)) if ranges == ["9-17".parse().unwrap()]
+        ));
+        assert!(matches!(
+            parse("work 09:00-12:00, 13:00-17:45"),
+            Ok(Message::SetWorkHours(ranges))
+                if ranges == ["9-12".parse().unwrap(), "13:00-17:45".parse().unwrap()]
+        ));
+        assert!(matches!(parse("  week "), Ok(Message::ToggleWeekGrid)));
+
+        assert_eq!(parse("theme plaid").unwrap_err(), "Unknown theme 'plaid'");
+        assert_eq!(parse("goto").unwrap_err(), "Usage: goto <time>");
+        assert!(parse("work 9-9").is_err());
+        assert!(matches!(
+            parse("awake 0-24"),
+            Ok(Message::SetAwakeHours(ranges)) if ranges[0].minutes() == 24 * 60
+        ));
+        assert!(parse("work 9-17,").is_err());
+        assert!(parse("fly").is_err());
+    }
+
+    #[test]
+    fn test_completions() {
+        let names = |input| {
+            completions(input)
+                .iter()
+                .map(|spec| spec.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names("re"), ["remove", "rename", "redo"]);
+        assert_eq!(names("theme oc"), ["theme"]);
+        assert_eq!(names("").len(), COMMANDS.len());
+        assert!(names("xyz").is_empty());
+    }
+
+    #[test]
+    fn test_parse_goto() {
+        let tokyo = chrono_tz::Asia::Tokyo;
+        let now = instant("2025-07-22T03:00:00Z"); // Tuesday 12:00 in Tokyo
+        let position = instant("2025-07-22T06:30:00Z"); // 15:30 in Tokyo
+
+        assert_eq!(parse_goto("now", tokyo, now, position), Some(now));
+        assert_eq!(
+            parse_goto("fri 15:00", tokyo, now, position),
+            Some(instant("2025-07-25T06:00:00Z"))
+        );
+        assert_eq!(
+            parse_goto("Tuesday 09:00", tokyo, now, position),
+            Some(instant("2025-07-22T00:00:00Z"))
+        );
+        assert_eq!(
+            parse_goto("tomorrow", tokyo, now, position),
+            Some(instant("2025-07-23T06:30:00Z"))
+        );
+        assert_eq!(
+            parse_goto("2025-08-01 08:00", tokyo, now, position),
+            Some(instant("2025-07-31T23:00:00Z"))
+        );
+        assert_eq!(
+            parse_goto("18:00", tokyo, now, position),
+            Some(instant("2025-07-22T09:00:00Z"))
+        );
+        assert_eq!(parse_goto("someday", tokyo, now, position), None);
+    }
+}
@@ -1,29 +1,434 @@
 use crate::app::{TimeFormat, TimezoneDisplayMode};
+use crate::bookmark::Bookmark;
+use crate::config_file::ConfigFile;
+use crate::countdown::Countdown;
+use crate::reminder::Reminder;
+use crate::solar::SolarPhase;
+use crate::time::TimeZoneManager;
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
+use std::env;
+use std::ffi::OsString;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-fn default_true() -> bool {
+/// Schema version written to new config files; bump it and add a migration when the format changes
+pub const CONFIG_VERSION: u32 = 3;
+
+/// Config path given on the command line, which beats the environment
+static CONFIG_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+pub(crate) fn default_true() -> bool {
     true
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A time of day to the minute, from 00:00 up to 24:00 (the end of the day)
+///
+/// Version 1 config files wrote it as a whole hour such as `8`, or as `"08:30"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "ClockTimeValue", into = "ClockTimeValue")]
+pub struct ClockTime(u32); // Minutes since midnight
+
+pub const MINUTES_PER_DAY: u32 = 24 * 60;
+
+impl ClockTime {
+    pub const fn new(hour: u32, minute: u32) -> Self {
+        Self(hour * 60 + minute)
+    }
+
+    pub fn minutes(self) -> u32 {
+        self.0
+    }
+
+    pub fn hour(self) -> u32 {
+        self.0 / 60
+    }
+
+    pub fn minute(self) -> u32 {
+        self.0 % 60
+    }
+
+    /// Moved by `minutes`, kept within 00:00-24:00
+    pub fn shifted(self, minutes: i32) -> Self {
+        Self((self.0 as i32 + minutes).clamp(0, MINUTES_PER_DAY as i32) as u32)
+    }
+}
+
+impl From<chrono::NaiveTime> for ClockTime {
+    fn from(time: chrono::NaiveTime) -> Self {
+        use chrono::Timelike;
+        Self::new(time.hour(), time.minute())
+    }
+}
+
+impl fmt::Display for ClockTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour(), self.minute())
+    }
+}
+
+impl std::str::FromStr for ClockTime {
+    type Err = String;
+
+    /// Reads "9", "09:30" or "24:00"
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("time '{text}' should look like 9 or 09:30");
+        let (hour, minute) = text.trim().split_once(':').unwrap_or((text.trim(), "0"));
+        let hour: u32 = hour.parse().map_err(|_| invalid())?;
+        let minute: u32 = minute.parse().map_err(|_| invalid())?;
+        let time = Self::new(hour, minute);
+        if minute >= 60 || time.0 > MINUTES_PER_DAY {
+            return Err(invalid());
+        }
+        Ok(time)
+    }
+}
+
+/// How a `ClockTime` is written in config files: whole hours stay plain numbers
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum ClockTimeValue {
+    Hour(u32),
+    Time(String),
+}
+
+impl TryFrom<ClockTimeValue> for ClockTime {
+    type Error = String;
+
+    fn try_from(value: ClockTimeValue) -> Result<Self, Self::Error> {
+        match value {
+            ClockTimeValue::Hour(hour) if hour <= 24 => Ok(Self::new(hour, 0)),
+            ClockTimeValue::Hour(hour) => Err(format!("hour {hour} is past 24")),
+            ClockTimeValue::Time(text) => text.parse(),
+        }
+    }
+}
+
+impl From<ClockTime> for ClockTimeValue {
+    fn from(time: ClockTime) -> Self {
+        if time.minute() == 0 {
+            ClockTimeValue::Hour(time.hour())
+        } else {
+            ClockTimeValue::Time(time.to_string())
+        }
+    }
+}
+
+/// A stretch of the day from `start` up to (not including) `end`, written as `"09:00-17:30"`
+///
+/// An end before the start runs past midnight, e.g. `"22:00-06:00"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TimeRange {
+    pub start: ClockTime,
+    pub end: ClockTime,
+}
+
+impl TimeRange {
+    pub const fn new(start: ClockTime, end: ClockTime) -> Self {
+        Self { start, end }
+    }
+
+    pub fn is_overnight(self) -> bool {
+        self.start > self.end
+    }
+
+    pub fn contains(self, time: ClockTime) -> bool {
+        if self.is_overnight() {
+            time >= self.start || time < self.end
+        } else {
+            time >= self.start && time < self.end
+        }
+    }
+
+    /// Length in minutes; "00:00-24:00" is the whole day
+    pub fn minutes(self) -> u32 {
+        if self.is_overnight() {
+            self.end.minutes() + MINUTES_PER_DAY - self.start.minutes()
+        } else {
+            self.end.minutes() - self.start.minutes()
+        }
+    }
+
+    /// The time halfway through
+    pub fn middle(self) -> ClockTime {
+        let middle = (self.start.minutes() + self.minutes() / 2) % MINUTES_PER_DAY;
+        ClockTime(middle)
+    }
+}
+
+impl fmt::Display for TimeRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl std::str::FromStr for TimeRange {
+    type Err = String;
+
+    /// Reads "9-17" or "09:30-17:30"
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("time range '{text}' should look like 09:00-17:30");
+        let (start, end) = text.split_once('-').ok_or_else(invalid)?;
+        let range = Self::new(
+            start.parse().map_err(|_| invalid())?,
+            end.parse().map_err(|_| invalid())?,
+        );
+        if range.start == range.end {
+            return Err(format!("time range '{text}' is empty"));
+        }
+        Ok(range)
+    }
+}
+
+impl TryFrom<String> for TimeRange {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        text.parse()
+    }
+}
+
+impl From<TimeRange> for String {
+    fn from(range: TimeRange) -> Self {
+        range.to_string()
+    }
+}
+
+/// Formats ranges for display, e.g. "09:00-12:00, 13:00-18:00", or "none"
+pub fn format_ranges(ranges: &[TimeRange]) -> String {
+    if ranges.is_empty() {
+        return "none".to_string();
+    }
+    ranges
+        .iter()
+        .map(TimeRange::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "TimeDisplayConfigCompat")]
 pub struct TimeDisplayConfig {
-    pub work_hours_start: u32,  // 8 (8 AM)
-    pub work_hours_end: u32,    // 18 (6 PM)
-    pub awake_hours_start: u32, // 6 (6 AM)
-    pub awake_hours_end: u32,   // 22 (10 PM)
-                                // Night hours are the complement: 22-6
+    pub work_hours: Vec<TimeRange>, // ["08:00-18:00"], or split shifts such as ["09:00-12:00", "13:00-18:00"]
+    pub awake_hours: Vec<TimeRange>, // ["06:00-22:00"]
+                                    // Night is whatever neither list covers
 }
 
 impl Default for TimeDisplayConfig {
     fn default() -> Self {
         Self {
-            work_hours_start: 8,  // 8 AM
-            work_hours_end: 18,   // 6 PM
-            awake_hours_start: 6, // 6 AM
-            awake_hours_end: 22,  // 10 PM
+            work_hours: vec![TimeRange::new(ClockTime::new(8, 0), ClockTime::new(18, 0))], // 8 AM - 6 PM
+            awake_hours: vec![TimeRange::new(ClockTime::new(6, 0), ClockTime::new(22, 0))], // 6 AM - 10 PM
+        }
+    }
+}
+
+/// `[time_config]` as written by any version: range lists, or the start and end times before them
+#[derive(Deserialize)]
+struct TimeDisplayConfigCompat {
+    work_hours: Option<Vec<TimeRange>>,
+    awake_hours: Option<Vec<TimeRange>>,
+    work_hours_start: Option<ClockTime>,
+    work_hours_end: Option<ClockTime>,
+    awake_hours_start: Option<ClockTime>,
+    awake_hours_end: Option<ClockTime>,
+}
+
+impl From<TimeDisplayConfigCompat> for TimeDisplayConfig {
+    fn from(compat: TimeDisplayConfigCompat) -> Self {
+        let defaults = TimeDisplayConfig::default();
+        Self {
+            work_hours: compat.work_hours.unwrap_or_else(|| {
+                legacy_ranges(
+                    compat.work_hours_start,
+                    compat.work_hours_end,
+                    defaults.work_hours[0],
+                )
+            }),
+            awake_hours: compat.awake_hours.unwrap_or_else(|| {
+                legacy_ranges(
+                    compat.awake_hours_start,
+                    compat.awake_hours_end,
+                    defaults.awake_hours[0],
+                )
+            }),
+        }
+    }
+}
+
+/// A version 1 or 2 start and end time as a range list, taking missing ones from `default`
+///
+/// Those ranges never crossed midnight, so one ending at or before its start had no hours.
+fn legacy_ranges(
+    start: Option<ClockTime>,
+    end: Option<ClockTime>,
+    default: TimeRange,
+) -> Vec<TimeRange> {
+    let range = TimeRange::new(start.unwrap_or(default.start), end.unwrap_or(default.end));
+    if range.start < range.end {
+        vec![range]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Which timelines show events loaded from calendar files
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum EventOverlay {
+    Off,
+    #[default]
+    Selected,
+    All,
+}
+
+impl EventOverlay {
+    pub fn next(&self) -> EventOverlay {
+        match self {
+            EventOverlay::Off => EventOverlay::Selected,
+            EventOverlay::Selected => EventOverlay::All,
+            EventOverlay::All => EventOverlay::Off,
+        }
+    }
+}
+
+/// What the timeline shading represents
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum ShadingMode {
+    #[default]
+    Schedule, // Night/awake/work hours from time_config
+    Daylight, // Actual sun position at each zone's city
+}
+
+impl ShadingMode {
+    pub fn next(&self) -> ShadingMode {
+        match self {
+            ShadingMode::Schedule => ShadingMode::Daylight,
+            ShadingMode::Daylight => ShadingMode::Schedule,
+        }
+    }
+}
+
+/// What the hour ruler above the zones shows
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum RulerMode {
+    #[default]
+    Off,
+    SelectedZone, // Local hours of the selected zone
+    Utc,
+}
+
+impl RulerMode {
+    pub fn next(&self) -> RulerMode {
+        match self {
+            RulerMode::Off => RulerMode::SelectedZone,
+            RulerMode::SelectedZone => RulerMode::Utc,
+            RulerMode::Utc => RulerMode::Off,
+        }
+    }
+}
+
+/// What each zone's title shows its offset relative to
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum RelativeOffsetMode {
+    #[default]
+    Off,
+    SelectedZone,
+    Local, // The system's own zone
+}
+
+impl RelativeOffsetMode {
+    pub fn next(&self) -> RelativeOffsetMode {
+        match self {
+            RelativeOffsetMode::Off => RelativeOffsetMode::SelectedZone,
+            RelativeOffsetMode::SelectedZone => RelativeOffsetMode::Local,
+            RelativeOffsetMode::Local => RelativeOffsetMode::Off,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RelativeOffsetMode::Off => "off",
+            RelativeOffsetMode::SelectedZone => "from the selected zone",
+            RelativeOffsetMode::Local => "from local time",
+        }
+    }
+}
+
+/// How much time the timelines span
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum TimelineZoom {
+    Hours6,
+    Hours12,
+    Hours24,
+    Hours48,
+    Week,
+    #[default]
+    Auto, // Derived from terminal width (48h - 1 week)
+}
+
+impl TimelineZoom {
+    /// Hours spanned by a timeline `width` columns wide
+    pub fn hours(&self, width: u16) -> f64 {
+        match self {
+            TimelineZoom::Hours6 => 6.0,
+            TimelineZoom::Hours12 => 12.0,
+            TimelineZoom::Hours24 => 24.0,
+            TimelineZoom::Hours48 => 48.0,
+            TimelineZoom::Week => 168.0,
+            TimelineZoom::Auto => {
+                // Optimal display: approximately 2 characters per hour for dense but readable display
+                // This means 48 hours fits in ~96 characters, allowing expansion on wider screens
+                const OPTIMAL_CHARS_PER_HOUR: f64 = 2.0;
+                const MIN_HOURS: f64 = 48.0; // Minimum 48-hour window (24h before + 24h after)
+                const MAX_HOURS: f64 = 168.0; // Maximum 1 week window
+
+                // Calculate how many hours we can display optimally with current width
+                let optimal_hours = (width as f64) / OPTIMAL_CHARS_PER_HOUR;
+
+                // Clamp between minimum and maximum
+                optimal_hours.clamp(MIN_HOURS, MAX_HOURS)
+            }
+        }
+    }
+
+    /// Size of one timeline cell in minutes; also the step for h/l scrubbing
+    pub fn cell_minutes(&self) -> i64 {
+        match self {
+            TimelineZoom::Hours6 => 15,
+            TimelineZoom::Hours12 => 30,
+            _ => 60,
+        }
+    }
+
+    pub fn zoom_in(&self) -> TimelineZoom {
+        match self {
+            TimelineZoom::Hours6 | TimelineZoom::Hours12 => TimelineZoom::Hours6,
+            TimelineZoom::Hours24 => TimelineZoom::Hours12,
+            TimelineZoom::Hours48 | TimelineZoom::Auto => TimelineZoom::Hours24,
+            TimelineZoom::Week => TimelineZoom::Hours48,
+        }
+    }
+
+    pub fn zoom_out(&self) -> TimelineZoom {
+        match self {
+            TimelineZoom::Hours6 => TimelineZoom::Hours12,
+            TimelineZoom::Hours12 => TimelineZoom::Hours24,
+            TimelineZoom::Hours24 => TimelineZoom::Hours48,
+            TimelineZoom::Hours48 | TimelineZoom::Week | TimelineZoom::Auto => TimelineZoom::Week,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimelineZoom::Hours6 => "6h",
+            TimelineZoom::Hours12 => "12h",
+            TimelineZoom::Hours24 => "24h",
+            TimelineZoom::Hours48 => "48h",
+            TimelineZoom::Week => "week",
+            TimelineZoom::Auto => "auto",
         }
     }
 }
@@ -35,6 +440,16 @@ pub enum TimeActivity {
     Work,  // 8 AM - 6 PM
 }
 
+impl TimeActivity {
+    pub fn label(self) -> &'static str {
+        match self {
+            TimeActivity::Night => "Night",
+            TimeActivity::Awake => "Awake",
+            TimeActivity::Work => "Work hours",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
 pub enum ColorTheme {
     #[default]
@@ -58,6 +473,13 @@ impl ColorTheme {
         ]
     }
 
+    /// Looks a theme up by name, ignoring case, e.g. "ocean"
+    pub fn from_name(name: &str) -> Option<ColorTheme> {
+        Self::all_themes()
+            .into_iter()
+            .find(|theme| format!("{theme:?}").eq_ignore_ascii_case(name.trim()))
+    }
+
     pub fn next(&self) -> ColorTheme {
         let themes = Self::all_themes();
         let current_index = themes.iter().position(|t| t == self).unwrap_or(0);
@@ -123,21 +545,71 @@ impl ColorTheme {
     pub fn get_current_time_color(&self) -> Color {
         Color::Red // Keep consistent across all themes for clarity
     }
+
+    /// Shade for daylight mode, darkest at night through to full day
+    pub fn get_solar_phase_display(&self, phase: SolarPhase) -> (char, Color) {
+        match phase {
+            SolarPhase::Night => ('░', self.get_night_color()),
+            SolarPhase::AstronomicalTwilight => ('▒', self.get_night_color()),
+            SolarPhase::NauticalTwilight => ('░', self.get_awake_color()),
+            SolarPhase::CivilTwilight => ('▒', self.get_awake_color()),
+            SolarPhase::Day => ('▓', self.get_work_color()),
+        }
+    }
+
+    pub fn get_event_color(&self) -> Color {
+        match self {
+            ColorTheme::Default => Color::Blue,
+            ColorTheme::Ocean => Color::Rgb(0, 70, 110),
+            ColorTheme::Forest => Color::Rgb(60, 45, 20),
+            ColorTheme::Sunset => Color::Rgb(80, 20, 60),
+            ColorTheme::Cyberpunk => Color::Rgb(20, 20, 90),
+            ColorTheme::Monochrome => Color::DarkGray,
+        }
+    }
+
+    /// Colour of countdown markers and the header readout
+    pub fn get_countdown_color(&self) -> Color {
+        match self {
+            ColorTheme::Monochrome => Color::Gray,
+            _ => Color::LightRed,
+        }
+    }
+
+    /// Colour of bookmark markers on the timelines
+    pub fn get_bookmark_color(&self) -> Color {
+        match self {
+            ColorTheme::Default => Color::Magenta,
+            ColorTheme::Ocean => Color::LightMagenta,
+            ColorTheme::Forest => Color::LightRed,
+            ColorTheme::Sunset => Color::LightCyan,
+            ColorTheme::Cyberpunk => Color::LightYellow,
+            ColorTheme::Monochrome => Color::White,
+        }
+    }
 }
 
 impl TimeDisplayConfig {
-    pub fn get_time_activity(&self, hour: u32) -> TimeActivity {
-        let hour = hour % 24; // Ensure valid hour range
-
-        if hour >= self.work_hours_start && hour < self.work_hours_end {
+    /// Activity at a time of day, to the minute; work hours win where the lists overlap
+    pub fn get_time_activity_at(&self, time: impl Into<ClockTime>) -> TimeActivity {
+        let time = time.into();
+        if self.work_hours.iter().any(|range| range.contains(time)) {
             TimeActivity::Work
-        } else if hour >= self.awake_hours_start && hour < self.awake_hours_end {
+        } else if self.awake_hours.iter().any(|range| range.contains(time)) {
             TimeActivity::Awake
         } else {
             TimeActivity::Night
         }
     }
 
+    /// Where date labels sit on a timeline: the middle of the longest work range, or noon
+    pub fn work_midday(&self) -> ClockTime {
+        self.work_hours
+            .iter()
+            .max_by_key(|range| range.minutes())
+            .map_or(ClockTime::new(12, 0), |range| range.middle())
+    }
+
     pub fn get_activity_char(&self, activity: TimeActivity) -> char {
         match activity {
             TimeActivity::Night => '░', // Light shade - low activity
@@ -187,6 +659,8 @@ impl ZoneConfigCompat {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
+    #[serde(default)]
+    pub version: u32, // Schema version, 0 for files written before versioning
     pub zones: Vec<ZoneConfigCompat>, // List of timezone configurations
     pub selected_zone_index: usize,   // Currently selected timezone
     pub display_format: TimeFormat,   // 12/24 hour format
@@ -196,11 +670,36 @@ pub struct AppConfig {
     pub show_date: bool,              // Date display toggle
     #[serde(default = "default_true")]
     pub show_sun_times: bool, // Sunrise/sunset times display toggle
+    #[serde(default)]
+    pub ics_files: Vec<String>, // Calendar files to overlay on timelines
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export_dir: Option<String>, // Where `x` writes invites; the config's directory when unset
+    #[serde(default)]
+    pub event_overlay: EventOverlay, // Which timelines show calendar events
+    #[serde(default)]
+    pub timeline_zoom: TimelineZoom,
+    #[serde(default)]
+    pub ruler_mode: RulerMode,
+    #[serde(default)]
+    pub shading_mode: ShadingMode,
+    #[serde(default)]
+    pub relative_offsets: RelativeOffsetMode,
+    #[serde(default)]
+    pub show_info_panel: bool, // Detail panel for the selected zone
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>, // Named instants and recurring local times
+    #[serde(default)]
+    pub countdowns: Vec<Countdown>,
+    #[serde(default)]
+    pub reminders: Vec<Reminder>, // e.g. "every weekday 09:55 in Europe/London: standup"
+    #[serde(default)]
+    pub reminder_notifications: bool, // Also send reminders as desktop notifications
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
+            version: CONFIG_VERSION,
             zones: vec![
                 ZoneConfigCompat::Simple("Los Angeles".to_string()),
                 ZoneConfigCompat::Simple("New York".to_string()),
@@ -217,37 +716,317 @@ impl Default for AppConfig {
             color_theme: ColorTheme::default(),
             show_date: false,
             show_sun_times: true, // Enable by default
+            ics_files: Vec::new(),
+            export_dir: None,
+            event_overlay: EventOverlay::default(),
+            timeline_zoom: TimelineZoom::default(),
+            ruler_mode: RulerMode::default(),
+            shading_mode: ShadingMode::default(),
+            relative_offsets: RelativeOffsetMode::default(),
+            show_info_panel: false,
+            bookmarks: Vec::new(),
+            countdowns: Vec::new(),
+            reminders: Vec::new(),
+            reminder_notifications: false,
+        }
+    }
+}
+
+/// Something wrong with the config file, located by path and (when known) line
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    pub path: PathBuf,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.path.display(), line, self.message),
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+/// A loaded config plus everything that was wrong with the file
+#[derive(Debug, Clone)]
+pub struct LoadedConfig {
+    pub config: AppConfig,
+    pub issues: Vec<ConfigIssue>,
+    /// False when the file could not be read and `config` holds the defaults
+    pub parsed: bool,
+    /// False when saving would throw away settings we could not read
+    pub writable: bool,
+    pub file: ConfigFile,
+}
+
+/// Upgrades a config table by one schema version; `MIGRATIONS[n]` takes version n to n + 1
+type Migration = fn(&mut toml::Table);
+
+const MIGRATIONS: [Migration; CONFIG_VERSION as usize] =
+    [migrate_v0_to_v1, migrate_v1_to_v2, migrate_v2_to_v3];
+
+/// Files from before versioning may lack keys later releases required (the untagged
+/// `ZoneConfigCompat` covers the older zone format); fill them in from the defaults
+/// so a partial file keeps the settings it does have
+fn migrate_v0_to_v1(table: &mut toml::Table) {
+    let defaults = toml::Table::try_from(AppConfig::default()).expect("default config serializes");
+    for (key, default) in defaults {
+        match (table.get_mut(&key), default) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(default)) => {
+                for (key, value) in default {
+                    existing.entry(key).or_insert(value);
+                }
+            }
+            (Some(_), _) => {}
+            (None, default) => {
+                table.insert(key, default);
+            }
+        }
+    }
+}
+
+/// Version 2 widened the start and end hours to also accept "HH:MM"; every version 1 file
+/// is already valid
+fn migrate_v1_to_v2(_table: &mut toml::Table) {}
+
+/// Version 2 stored one start and end time per activity; version 3 stores lists of ranges
+fn migrate_v2_to_v3(table: &mut toml::Table) {
+    let Some(toml::Value::Table(time_config)) = table.get_mut("time_config") else {
+        return;
+    };
+    let defaults = TimeDisplayConfig::default();
+    for (activity, default) in [
+        ("work_hours", defaults.work_hours[0]),
+        ("awake_hours", defaults.awake_hours[0]),
+    ] {
+        let (start_key, end_key) = (format!("{activity}_start"), format!("{activity}_end"));
+        let time = |key: &str| time_config.get(key).cloned().map(toml::Value::try_into);
+        let (start, end) = match (time(&start_key), time(&end_key)) {
+            (None, None) => continue,
+            (Some(Err(_)), _) | (_, Some(Err(_))) => continue, // Left for loading to report
+            (start, end) => (start.and_then(Result::ok), end.and_then(Result::ok)),
+        };
+        let ranges = legacy_ranges(start, end, default)
+            .iter()
+            .map(|range| range.to_string().into())
+            .collect();
+        time_config.remove(&start_key);
+        time_config.remove(&end_key);
+        time_config.insert(activity.to_string(), toml::Value::Array(ranges));
+    }
+}
+
+/// Keys of `before`, and of the tables in it, that `after` no longer has
+fn removed_keys(before: &toml::Table, after: &toml::Table) -> Vec<Vec<String>> {
+    let mut removed = Vec::new();
+    for (key, value) in before {
+        match (value, after.get(key)) {
+            (_, None) => removed.push(vec![key.clone()]),
+            (toml::Value::Table(before), Some(toml::Value::Table(after))) => {
+                removed.extend(removed_keys(before, after).into_iter().map(|mut path| {
+                    path.insert(0, key.clone());
+                    path
+                }))
+            }
+            _ => {}
         }
     }
+    removed
+}
+
+/// 1-based line of a byte offset in `content`
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
 }
 
 impl AppConfig {
+    /// Where the config lives: `--config`, then `$ALLTZ_CONFIG`, then
+    /// `$XDG_CONFIG_HOME/alltz/config.toml`, then `~/.config/alltz/config.toml`
     pub fn config_path() -> Option<PathBuf> {
-        dirs::home_dir().map(|home_dir| home_dir.join(".config").join("alltz").join("config.toml"))
+        if let Some(path) = CONFIG_PATH_OVERRIDE.get() {
+            return Some(path.clone());
+        }
+        Self::resolve_config_path(
+            env::var_os("ALLTZ_CONFIG"),
+            env::var_os("XDG_CONFIG_HOME"),
+            dirs::home_dir(),
+        )
+    }
+
+    /// Uses `path` instead of the usual location for the rest of this run (`--config`)
+    pub fn set_config_path(path: PathBuf) {
+        let _ = CONFIG_PATH_OVERRIDE.set(path);
+    }
+
+    fn resolve_config_path(
+        explicit: Option<OsString>,
+        xdg_config_home: Option<OsString>,
+        home_dir: Option<PathBuf>,
+    ) -> Option<PathBuf> {
+        if let Some(path) = explicit.filter(|path| !path.is_empty()) {
+            return Some(PathBuf::from(path));
+        }
+        // The XDG spec says to ignore relative paths
+        let config_home = xdg_config_home
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| home_dir.map(|home_dir| home_dir.join(".config")))?;
+        Some(config_home.join("alltz").join("config.toml"))
     }
 
+    /// Loads the config for CLI commands, reporting problems on stderr
     pub fn load() -> Self {
-        if let Some(config_path) = Self::config_path() {
-            if let Ok(content) = fs::read_to_string(&config_path) {
-                if let Ok(config) = toml::from_str::<AppConfig>(&content) {
-                    return config;
-                }
-            }
+        let loaded = Self::load_reported();
+        for issue in &loaded.issues {
+            eprintln!("{}", t!("cli.config.issue", issue = issue.to_string()));
+        }
+        loaded.config
+    }
+
+    /// Loads the config and collects problems instead of printing them
+    pub fn load_reported() -> LoadedConfig {
+        let Some(config_path) = Self::config_path() else {
+            return LoadedConfig {
+                config: Self::default(),
+                issues: Vec::new(),
+                parsed: true,
+                writable: true,
+                file: ConfigFile::new(None),
+            };
+        };
+        match fs::read_to_string(&config_path) {
+            Ok(content) => Self::parse(&content, &config_path),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => LoadedConfig {
+                config: Self::default(),
+                issues: Vec::new(),
+                parsed: true,
+                writable: true,
+                file: ConfigFile::new(Some(config_path)),
+            },
+            Err(e) => LoadedConfig {
+                config: Self::default(),
+                issues: vec![ConfigIssue {
+                    path: config_path.clone(),
+                    line: None,
+                    message: format!("could not read config: {e}"),
+                }],
+                parsed: false,
+                writable: false,
+                file: ConfigFile::new(Some(config_path)),
+            },
         }
-        Self::default()
     }
 
-    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(config_path) = Self::config_path() {
-            // Create config directory if it doesn't exist
-            if let Some(parent) = config_path.parent() {
-                fs::create_dir_all(parent)?;
+    /// Parses, migrates and validates config file `content` read from `path`
+    pub fn parse(content: &str, path: &Path) -> LoadedConfig {
+        let issue = |line: Option<usize>, message: String| ConfigIssue {
+            path: path.to_path_buf(),
+            line,
+            message,
+        };
+        let unreadable = |issue: ConfigIssue| LoadedConfig {
+            config: Self::default(),
+            issues: vec![issue],
+            parsed: false,
+            writable: false,
+            file: ConfigFile::new(Some(path.to_path_buf())),
+        };
+        let error_issue = |e: toml::de::Error| {
+            issue(
+                e.span().map(|span| line_of(content, span.start)),
+                e.message().to_string(),
+            )
+        };
+
+        let mut table = match toml::from_str::<toml::Table>(content) {
+            Ok(table) => table,
+            Err(e) => return unreadable(error_issue(e)),
+        };
+        // Bad reminders are reported and skipped, like unknown cities, rather than making
+        // the whole file unreadable; they stay in the file until the reminders change
+        let mut issues = Vec::new();
+        if let Some(toml::Value::Array(reminders)) = table.get_mut("reminders") {
+            reminders.retain(|reminder| {
+                let Some(definition) = reminder.as_str() else {
+                    return true;
+                };
+                let Err(message) = definition.parse::<Reminder>() else {
+                    return true;
+                };
+                let line = content
+                    .find(definition)
+                    .map(|offset| line_of(content, offset));
+                issues.push(issue(line, message));
+                false
+            });
+        }
+        let mut file = ConfigFile::loaded(path, content, table.clone());
+
+        let version = match table.get("version") {
+            None => 0,
+            Some(toml::Value::Integer(version)) if *version >= 0 => *version as u32,
+            Some(_) => {
+                let line = content
+                    .find("version")
+                    .map(|offset| line_of(content, offset));
+                return unreadable(issue(line, "version must be a whole number".to_string()));
+            }
+        };
+        // A newer alltz wrote this: read what we understand but leave the file alone
+        let newer = version > CONFIG_VERSION;
+        if newer {
+            issues.push(issue(
+                None,
+                format!("written by a newer alltz (config version {version}); changes will not be saved"),
+            ));
+        }
+        let original = table.clone();
+        for migration in &MIGRATIONS[version.min(CONFIG_VERSION) as usize..] {
+            migration(&mut table);
+        }
+        // Keys the migrations replaced come out of the file the next time it's saved
+        file.retire(removed_keys(&original, &table));
+        table.insert(
+            "version".to_string(),
+            toml::Value::Integer(CONFIG_VERSION as i64),
+        );
+
+        let config = match toml::Value::Table(table).try_into::<AppConfig>() {
+            Ok(config) => config,
+            Err(e) => {
+                // Deserializing the original text again gives the error a line number, as
+                // long as it fails the same way: migrated files can fail differently
+                let line = toml::from_str::<AppConfig>(content)
+                    .err()
+                    .filter(|located| located.message() == e.message())
+                    .and_then(|located| located.span())
+                    .map(|span| line_of(content, span.start));
+                return unreadable(issue(line, e.message().to_string()));
             }
+        };
 
-            let content = toml::to_string_pretty(self)?;
-            fs::write(&config_path, content)?;
+        issues.extend(
+            config
+                .zones
+                .iter()
+                .filter(|zone| !TimeZoneManager::is_known_city(zone.city_name()))
+                .map(|zone| {
+                    let line = content
+                        .find(&format!("\"{}\"", zone.city_name()))
+                        .map(|offset| line_of(content, offset));
+                    issue(line, format!("unknown city \"{}\"", zone.city_name()))
+                }),
+        );
+
+        LoadedConfig {
+            config,
+            issues,
+            parsed: true,
+            writable: !newer,
+            file,
         }
-        Ok(())
     }
 }
 
@@ -260,19 +1039,46 @@ mod tests {
         let config = TimeDisplayConfig::default();
 
         // Test work hours
-        assert_eq!(config.get_time_activity(9), TimeActivity::Work); // 9 AM
-        assert_eq!(config.get_time_activity(14), TimeActivity::Work); // 2 PM
-        assert_eq!(config.get_time_activity(17), TimeActivity::Work); // 5 PM
+        assert_eq!(
+            config.get_time_activity_at(ClockTime::new(9, 0)),
+            TimeActivity::Work
+        ); // 9 AM
+        assert_eq!(
+            config.get_time_activity_at(ClockTime::new(14, 0)),
+            TimeActivity::Work
+        ); // 2 PM
+        assert_eq!(
+            config.get_time_activity_at(ClockTime::new(17, 0)),
+            TimeActivity::Work
+        ); // 5 PM
 
         // Test awake hours
-        assert_eq!(config.get_time_activity(7), TimeActivity::Awake); // 7 AM
-        assert_eq!(config.get_time_activity(19), TimeActivity::Awake); // 7 PM
-        assert_eq!(config.get_time_activity(21), TimeActivity::Awake); // 9 PM
+        assert_eq!(
+            config.get_time_activity_at(ClockTime::new(7, 0)),
+            TimeActivity::Awake
+        ); // 7 AM
+        assert_eq!(
+            config.get_time_activity_at(ClockTime::new(19, 0)),
+            TimeActivity::Awake
+        ); // 7 PM
+        assert_eq!(
+            config.get_time_activity_at(ClockTime::new(21, 0)),
+            TimeActivity::Awake
+        ); // 9 PM
 
         // Test night hours
-        assert_eq!(config.get_time_activity(23), TimeActivity::Night); // 11 PM
-        assert_eq!(config.get_time_activity(2), TimeActivity::Night); // 2 AM
-        assert_eq!(config.get_time_activity(5), TimeActivity::Night); // 5 AM
+        assert_eq!(
+            config.get_time_activity_at(ClockTime::new(23, 0)),
+            TimeActivity::Night
+        ); // 11 PM
+        assert_eq!(
+            config.get_time_activity_at(ClockTime::new(2, 0)),
+            TimeActivity::Night
+        ); // 2 AM
+        assert_eq!(
+            config.get_time_activity_at(ClockTime::new(5, 0)),
+            TimeActivity::Night
+        ); // 5 AM
     }
 
     #[test]
@@ -289,16 +1095,139 @@ mod tests {
         let config = TimeDisplayConfig::default();
 
         // Boundary at work start (8 AM)
-        assert_eq!(config.get_time_activity(7), TimeActivity::Awake);
-        assert_eq!(config.get_time_activity(8), TimeActivity::Work);
+        assert_eq!(
+            config.get_time_activity_at(ClockTime::new(7, 0)),
+            TimeActivity::Awake
+        );
+        assert_eq!(
+            config.get_time_activity_at(ClockTime::new(8, 0)),
+            TimeActivity::Work
+        );
 
         // Boundary at work end (6 PM)
-        assert_eq!(config.get_time_activity(17), TimeActivity::Work);
-        assert_eq!(config.get_time_activity(18), TimeActivity::Awake);
+        assert_eq!(
+            config.get_time_activity_at(ClockTime::new(17, 0)),
+            TimeActivity::Work
+        );
+        assert_eq!(
+            config.get_time_activity_at(ClockTime::new(18, 0)),
+            TimeActivity::Awake
+        );
 
         // Boundary at awake end (10 PM)
-        assert_eq!(config.get_time_activity(21), TimeActivity::Awake);
-        assert_eq!(config.get_time_activity(22), TimeActivity::Night);
+        assert_eq!(
+            config.get_time_activity_at(ClockTime::new(21, 0)),
+            TimeActivity::Awake
+        );
+        assert_eq!(
+            config.get_time_activity_at(ClockTime::new(22, 0)),
+            TimeActivity::Night
+        );
+    }
+
+    #[test]
+    fn test_time_ranges() {
+        let config: TimeDisplayConfig = toml::from_str(
+            r#"
+work_hours = ["09:30-12:00", "13:00-17:30"]
+awake_hours = ["7-23:15"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.work_hours[0].start, ClockTime::new(9, 30));
+        assert_eq!(config.awake_hours[0].end, ClockTime::new(23, 15));
+
+        let at = |hour, minute| config.get_time_activity_at(ClockTime::new(hour, minute));
+        assert_eq!(at(9, 29), TimeActivity::Awake);
+        assert_eq!(at(9, 30), TimeActivity::Work);
+        assert_eq!(at(12, 30), TimeActivity::Awake); // Lunch
+        assert_eq!(at(17, 29), TimeActivity::Work);
+        assert_eq!(at(23, 15), TimeActivity::Night);
+        assert_eq!(config.work_midday(), ClockTime::new(15, 15));
+
+        let saved = toml::to_string(&config).unwrap();
+        assert!(saved.contains(r#"work_hours = ["09:30-12:00", "13:00-17:30"]"#));
+
+        // Night shifts run past midnight
+        let night_shift: TimeRange = "22:00-06:00".parse().unwrap();
+        assert!(night_shift.is_overnight());
+        assert!(night_shift.contains(ClockTime::new(23, 0)));
+        assert!(night_shift.contains(ClockTime::new(5, 59)));
+        assert!(!night_shift.contains(ClockTime::new(6, 0)));
+        assert_eq!(night_shift.minutes(), 8 * 60);
+        assert_eq!(night_shift.middle(), ClockTime::new(2, 0));
+
+        assert!("25:00".parse::<ClockTime>().is_err());
+        assert!("9:60".parse::<ClockTime>().is_err());
+        assert!("9-9".parse::<TimeRange>().is_err());
+        assert!("24:00-24:00".parse::<TimeRange>().is_err());
+        assert_eq!("0-24".parse::<TimeRange>().unwrap().minutes(), 24 * 60);
+        assert!("9 to 17".parse::<TimeRange>().is_err());
+    }
+
+    #[test]
+    fn test_migrate_start_and_end_hours() {
+        let path = Path::new("/home/me/.config/alltz/config.toml");
+        let loaded = AppConfig::parse(
+            r#"
+version = 2
+zones = ["Tokyo"]
+selected_zone_index = 0
+display_format = "TwentyFourHour"
+timezone_display_mode = "Short"
+color_theme = "Default"
+show_date = false
+
+[time_config]
+work_hours_start = "09:30"
+work_hours_end = 17
+awake_hours_start = 7
+awake_hours_end = 7
+"#,
+            path,
+        );
+        assert!(loaded.issues.is_empty());
+        let time_config = loaded.config.time_config;
+        assert_eq!(time_config.work_hours, ["09:30-17:00".parse().unwrap()]);
+        // Old ranges ending where they started had no hours
+        assert!(time_config.awake_hours.is_empty());
+
+        // Awake around the clock stays a whole-day range
+        let loaded = AppConfig::parse(
+            r#"
+version = 1
+zones = ["Tokyo"]
+selected_zone_index = 0
+display_format = "TwentyFourHour"
+timezone_display_mode = "Short"
+color_theme = "Default"
+show_date = false
+
+[time_config]
+work_hours_start = 9
+work_hours_end = 17
+awake_hours_start = 0
+awake_hours_end = 24
+"#,
+            path,
+        );
+        assert!(loaded.parsed && loaded.writable);
+        assert!(loaded.issues.is_empty());
+        let time_config = loaded.config.time_config;
+        assert_eq!(time_config.awake_hours, ["00:00-24:00".parse().unwrap()]);
+        assert_eq!(time_config.awake_hours[0].minutes(), 24 * 60);
+        assert_eq!(
+            time_config.get_time_activity_at(ClockTime::new(3, 0)),
+            TimeActivity::Awake
+        );
+
+        // Bad old hours are still reported rather than quietly replaced
+        let loaded = AppConfig::parse(
+            "version = 1\nzones = [\"Tokyo\"]\n\n[time_config]\nwork_hours_start = 30\n",
+            path,
+        );
+        assert!(!loaded.parsed);
+        assert!(loaded.issues[0].message.contains("past 24"));
     }
 
     #[test]
@@ -391,4 +1320,207 @@ awake_hours_end = 22
         assert_eq!(config.zones[2].city_name(), "London");
         assert_eq!(config.zones[2].custom_label(), Some("Bob (Sales)"));
     }
+
+    #[test]
+    fn test_calendar_settings() {
+        // Older configs have no calendar settings
+        let config: AppConfig = toml::from_str(
+            r#"
+zones = ["Tokyo"]
+selected_zone_index = 0
+display_format = "TwentyFourHour"
+timezone_display_mode = "Short"
+color_theme = "Default"
+show_date = false
+
+[time_config]
+work_hours_start = 8
+work_hours_end = 18
+awake_hours_start = 6
+awake_hours_end = 22
+"#,
+        )
+        .unwrap();
+        assert!(config.ics_files.is_empty());
+        assert_eq!(config.event_overlay, EventOverlay::Selected);
+
+        let config = AppConfig {
+            ics_files: vec!["~/calendars/work.ics".to_string()],
+            event_overlay: EventOverlay::All,
+            ..AppConfig::default()
+        };
+        let parsed: AppConfig = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(parsed.ics_files, config.ics_files);
+        assert_eq!(parsed.event_overlay, EventOverlay::All);
+        assert_eq!(EventOverlay::All.next(), EventOverlay::Off);
+    }
+
+    #[test]
+    fn test_timeline_zoom() {
+        assert_eq!(TimelineZoom::Auto.hours(80), 48.0);
+        assert_eq!(TimelineZoom::Auto.hours(200), 100.0);
+        assert_eq!(TimelineZoom::Auto.hours(1000), 168.0);
+        assert_eq!(TimelineZoom::Hours6.hours(1000), 6.0);
+        assert_eq!(TimelineZoom::Hours6.cell_minutes(), 15);
+        assert_eq!(TimelineZoom::Week.cell_minutes(), 60);
+
+        // Zooming walks the fixed levels and stops at either end
+        let mut zoom = TimelineZoom::Auto;
+        for expected in [
+            TimelineZoom::Hours24,
+            TimelineZoom::Hours12,
+            TimelineZoom::Hours6,
+            TimelineZoom::Hours6,
+        ] {
+            zoom = zoom.zoom_in();
+            assert_eq!(zoom, expected);
+        }
+        for expected in [
+            TimelineZoom::Hours12,
+            TimelineZoom::Hours24,
+            TimelineZoom::Hours48,
+            TimelineZoom::Week,
+            TimelineZoom::Week,
+        ] {
+            zoom = zoom.zoom_out();
+            assert_eq!(zoom, expected);
+        }
+
+        // Older configs fall back to the width-derived span
+        let config: AppConfig = toml::from_str(
+            r#"zones = ["Tokyo"]
+selected_zone_index = 0
+display_format = "TwentyFourHour"
+timezone_display_mode = "Short"
+color_theme = "Default"
+show_date = false
+
+[time_config]
+work_hours_start = 8
+work_hours_end = 18
+awake_hours_start = 6
+awake_hours_end = 22
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.timeline_zoom, TimelineZoom::Auto);
+        assert_eq!(config.ruler_mode, RulerMode::Off);
+        assert_eq!(config.shading_mode, ShadingMode::Schedule);
+    }
+
+    #[test]
+    fn test_config_migration_and_issues() {
+        let path = Path::new("/home/me/.config/alltz/config.toml");
+
+        // A pre-versioning file missing keys later releases required keeps what it has
+        let loaded = AppConfig::parse(
+            r#"
+zones = ["Tokyo", "Atlantis", { city_name = "London", custom_label = "Bob" }]
+display_format = "TwelveHour"
+color_theme = "Ocean"
+
+[time_config]
+work_hours_start = 10
+"#,
+            path,
+        );
+        assert!(loaded.writable);
+        assert_eq!(loaded.config.version, CONFIG_VERSION);
+        assert_eq!(loaded.config.zones.len(), 3);
+        assert_eq!(loaded.config.display_format, TimeFormat::TwelveHour);
+        assert_eq!(loaded.config.color_theme, ColorTheme::Ocean);
+        assert_eq!(
+            loaded.config.time_config.work_hours,
+            [TimeRange::new(ClockTime::new(10, 0), ClockTime::new(18, 0))]
+        );
+        assert_eq!(
+            loaded.issues,
+            vec![ConfigIssue {
+                path: path.to_path_buf(),
+                line: Some(2),
+                message: "unknown city \"Atlantis\"".to_string(),
+            }]
+        );
+        assert_eq!(
+            loaded.issues[0].to_string(),
+            "/home/me/.config/alltz/config.toml:2: unknown city \"Atlantis\""
+        );
+
+        // Bad values are reported at their line, and the file is left alone
+        let loaded = AppConfig::parse(
+            "version = 1\nzones = [\"Tokyo\"]\ncolor_theme = \"Plaid\"\n",
+            path,
+        );
+        assert!(!loaded.writable);
+        assert_eq!(loaded.issues.len(), 1);
+        assert_eq!(loaded.issues[0].line, Some(3));
+
+        let loaded = AppConfig::parse(
+            "zones = [\"Tokyo\"]\n\n[time_config]\nwork_hours = [\"9-9\"]\n",
+            path,
+        );
+        assert_eq!(loaded.issues[0].line, Some(4));
+        assert!(loaded.issues[0].message.contains("empty"));
+
+        let loaded = AppConfig::parse("zones = [\"Tokyo\"\nshow_date = true\n", path);
+        assert!(!loaded.writable);
+        assert!(loaded.issues[0].line.is_some());
+
+        // Files from a newer release load but are not overwritten
+        let newer = AppConfig {
+            version: 99,
+            zones: vec![ZoneConfigCompat::Simple("Tokyo".to_string())],
+            ..AppConfig::default()
+        };
+        let loaded = AppConfig::parse(&toml::to_string(&newer).unwrap(), path);
+        assert!(!loaded.writable);
+        assert_eq!(loaded.config.zones[0].city_name(), "Tokyo");
+        assert!(loaded.issues[0].message.contains("newer alltz"));
+
+        // One bad reminder doesn't cost the others
+        let loaded = AppConfig::parse(
+            r#"zones = ["Tokyo"]
+reminders = [
+    "every weekday 09:55 in Europe/London: standup",
+    "every Funday 10:00 in Tokyo: nap",
+    "every day 12:00 in Atlantis: lunch",
+]
+"#,
+            path,
+        );
+        assert!(loaded.writable);
+        assert_eq!(loaded.config.reminders.len(), 1);
+        assert_eq!(loaded.config.reminders[0].message, "standup");
+        let lines: Vec<_> = loaded.issues.iter().map(|issue| issue.line).collect();
+        assert_eq!(lines, [Some(4), Some(5)]);
+        assert!(loaded.issues[0].message.contains("unknown day"));
+        assert!(loaded.issues[1].message.contains("unknown zone"));
+    }
+
+    #[test]
+    fn test_config_path_resolution() {
+        let home = Some(PathBuf::from("/home/me"));
+        assert_eq!(
+            AppConfig::resolve_config_path(None, None, home.clone()),
+            Some(PathBuf::from("/home/me/.config/alltz/config.toml"))
+        );
+        assert_eq!(
+            AppConfig::resolve_config_path(None, Some("/xdg".into()), home.clone()),
+            Some(PathBuf::from("/xdg/alltz/config.toml"))
+        );
+        // Relative XDG paths are ignored, an explicit path wins over everything
+        assert_eq!(
+            AppConfig::resolve_config_path(None, Some("xdg".into()), home.clone()),
+            Some(PathBuf::from("/home/me/.config/alltz/config.toml"))
+        );
+        assert_eq!(
+            AppConfig::resolve_config_path(
+                Some("/etc/alltz.toml".into()),
+                Some("/xdg".into()),
+                home
+            ),
+            Some(PathBuf::from("/etc/alltz.toml"))
+        );
+        assert_eq!(AppConfig::resolve_config_path(None, None, None), None);
+    }
 }
@@ -0,0 +1,476 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table, Value};
+
+use crate::config::AppConfig;
+
+/// How a save went
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SaveOutcome {
+    Saved,
+    Unchanged,
+    Merged, // Someone else changed the file since we read it; our changes went on top of theirs
+}
+
+/// The config file as this instance last saw it
+///
+/// Saves only rewrite settings that changed since then, through a format-preserving
+/// editor, so comments, unknown keys and edits made by other instances survive.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigFile {
+    path: Option<PathBuf>,
+    baseline: toml::Table,        // Settings as last read or written
+    disk_content: Option<String>, // File content as last read or written
+    retired: Vec<Vec<String>>,    // Key paths a migration replaced, removed on the next save
+}
+
+impl ConfigFile {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            ..Self::default()
+        }
+    }
+
+    /// Records what was read from disk; `settings` is the file's own (unmigrated) table
+    pub fn loaded(path: &Path, content: &str, settings: toml::Table) -> Self {
+        Self {
+            path: Some(path.to_path_buf()),
+            baseline: settings,
+            disk_content: Some(content.to_string()),
+            retired: Vec::new(),
+        }
+    }
+
+    /// Marks keys an older schema used, so the next save takes them out of the file
+    pub fn retire(&mut self, keys: Vec<Vec<String>>) {
+        self.retired = keys;
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// The file's content if someone changed it since we last read or wrote it
+    pub fn changed_on_disk(&self) -> Option<String> {
+        let content = fs::read_to_string(self.path.as_ref()?).ok()?;
+        (self.disk_content.as_ref() != Some(&content)).then_some(content)
+    }
+
+    /// Marks `content` as seen without taking its settings, e.g. when it doesn't parse
+    pub fn acknowledge(&mut self, content: String) {
+        self.disk_content = Some(content);
+    }
+
+    /// Writes the settings that differ from the baseline into the file on disk
+    pub fn save(&mut self, config: &AppConfig) -> Result<SaveOutcome, Box<dyn std::error::Error>> {
+        let Some(path) = self.path.clone() else {
+            return Ok(SaveOutcome::Unchanged);
+        };
+
+        let current = match fs::read_to_string(&path) {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        let changed_elsewhere = current != self.disk_content;
+
+        let settings = toml::Table::try_from(config)?;
+        let content = match &current {
+            Some(current) => {
+                let mut document = current.parse::<DocumentMut>().map_err(|e| {
+                    format!("{} has errors, not saving: {}", path.display(), e.message())
+                })?;
+                for key in &self.retired {
+                    remove_key(document.as_table_mut(), key);
+                }
+                update_table(document.as_table_mut(), &settings, &self.baseline)?;
+                document.to_string()
+            }
+            // New or deleted file: write everything
+            None => toml::to_string_pretty(config)?,
+        };
+
+        let outcome = if current.as_deref() == Some(content.as_str()) {
+            SaveOutcome::Unchanged
+        } else {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            write_atomic(&path, &content)?;
+            if changed_elsewhere && current.is_some() {
+                SaveOutcome::Merged
+            } else {
+                SaveOutcome::Saved
+            }
+        };
+
+        self.baseline = settings;
+        self.disk_content = Some(content);
+        self.retired.clear();
+        Ok(outcome)
+    }
+}
+
+/// Writes to a temporary file beside `path` and renames it into place, so readers
+/// (and a crash) never see a half-written file
+pub fn write_atomic(path: &Path, content: &str) -> io::Result<()> {
+    // Write through symlinks (e.g. dotfile managers) instead of replacing them
+    let target = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let file_name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let temp = target.with_file_name(format!(".{file_name}.{}.tmp", std::process::id()));
+
+    let result = (|| {
+        let mut file = File::create(&temp)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&temp, &target)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Sets every key of `settings` that differs from `baseline`; keys we don't know about are left alone
+fn update_table(
+    table: &mut Table,
+    settings: &toml::Table,
+    baseline: &toml::Table,
+) -> Result<(), toml_edit::TomlError> {
+    for (key, value) in settings {
+        let old = baseline.get(key);
+        if old == Some(value) && table.contains_key(key) {
+            continue;
+        }
+
+        match value {
+            toml::Value::Table(settings) => {
+                if !table.get(key).is_some_and(Item::is_table) {
+                    table.insert(key, Item::Table(Table::new()));
+                }
+                let old = old
+                    .and_then(toml::Value::as_table)
+                    .cloned()
+                    .unwrap_or_default();
+                update_table(table[key].as_table_mut().unwrap(), settings, &old)?;
+            }
+            // Lists of tables such as bookmarks are written as [[key]] sections
+            toml::Value::Array(elements)
+                if !elements.is_empty() && elements.iter().all(toml::Value::is_table) =>
+            {
+                let old_tables: Vec<Table> = table
+                    .get(key)
+                    .and_then(Item::as_array_of_tables)
+                    .map(|tables| tables.iter().cloned().collect())
+                    .unwrap_or_default();
+                let mut tables = ArrayOfTables::new();
+                for element in elements.iter().filter_map(toml::Value::as_table) {
+                    // Entries that didn't change keep their formatting and comments
+                    let unchanged = old_tables.iter().find(|old| {
+                        old.to_string().parse::<toml::Table>().ok().as_ref() == Some(element)
+                    });
+                    let section = match unchanged {
+                        Some(old) => old.clone(),
+                        None => {
+                            let mut section = Table::new();
+                            update_table(&mut section, element, &toml::Table::new())?;
+                            section
+                        }
+                    };
+                    tables.push(section);
+                }
+                table.insert(key, Item::ArrayOfTables(tables));
+            }
+            _ => {
+                let mut new_value: Value = value.to_string().parse()?;
+                new_value.decor_mut().clear();
+                match table.get_mut(key).and_then(Item::as_value_mut) {
+                    Some(existing) => replace_value(existing, new_value),
+                    None => {
+                        table.insert(key, Item::Value(new_value));
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Removes the key at `path`, e.g. ["time_config", "work_hours_start"]
+fn remove_key(table: &mut Table, path: &[String]) {
+    match path {
+        [key] => {
+            table.remove(key);
+        }
+        [key, rest @ ..] => {
+            if let Some(inner) = table.get_mut(key).and_then(Item::as_table_mut) {
+                remove_key(inner, rest);
+            }
+        }
+        [] => {}
+    }
+}
+
+/// Swaps in a new value, keeping the old one's surrounding whitespace and comments
+fn replace_value(existing: &mut Value, mut new_value: Value) {
+    let decor = existing.decor().clone();
+    if let (Value::Array(old), Value::Array(new)) = (&*existing, &mut new_value) {
+        // Keep each element's own formatting (and comments) where the element survives
+        let old_elements: Vec<(String, toml_edit::Decor)> = old
+            .iter()
+            .map(|element| (bare(element), element.decor().clone()))
+            .collect();
+        // New elements go on their own line if the old ones did, minus any comments
+        let spacing = old
+            .iter()
+            .last()
+            .and_then(|element| element.decor().prefix()?.as_str())
+            .map(|prefix| match prefix.rfind('\n') {
+                Some(newline) => prefix[newline..].to_string(),
+                None => prefix.to_string(),
+            });
+        for element in new.iter_mut() {
+            let text = bare(element);
+            match old_elements.iter().find(|(old, _)| *old == text) {
+                Some((_, decor)) => *element.decor_mut() = decor.clone(),
+                None => {
+                    if let Some(spacing) = &spacing {
+                        element.decor_mut().set_prefix(spacing.as_str());
+                    }
+                }
+            }
+        }
+        new.set_trailing(old.trailing().clone());
+        new.set_trailing_comma(old.trailing_comma());
+    }
+    *existing = new_value;
+    *existing.decor_mut() = decor;
+}
+
+/// A value's text without its surrounding whitespace and comments
+fn bare(value: &Value) -> String {
+    let mut value = value.clone();
+    value.decor_mut().clear();
+    value.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bookmark::Bookmark;
+    use crate::config::{ClockTime, ColorTheme};
+    use chrono::{DateTime, Utc};
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("alltz-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("config.toml")
+    }
+
+    #[test]
+    fn test_save_preserves_comments_and_unknown_keys() {
+        let path = temp_path("preserve");
+        let original = r#"# My alltz setup
+version = 3
+zones = [
+    # Office
+    "Tokyo",
+    # Home
+    "London",
+]
+selected_zone_index = 0
+display_format = "TwentyFourHour"
+timezone_display_mode = "Short"
+color_theme = "Default" # calm
+show_date = false
+future_option = "kept"
+
+[time_config]
+work_hours = ["09:00-17:00"] # team standup
+awake_hours = ["07:00-23:00"]
+"#;
+        fs::write(&path, original).unwrap();
+        let loaded = AppConfig::parse(original, &path);
+        let mut file = loaded.file;
+
+        let mut config = loaded.config;
+        config.color_theme = ColorTheme::Ocean;
+        config.time_config.work_hours[0].end = ClockTime::new(18, 0);
+        config.zones.push(crate::config::ZoneConfigCompat::Simple(
+            "Berlin".to_string(),
+        ));
+        assert_eq!(file.save(&config).unwrap(), SaveOutcome::Saved);
+
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.starts_with("# My alltz setup\n"));
+        assert!(saved.contains("color_theme = \"Ocean\" # calm"));
+        assert!(saved.contains("zones = [\n    # Office\n    \"Tokyo\",\n    # Home\n    \"London\",\n    \"Berlin\",\n]"));
+        assert!(saved.contains("future_option = \"kept\""));
+        assert!(saved.contains("work_hours = [\"09:00-18:00\"] # team standup"));
+
+        // Nothing changed: nothing written
+        assert_eq!(file.save(&config).unwrap(), SaveOutcome::Unchanged);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_save_merges_changes_from_other_instances() {
+        let path = temp_path("merge");
+        let mut file = ConfigFile::new(Some(path.clone()));
+        let config = AppConfig::default();
+        assert_eq!(file.save(&config).unwrap(), SaveOutcome::Saved);
+        assert!(AppConfig::parse(&fs::read_to_string(&path).unwrap(), &path)
+            .issues
+            .is_empty());
+
+        // Another instance switches to 12-hour time while we change the theme
+        let other = fs::read_to_string(&path)
+            .unwrap()
+            .replace("TwentyFourHour", "TwelveHour");
+        fs::write(&path, other).unwrap();
+        let config = AppConfig {
+            color_theme: ColorTheme::Forest,
+            ..config
+        };
+        assert_eq!(file.save(&config).unwrap(), SaveOutcome::Merged);
+
+        let merged = AppConfig::parse(&fs::read_to_string(&path).unwrap(), &path).config;
+        assert_eq!(merged.color_theme, ColorTheme::Forest);
+        assert_eq!(merged.display_format, crate::app::TimeFormat::TwelveHour);
+
+        // A file someone left half-edited is not overwritten
+        fs::write(&path, "zones = [\"Tokyo\"\n").unwrap();
+        assert!(file.save(&AppConfig::default()).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "zones = [\"Tokyo\"\n");
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_changed_on_disk() {
+        let path = temp_path("changed");
+        let mut file = ConfigFile::new(Some(path.clone()));
+        assert_eq!(file.changed_on_disk(), None);
+
+        file.save(&AppConfig::default()).unwrap();
+        assert_eq!(file.changed_on_disk(), None);
+
+        fs::write(&path, "zones = [\"Tokyo\"\n").unwrap();
+        assert_eq!(
+            file.changed_on_disk().as_deref(),
+            Some("zones = [\"Tokyo\"\n")
+        );
+        file.acknowledge("zones = [\"Tokyo\"\n".to_string());
+        assert_eq!(file.changed_on_disk(), None);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_save_removes_migrated_keys() {
+        let path = temp_path("migrated");
+        let original = r#"version = 1
+zones = ["London"]
+selected_zone_index = 0
+display_format = "TwentyFourHour"
+timezone_display_mode = "Short"
+color_theme = "Default"
+show_date = false
+future_option = "kept"
+
+[time_config]
+work_hours_start = 9 # team standup
+work_hours_end = 17
+awake_hours_start = 7
+awake_hours_end = 23
+"#;
+        fs::write(&path, original).unwrap();
+        let loaded = AppConfig::parse(original, &path);
+        let mut file = loaded.file;
+
+        let mut config = loaded.config;
+        config.show_date = true;
+        file.save(&config).unwrap();
+
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("version = 3"));
+        assert!(saved.contains("work_hours = [\"09:00-17:00\"]"));
+        assert!(saved.contains("awake_hours = [\"07:00-23:00\"]"));
+        assert!(!saved.contains("work_hours_start"));
+        assert!(!saved.contains("awake_hours_end"));
+        assert!(saved.contains("future_option = \"kept\""));
+        assert_eq!(
+            AppConfig::parse(&saved, &path).config.time_config,
+            config.time_config
+        );
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_save_writes_lists_of_tables_as_sections() {
+        let path = temp_path("sections");
+        let original = r#"version = 3
+zones = ["London"]
+selected_zone_index = 0
+display_format = "TwentyFourHour"
+timezone_display_mode = "Short"
+color_theme = "Default"
+show_date = false
+
+[time_config]
+work_hours = ["09:00-17:00"]
+awake_hours = ["07:00-23:00"]
+
+# Team rituals
+[[bookmarks]]
+name = "Weekly sync" # keep
+time = "09:00:00"
+zone = "London"
+days = ["Tue"]
+"#;
+        fs::write(&path, original).unwrap();
+        let loaded = AppConfig::parse(original, &path);
+        let mut file = loaded.file;
+
+        let mut config = loaded.config;
+        let cutover = DateTime::parse_from_rfc3339("2025-11-03T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        config
+            .bookmarks
+            .push(Bookmark::once("Cutover".to_string(), cutover));
+        file.save(&config).unwrap();
+
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("# Team rituals\n[[bookmarks]]\nname = \"Weekly sync\" # keep\n"));
+        assert!(
+            saved.contains("[[bookmarks]]\nat = \"2025-11-03T00:00:00Z\"\nname = \"Cutover\"\n")
+        );
+        let reloaded = AppConfig::parse(&saved, &path).config;
+        assert_eq!(reloaded.bookmarks, config.bookmarks);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_save_keeps_skipped_reminders() {
+        let path = temp_path("reminders");
+        let original = r#"zones = ["London"]
+reminders = ["every day 09:00 in Tokyo: hello", "every Funday 10:00 in Tokyo: nap"]
+"#;
+        fs::write(&path, original).unwrap();
+        let loaded = AppConfig::parse(original, &path);
+        let mut file = loaded.file;
+
+        // Reminders the app couldn't read aren't dropped by an unrelated save
+        let mut config = loaded.config;
+        config.show_date = true;
+        file.save(&config).unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("every Funday 10:00 in Tokyo: nap"));
+        assert!(saved.contains("show_date = true"));
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,121 @@
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+
+use crate::config::default_true;
+use crate::time::{resolve_local, TimeZoneManager};
+
+/// A named moment to count down to, e.g. "Launch at 09:00 Tokyo on Nov 3"
+///
+/// Either a fixed instant (`at`), or a wall-clock `local` time in a `zone`, which keeps
+/// meaning "09:00 in Tokyo" even if the zone's rules change before it arrives.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Countdown {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local: Option<NaiveDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone: Option<String>, // City name or IANA zone
+    #[serde(default = "default_true")]
+    pub bell: bool, // Ring the terminal bell when it expires
+}
+
+impl Countdown {
+    pub fn in_zone(name: String, local: NaiveDateTime, zone: String) -> Self {
+        Self {
+            name,
+            at: None,
+            local: Some(local),
+            zone: Some(zone),
+            bell: true,
+        }
+    }
+
+    /// When the countdown expires, or None if its zone is unknown
+    pub fn instant(&self) -> Option<DateTime<Utc>> {
+        if self.at.is_some() {
+            return self.at;
+        }
+        let tz = TimeZoneManager::resolve_zone(self.zone.as_deref()?)?;
+        resolve_local(tz, self.local?)
+    }
+}
+
+/// Time left as "2d 03:14:05", or "03:14:05" under a day
+pub fn format_remaining(remaining: Duration) -> String {
+    let seconds = remaining.num_seconds().max(0);
+    let (days, rest) = (seconds / 86_400, seconds % 86_400);
+    let clock = format!(
+        "{:02}:{:02}:{:02}",
+        rest / 3600,
+        rest % 3600 / 60,
+        rest % 60
+    );
+    if days > 0 {
+        format!("{days}d {clock}")
+    } else {
+        clock
+    }
+}
+
+/// Rings the terminal bell, which most terminals turn into an alert or notification
+pub fn ring_bell() -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(b"\x07")?;
+    stdout.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_countdown_instant() {
+        let launch = Countdown::in_zone(
+            "Launch".to_string(),
+            NaiveDateTime::parse_from_str("2025-11-03 09:00", "%Y-%m-%d %H:%M").unwrap(),
+            "Tokyo".to_string(),
+        );
+        assert_eq!(
+            launch.instant(),
+            Some(
+                DateTime::parse_from_rfc3339("2025-11-03T00:00:00Z")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )
+        );
+
+        // 02:30 doesn't exist in New York on the night the clocks go forward
+        let skipped = Countdown::in_zone(
+            "Skipped".to_string(),
+            NaiveDateTime::parse_from_str("2025-03-09 02:30", "%Y-%m-%d %H:%M").unwrap(),
+            "America/New_York".to_string(),
+        );
+        assert_eq!(
+            skipped.instant(),
+            Some(
+                DateTime::parse_from_rfc3339("2025-03-09T07:30:00Z")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )
+        );
+
+        let unknown = Countdown {
+            zone: Some("Atlantis".to_string()),
+            ..launch
+        };
+        assert_eq!(unknown.instant(), None);
+    }
+
+    #[test]
+    fn test_format_remaining() {
+        assert_eq!(format_remaining(Duration::seconds(59)), "00:00:59");
+        assert_eq!(
+            format_remaining(Duration::days(2) + Duration::seconds(3 * 3600 + 14 * 60 + 5)),
+            "2d 03:14:05"
+        );
+        assert_eq!(format_remaining(Duration::seconds(-5)), "00:00:00");
+    }
+}
@@ -4,8 +4,19 @@ extern crate rust_i18n;
 // Load translations from locales directory
 i18n!("locales");
 
+mod annotate;
 mod app;
+mod bookmark;
+mod calendar;
+mod clipboard;
+mod command;
 mod config;
+mod config_file;
+mod countdown;
+mod notify;
+mod reminder;
+mod solar;
+mod table;
 mod time;
 mod ui;
 
@@ -50,6 +61,18 @@ struct Cli {
     /// Start with a specific color theme
     #[arg(long, value_parser = parse_theme)]
     theme: Option<config::ColorTheme>,
+
+    /// Overlay events from a local .ics calendar file (can be repeated)
+    #[arg(long = "ics", value_name = "PATH")]
+    ics: Vec<std::path::PathBuf>,
+
+    /// Use this config file instead of $ALLTZ_CONFIG or ~/.config/alltz/config.toml
+    #[arg(long, value_name = "PATH", global = true)]
+    config: Option<std::path::PathBuf>,
+
+    /// Never write settings changes back to the config file
+    #[arg(long)]
+    no_save: bool,
 }
 
 #[derive(Subcommand)]
@@ -71,19 +94,99 @@ enum Commands {
         /// City name to get information for
         city: String,
     },
+
+    /// Write a meeting slot to an .ics invite and print it across your configured zones
+    Invite {
+        /// Start time as "YYYY-MM-DD HH:MM" or "HH:MM" (today)
+        #[arg(long)]
+        at: String,
+
+        /// City whose local time --at is given in (defaults to your local time)
+        #[arg(long)]
+        zone: Option<String>,
+
+        /// Meeting length in minutes
+        #[arg(long, default_value_t = 30)]
+        duration: u32,
+
+        /// Meeting title
+        #[arg(long, default_value = "Meeting")]
+        title: String,
+
+        /// Where to write the invite (defaults to alltz-<date>-<time>.ics)
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+
+    /// Show sunrise, sunset, twilight and golden hour for a city
+    Sun {
+        /// City name
+        city: String,
+
+        /// First date as YYYY-MM-DD (defaults to today in that city)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Last date as YYYY-MM-DD (defaults to --from)
+        #[arg(long, conflicts_with = "days")]
+        to: Option<String>,
+
+        /// Number of days to show, starting at --from
+        #[arg(long)]
+        days: Option<u32>,
+
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print an hour-by-hour conversion table for two or three cities
+    Table {
+        /// Cities to compare; the day and its hours follow the first
+        #[arg(required = true, num_args = 2..=3)]
+        cities: Vec<String>,
+
+        /// Date as YYYY-MM-DD (defaults to today in the first city)
+        #[arg(long)]
+        date: Option<String>,
+    },
+
+    /// Annotate timestamps in logs read from stdin with their time in other zones
+    Annotate {
+        /// City to convert to (defaults to all configured zones)
+        #[arg(long)]
+        zone: Option<String>,
+
+        /// Replace timestamps instead of appending the converted time
+        #[arg(long)]
+        replace: bool,
+    },
+
+    /// Show, edit or reset the config file
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print where the config file is
+    Path,
+
+    /// Print the config file, or the defaults if there isn't one yet
+    Show,
+
+    /// Open the config file in $VISUAL or $EDITOR, creating it first if needed
+    Edit,
+
+    /// Replace the config file with the defaults, keeping a backup of the old one
+    Reset,
 }
 
 /// Parse theme name from CLI argument into ColorTheme enum
 fn parse_theme(s: &str) -> Result<config::ColorTheme, String> {
-    match s.to_lowercase().as_str() {
-        "default" => Ok(config::ColorTheme::Default),
-        "ocean" => Ok(config::ColorTheme::Ocean),
-        "forest" => Ok(config::ColorTheme::Forest),
-        "sunset" => Ok(config::ColorTheme::Sunset),
-        "cyberpunk" => Ok(config::ColorTheme::Cyberpunk),
-        "monochrome" => Ok(config::ColorTheme::Monochrome),
-        _ => Err(t!("cli.unknown_theme_error", s = s).to_string()),
-    }
+    config::ColorTheme::from_name(s).ok_or_else(|| t!("cli.unknown_theme_error", s = s).to_string())
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -92,6 +195,10 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     let cli = Cli::parse();
 
+    if let Some(path) = cli.config.clone() {
+        config::AppConfig::set_config_path(path);
+    }
+
     if let Some(command) = cli.command {
         return handle_command(command);
     }
@@ -163,6 +270,231 @@ fn run_app<B: ratatui::backend::Backend>(
                             KeyCode::Esc => Some(Message::CancelRename),
                             _ => None,
                         }
+                    } else if app.yanking {
+                        match key.code {
+                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                Some(Message::Quit)
+                            }
+                            KeyCode::Char('i') => Some(Message::Yank(app::YankFormat::IsoUtc)),
+                            KeyCode::Char('l') => {
+                                Some(Message::Yank(app::YankFormat::SelectedLocal))
+                            }
+                            KeyCode::Char('u') => {
+                                Some(Message::Yank(app::YankFormat::UnixTimestamp))
+                            }
+                            KeyCode::Char('z') | KeyCode::Char('y') => {
+                                Some(Message::Yank(app::YankFormat::MultiZone))
+                            }
+                            _ => Some(Message::CancelYank),
+                        }
+                    } else if app.exporting {
+                        // Special input handling for export modal
+                        let current_input = match app.export_field {
+                            app::ExportField::Title => &app.export_title_input,
+                            app::ExportField::Duration => &app.export_duration_input,
+                        };
+                        match key.code {
+                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                Some(Message::Quit)
+                            }
+                            KeyCode::Char(c) => {
+                                let mut input = current_input.clone();
+                                input.push(c);
+                                Some(Message::UpdateExportInput(input))
+                            }
+                            KeyCode::Backspace => {
+                                let mut input = current_input.clone();
+                                input.pop();
+                                Some(Message::UpdateExportInput(input))
+                            }
+                            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                                Some(Message::SwitchExportField)
+                            }
+                            KeyCode::Enter => Some(Message::ConfirmExport),
+                            KeyCode::Esc => Some(Message::CancelExport),
+                            _ => None,
+                        }
+                    } else if app.adding_bookmark {
+                        match key.code {
+                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                Some(Message::Quit)
+                            }
+                            KeyCode::Char(c) => {
+                                let mut input = app.bookmark_name_input.clone();
+                                input.push(c);
+                                Some(Message::UpdateBookmarkInput(input))
+                            }
+                            KeyCode::Backspace => {
+                                let mut input = app.bookmark_name_input.clone();
+                                input.pop();
+                                Some(Message::UpdateBookmarkInput(input))
+                            }
+                            KeyCode::Tab | KeyCode::BackTab => Some(Message::CycleBookmarkRepeat),
+                            KeyCode::Enter => Some(Message::ConfirmAddBookmark),
+                            KeyCode::Esc => Some(Message::CancelAddBookmark),
+                            _ => None,
+                        }
+                    } else if app.palette_open {
+                        match key.code {
+                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                Some(Message::Quit)
+                            }
+                            KeyCode::Char(c) => {
+                                let mut input = app.palette_input.clone();
+                                input.push(c);
+                                Some(Message::UpdatePaletteInput(input))
+                            }
+                            KeyCode::Backspace if app.palette_input.is_empty() => {
+                                Some(Message::CancelPalette)
+                            }
+                            KeyCode::Backspace => {
+                                let mut input = app.palette_input.clone();
+                                input.pop();
+                                Some(Message::UpdatePaletteInput(input))
+                            }
+                            KeyCode::Up => Some(Message::NavigatePalette(Direction::Up)),
+                            KeyCode::Down => Some(Message::NavigatePalette(Direction::Down)),
+                            KeyCode::Tab => Some(Message::CompletePalette),
+                            KeyCode::Enter => Some(Message::RunPalette),
+                            KeyCode::Esc => Some(Message::CancelPalette),
+                            _ => None,
+                        }
+                    } else if app.filtering_zones {
+                        match key.code {
+                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                Some(Message::Quit)
+                            }
+                            KeyCode::Char(c) => {
+                                let mut filter = app.zone_filter.clone();
+                                filter.push(c);
+                                Some(Message::UpdateZoneFilter(filter))
+                            }
+                            KeyCode::Backspace => {
+                                let mut filter = app.zone_filter.clone();
+                                filter.pop();
+                                Some(Message::UpdateZoneFilter(filter))
+                            }
+                            KeyCode::Enter => Some(Message::ConfirmZoneFilter),
+                            KeyCode::Esc => Some(Message::ClearZoneFilter),
+                            _ => None,
+                        }
+                    } else if app.show_settings {
+                        match key.code {
+                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                Some(Message::Quit)
+                            }
+                            KeyCode::Up | KeyCode::Char('k') => {
+                                Some(Message::NavigateSettings(Direction::Up))
+                            }
+                            KeyCode::Down | KeyCode::Char('j') => {
+                                Some(Message::NavigateSettings(Direction::Down))
+                            }
+                            KeyCode::Tab | KeyCode::BackTab => Some(Message::SwitchSettingsField),
+                            KeyCode::Left | KeyCode::Char('h') => Some(Message::AdjustSetting(-15)),
+                            KeyCode::Right | KeyCode::Char('l') => Some(Message::AdjustSetting(15)),
+                            KeyCode::Char('H') => Some(Message::AdjustSetting(-1)),
+                            KeyCode::Char('L') => Some(Message::AdjustSetting(1)),
+                            KeyCode::Char('[') => Some(Message::AdjustSetting(-60)),
+                            KeyCode::Char(']') => Some(Message::AdjustSetting(60)),
+                            KeyCode::Char('a') => {
+                                Some(Message::AddSettingsRange(config::TimeActivity::Work))
+                            }
+                            KeyCode::Char('A') => {
+                                Some(Message::AddSettingsRange(config::TimeActivity::Awake))
+                            }
+                            KeyCode::Char('d') | KeyCode::Delete => {
+                                Some(Message::DeleteSettingsRange)
+                            }
+                            KeyCode::Enter => Some(Message::SaveSettings),
+                            KeyCode::Esc | KeyCode::Char('q') => Some(Message::CancelSettings),
+                            _ => None,
+                        }
+                    } else if app.show_table {
+                        match key.code {
+                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                Some(Message::Quit)
+                            }
+                            KeyCode::Up | KeyCode::Char('k') => {
+                                Some(Message::NavigateTable(Direction::Up))
+                            }
+                            KeyCode::Down | KeyCode::Char('j') => {
+                                Some(Message::NavigateTable(Direction::Down))
+                            }
+                            KeyCode::Left | KeyCode::Char('h') => {
+                                Some(Message::NavigateTable(Direction::Left))
+                            }
+                            KeyCode::Right | KeyCode::Char('l') => {
+                                Some(Message::NavigateTable(Direction::Right))
+                            }
+                            KeyCode::Enter => Some(Message::JumpToTableRow),
+                            KeyCode::Esc | KeyCode::Char('T') | KeyCode::Char('q') => {
+                                Some(Message::ToggleTable)
+                            }
+                            _ => None,
+                        }
+                    } else if app.show_bookmarks {
+                        match key.code {
+                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                Some(Message::Quit)
+                            }
+                            KeyCode::Up | KeyCode::Char('k') => {
+                                Some(Message::NavigateBookmarks(Direction::Up))
+                            }
+                            KeyCode::Down | KeyCode::Char('j') => {
+                                Some(Message::NavigateBookmarks(Direction::Down))
+                            }
+                            KeyCode::Enter => Some(Message::JumpToBookmark),
+                            KeyCode::Char('d') | KeyCode::Delete => Some(Message::DeleteBookmark),
+                            KeyCode::Esc | KeyCode::Char('b') | KeyCode::Char('q') => {
+                                Some(Message::ToggleBookmarks)
+                            }
+                            _ => None,
+                        }
+                    } else if app.adding_countdown {
+                        let current_input = match app.countdown_field {
+                            app::CountdownField::Name => &app.countdown_name_input,
+                            app::CountdownField::When => &app.countdown_when_input,
+                        };
+                        match key.code {
+                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                Some(Message::Quit)
+                            }
+                            KeyCode::Char(c) => {
+                                let mut input = current_input.clone();
+                                input.push(c);
+                                Some(Message::UpdateCountdownInput(input))
+                            }
+                            KeyCode::Backspace => {
+                                let mut input = current_input.clone();
+                                input.pop();
+                                Some(Message::UpdateCountdownInput(input))
+                            }
+                            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                                Some(Message::SwitchCountdownField)
+                            }
+                            KeyCode::Enter => Some(Message::ConfirmAddCountdown),
+                            KeyCode::Esc => Some(Message::CancelAddCountdown),
+                            _ => None,
+                        }
+                    } else if app.show_countdowns {
+                        match key.code {
+                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                Some(Message::Quit)
+                            }
+                            KeyCode::Up | KeyCode::Char('k') => {
+                                Some(Message::NavigateCountdowns(Direction::Up))
+                            }
+                            KeyCode::Down | KeyCode::Char('j') => {
+                                Some(Message::NavigateCountdowns(Direction::Down))
+                            }
+                            KeyCode::Enter => Some(Message::JumpToCountdown),
+                            KeyCode::Char('a') => Some(Message::StartAddCountdown),
+                            KeyCode::Char('d') | KeyCode::Delete => Some(Message::DeleteCountdown),
+                            KeyCode::Esc | KeyCode::Char('o') | KeyCode::Char('q') => {
+                                Some(Message::ToggleCountdowns)
+                            }
+                            _ => None,
+                        }
                     } else if app.adding_zone {
                         // Special input handling for add zone modal
                         match key.code {
@@ -204,16 +536,66 @@ fn run_app<B: ratatui::backend::Backend>(
                                 Some(Message::Quit)
                             }
                             KeyCode::Char('?') => Some(Message::ToggleHelp),
+                            KeyCode::Esc if !app.due_reminders.is_empty() => {
+                                Some(Message::DismissReminders)
+                            }
+                            KeyCode::Esc if !app.zone_filter.is_empty() => {
+                                Some(Message::ClearZoneFilter)
+                            }
+                            KeyCode::Esc if app.show_week_grid => Some(Message::ToggleWeekGrid),
+                            KeyCode::Esc => Some(Message::DismissConfigIssues),
+                            KeyCode::Char('w') => Some(Message::ToggleWeekGrid),
+                            KeyCode::Char('T') => Some(Message::ToggleTable),
+                            KeyCode::Char('W') => Some(Message::OpenSettings),
                             KeyCode::Char('a') => Some(Message::StartAddZone),
+                            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                Some(Message::Redo)
+                            }
                             KeyCode::Char('r') => Some(Message::RemoveCurrentZone),
+                            KeyCode::Char('u') => Some(Message::Undo),
                             KeyCode::Char('e') => Some(Message::StartRenameZone),
                             KeyCode::Char('E') => Some(Message::ClearCustomName),
                             KeyCode::Char('m') => Some(Message::ToggleTimeFormat),
+                            // n/N step through filter matches while a filter is active
+                            KeyCode::Char('n') if !app.zone_filter.is_empty() => {
+                                Some(Message::NextFilterMatch(Direction::Down))
+                            }
+                            KeyCode::Char('N') if !app.zone_filter.is_empty() => {
+                                Some(Message::NextFilterMatch(Direction::Up))
+                            }
                             KeyCode::Char('n') => Some(Message::ToggleTimezoneDisplayMode),
+                            KeyCode::Char('/') => Some(Message::StartZoneFilter),
+                            KeyCode::Char(':') => Some(Message::StartPalette),
                             KeyCode::Char('d') => Some(Message::ToggleDate),
                             KeyCode::Char('s') => Some(Message::ToggleSunTimes),
                             KeyCode::Char('c') => Some(Message::CycleColorTheme),
+                            KeyCode::Char('x') => Some(Message::StartExport),
+                            KeyCode::Char('y') => Some(Message::StartYank),
+                            KeyCode::Char('b') => Some(Message::ToggleBookmarks),
+                            KeyCode::Char('B') => Some(Message::StartAddBookmark),
+                            KeyCode::Char('o') => Some(Message::ToggleCountdowns),
+                            KeyCode::Char('v') => Some(Message::CycleEventOverlay),
                             KeyCode::Char('t') => Some(Message::ResetToNow),
+                            KeyCode::Char('+') | KeyCode::Char('=') => Some(Message::ZoomIn),
+                            KeyCode::Char('-') | KeyCode::Char('_') => Some(Message::ZoomOut),
+                            KeyCode::Char('0') => Some(Message::ResetZoom),
+                            KeyCode::Char('R') => Some(Message::CycleRulerMode),
+                            KeyCode::Char('f') => Some(Message::CycleRelativeOffsets),
+                            KeyCode::Char('i') => Some(Message::ToggleInfoPanel),
+                            KeyCode::Char('S') => Some(Message::CycleShadingMode),
+                            // In the week grid the arrows move the cursor cell
+                            KeyCode::Char('h') | KeyCode::Left if app.show_week_grid => {
+                                Some(Message::MoveWeekGridCursor(Direction::Left))
+                            }
+                            KeyCode::Char('l') | KeyCode::Right if app.show_week_grid => {
+                                Some(Message::MoveWeekGridCursor(Direction::Right))
+                            }
+                            KeyCode::Char('k') | KeyCode::Up if app.show_week_grid => {
+                                Some(Message::MoveWeekGridCursor(Direction::Up))
+                            }
+                            KeyCode::Char('j') | KeyCode::Down if app.show_week_grid => {
+                                Some(Message::MoveWeekGridCursor(Direction::Down))
+                            }
                             KeyCode::Char('h') | KeyCode::Left => {
                                 if key.modifiers.contains(KeyModifiers::SHIFT) {
                                     Some(Message::ScrubTimelineWithShift(Direction::Left))
@@ -254,6 +636,11 @@ fn run_app<B: ratatui::backend::Backend>(
                         if app.should_quit {
                             return Ok(());
                         }
+                        if let Some(text) = app.pending_clipboard.take() {
+                            if let Err(e) = clipboard::copy(&text) {
+                                app.set_status(format!("Could not copy to clipboard: {e}"));
+                            }
+                        }
                     }
                 }
             }
@@ -263,13 +650,137 @@ fn run_app<B: ratatui::backend::Backend>(
         if last_tick.elapsed() >= TICK_RATE {
             app.update(Message::Tick);
             last_tick = Instant::now();
+            if std::mem::take(&mut app.pending_bell) {
+                let _ = countdown::ring_bell();
+            }
         }
     }
 }
 
+/// Longest date range `alltz sun` will print
+const MAX_SUN_DAYS: i64 = 366;
+
+/// Width of each city's column in `alltz table`
+const TABLE_COLUMN_WIDTH: usize = 16;
+
+/// Print one row per hour of `date` in the first zone for `alltz table`
+fn print_conversion_table(
+    zones: &[time::TimeZone],
+    date: chrono::NaiveDate,
+    time_config: &config::TimeDisplayConfig,
+    use_12_hour: bool,
+) {
+    let heading: String = zones
+        .iter()
+        .map(|zone| format!("{:<TABLE_COLUMN_WIDTH$}", zone.get_city_name()))
+        .collect();
+    println!("{}", heading.trim_end());
+
+    for hour in table::day_hours(zones[0].tz, date) {
+        let row: String = zones
+            .iter()
+            .map(|zone| {
+                let mut cell = table::format_cell(hour, zone.tz, date, use_12_hour);
+                if table::is_work_hour(hour, zone.tz, time_config) {
+                    cell.push('*');
+                }
+                format!("{cell:<TABLE_COLUMN_WIDTH$}")
+            })
+            .collect();
+        println!("{}", row.trim_end());
+    }
+}
+
+/// Print one row per day of solar details for `alltz sun`
+fn print_sun_table(details: &[solar::SolarDetails], use_12_hour: bool) {
+    use chrono_tz::Tz;
+
+    let time_format = if use_12_hour { "%I:%M %p" } else { "%H:%M" };
+    let time = |t: Option<chrono::DateTime<Tz>>| {
+        t.map_or_else(
+            || "--:--".to_string(),
+            |t| t.format(time_format).to_string(),
+        )
+    };
+    let range = |(start, end): (Option<chrono::DateTime<Tz>>, Option<chrono::DateTime<Tz>>)| {
+        if start.is_none() && end.is_none() {
+            // The sun never gets that far below the horizon
+            return "--".to_string();
+        }
+        format!("{}-{}", time(start), time(end))
+    };
+
+    let header: Vec<String> = [
+        "date",
+        "sunrise",
+        "sunset",
+        "noon",
+        "length",
+        "civil",
+        "nautical",
+        "astro",
+        "golden_hour",
+    ]
+    .iter()
+    .map(|column| t!(format!("cli.sun.columns.{column}")).to_string())
+    .collect();
+    let rows: Vec<Vec<String>> = details
+        .iter()
+        .map(|day| {
+            let (sunrise, sunset) = match day.sun {
+                solar::SunTimes::RiseAndSet { sunrise, sunset } => (Some(sunrise), Some(sunset)),
+                _ => (None, None),
+            };
+            let length = day.day_length.num_minutes();
+            let golden_hours = match day.sun {
+                _ if !day.golden_hours.is_empty() => day
+                    .golden_hours
+                    .iter()
+                    .map(|(start, end)| range((Some(*start), Some(*end))))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                solar::SunTimes::RiseAndSet { .. } => String::new(),
+                solar::SunTimes::MidnightSun => t!("cli.sun.midnight_sun").to_string(),
+                solar::SunTimes::PolarNight => t!("cli.sun.polar_night").to_string(),
+                solar::SunTimes::NoLocation => t!("cli.sun.no_location").to_string(),
+            };
+            vec![
+                day.date.to_string(),
+                time(sunrise),
+                time(sunset),
+                day.solar_noon.format(time_format).to_string(),
+                format!("{}h{:02}", length / 60, length % 60),
+                range(day.civil),
+                range(day.nautical),
+                range(day.astronomical),
+                golden_hours,
+            ]
+        })
+        .collect();
+
+    // Columns are as wide as their widest cell, which depends on the time format and locale
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            std::iter::once(&header)
+                .chain(&rows)
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    for row in std::iter::once(&header).chain(&rows) {
+        let line: String = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}  "))
+            .collect();
+        println!("{}", line.trim_end());
+    }
+}
+
 /// Handle CLI subcommands (list, time, zone) and exit without starting TUI
 fn handle_command(command: Commands) -> Result<(), Box<dyn Error>> {
-    use chrono::{Local, Offset, Utc};
+    use chrono::{Local, NaiveDate, Offset, Utc};
     use time::TimeZoneManager;
 
     match command {
@@ -364,11 +875,312 @@ fn handle_command(command: Commands) -> Result<(), Box<dyn Error>> {
 
                 // Simple DST status (just show current offset)
                 println!("{}", t!("cli.zone.dst_status", offset_hours = offset_hours));
+
+                let zone = time::TimeZone::with_source_city(
+                    *tz,
+                    code.clone(),
+                    None,
+                    Some(city_name.clone()),
+                );
+                let sun_line = match zone.get_sunrise_sunset(now) {
+                    solar::SunTimes::RiseAndSet { sunrise, sunset } => t!(
+                        "cli.zone.sun_times",
+                        sunrise = sunrise.format("%H:%M"),
+                        sunset = sunset.format("%H:%M")
+                    ),
+                    solar::SunTimes::MidnightSun => t!("cli.zone.midnight_sun"),
+                    solar::SunTimes::PolarNight => t!("cli.zone.polar_night"),
+                    solar::SunTimes::NoLocation => t!("cli.zone.no_location"),
+                };
+                println!("{sun_line}");
             } else {
                 eprintln!("{}", t!("cli.zone.not_found", city = city));
                 std::process::exit(1);
             }
         }
+
+        Commands::Invite {
+            at,
+            zone,
+            duration,
+            title,
+            output,
+        } => {
+            let tz = match zone {
+                Some(city) => {
+                    let timezones = TimeZoneManager::get_all_available_timezones();
+                    match timezones
+                        .iter()
+                        .find(|(_, name, _, _, _)| name.eq_ignore_ascii_case(&city))
+                    {
+                        Some((tz, _, _, _, _)) => Some(*tz),
+                        None => {
+                            eprintln!("{}", t!("cli.zone.not_found", city = city));
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                None => None,
+            };
+
+            let today = Local::now().date_naive();
+            let Some(start) = time::parse_datetime_in_zone(&at, tz, today) else {
+                eprintln!("{}", t!("cli.invite.invalid_time", at = at));
+                std::process::exit(1);
+            };
+
+            let app = App::from_config(config::AppConfig::load());
+            let use_12_hour = matches!(app.display_format, app::TimeFormat::TwelveHour);
+            let summary = app
+                .timezone_manager
+                .format_multi_zone_summary(start, use_12_hour);
+
+            let invite = calendar::Invite {
+                title,
+                start,
+                duration: chrono::Duration::minutes(duration as i64),
+                description: Some(summary.clone()),
+            };
+            // An explicit --output is overwritten; the default name never replaces a file
+            let path = match output {
+                Some(path) => {
+                    invite.write_to(&path)?;
+                    path
+                }
+                None => invite.write_new(std::path::Path::new("."))?,
+            };
+
+            println!("{summary}");
+            println!("{}", t!("cli.invite.written", path = path.display()));
+        }
+
+        Commands::Sun {
+            city,
+            from,
+            to,
+            days,
+            json,
+        } => {
+            let timezones = TimeZoneManager::get_all_available_timezones();
+            let Some((tz, city_name, code, lat, lon)) = timezones
+                .iter()
+                .find(|(_, name, _, _, _)| name.eq_ignore_ascii_case(&city))
+            else {
+                eprintln!("{}", t!("cli.zone.not_found", city = city));
+                std::process::exit(1);
+            };
+            let zone =
+                time::TimeZone::with_source_city(*tz, code.clone(), None, Some(city_name.clone()));
+
+            let parse_date = |input: &str| match NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+                Ok(date) => date,
+                Err(_) => {
+                    eprintln!("{}", t!("cli.sun.invalid_date", date = input));
+                    std::process::exit(1);
+                }
+            };
+            let first = from
+                .as_deref()
+                .map(parse_date)
+                .unwrap_or_else(|| Utc::now().with_timezone(tz).date_naive());
+            let last = match (to.as_deref(), days) {
+                (Some(to), _) => parse_date(to),
+                (None, Some(days)) => first + chrono::Days::new(days.max(1) as u64 - 1),
+                (None, None) => first,
+            };
+            if last < first || (last - first).num_days() >= MAX_SUN_DAYS {
+                eprintln!("{}", t!("cli.sun.invalid_range", max = MAX_SUN_DAYS));
+                std::process::exit(1);
+            }
+
+            let details: Vec<_> = first
+                .iter_days()
+                .take_while(|date| *date <= last)
+                .filter_map(|date| zone.get_solar_details(date))
+                .collect();
+
+            if json {
+                let output = serde_json::json!({
+                    "city": city_name,
+                    "timezone": tz.to_string(),
+                    "latitude": lat,
+                    "longitude": lon,
+                    "days": details.iter().map(|day| day.to_json()).collect::<Vec<_>>(),
+                });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            } else {
+                println!(
+                    "{}",
+                    t!("cli.sun.header", city_name = city_name, tz = tz.to_string())
+                );
+                println!();
+                let display_format = config::AppConfig::load().display_format;
+                print_sun_table(
+                    &details,
+                    matches!(display_format, app::TimeFormat::TwelveHour),
+                );
+            }
+        }
+
+        Commands::Table { cities, date } => {
+            let app = App::from_config(config::AppConfig::load());
+            // Columns follow the argument order: the first city sets the day and its hours
+            let mut zones: Vec<time::TimeZone> = Vec::new();
+            for city in &cities {
+                let Some(zone) = TimeZoneManager::city_zone(city, None) else {
+                    eprintln!("{}", t!("cli.zone.not_found", city = city));
+                    std::process::exit(1);
+                };
+                if zones.iter().any(|z| z.display_name == zone.display_name) {
+                    eprintln!("{}", t!("cli.table.duplicate_city", city = city));
+                    std::process::exit(1);
+                }
+                zones.push(zone);
+            }
+            let date = match date {
+                Some(date) => match NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
+                    Ok(date) => date,
+                    Err(_) => {
+                        eprintln!("{}", t!("cli.sun.invalid_date", date = date));
+                        std::process::exit(1);
+                    }
+                },
+                None => Utc::now().with_timezone(&zones[0].tz).date_naive(),
+            };
+            let use_12_hour = matches!(app.display_format, app::TimeFormat::TwelveHour);
+
+            println!(
+                "{}",
+                t!(
+                    "cli.table.header",
+                    date = date.format("%a %d %b %Y"),
+                    city_name = zones[0].get_city_name()
+                )
+            );
+            println!();
+            print_conversion_table(&zones, date, &app.time_config, use_12_hour);
+            println!();
+            println!(
+                "{}",
+                t!(
+                    "cli.table.legend",
+                    hours = config::format_ranges(&app.time_config.work_hours)
+                )
+            );
+        }
+
+        Commands::Annotate { zone, replace } => {
+            let app = App::from_config(config::AppConfig::load());
+            let zones = match zone {
+                Some(city) => {
+                    let mut manager = TimeZoneManager::new();
+                    if !manager.add_timezone_by_name(&city) {
+                        eprintln!("{}", t!("cli.zone.not_found", city = city));
+                        std::process::exit(1);
+                    }
+                    manager.zones().to_vec()
+                }
+                None => app.timezone_manager.zones().to_vec(),
+            };
+            let use_12_hour = matches!(app.display_format, app::TimeFormat::TwelveHour);
+
+            let annotator = annotate::Annotator::new(zones, replace, use_12_hour);
+            let stdin = io::stdin();
+            let stdout = io::stdout();
+            // Ignore broken pipe errors (when output is piped to head, etc.)
+            if let Err(e) = annotator.run(stdin.lock(), stdout.lock()) {
+                if e.kind() != io::ErrorKind::BrokenPipe {
+                    return Err(e.into());
+                }
+            }
+        }
+
+        Commands::Config { action } => handle_config_command(action)?,
+    }
+
+    Ok(())
+}
+
+/// Handle `alltz config path|show|edit|reset`
+fn handle_config_command(action: ConfigAction) -> Result<(), Box<dyn Error>> {
+    use config::AppConfig;
+    use std::fs;
+
+    let Some(path) = AppConfig::config_path() else {
+        eprintln!("{}", t!("cli.config.no_path"));
+        std::process::exit(1);
+    };
+    let report_issues = |content: &str| {
+        let issues = AppConfig::parse(content, &path).issues;
+        for issue in &issues {
+            eprintln!("{}", t!("cli.config.issue", issue = issue.to_string()));
+        }
+        issues.is_empty()
+    };
+
+    match action {
+        ConfigAction::Path => println!("{}", path.display()),
+
+        ConfigAction::Show => match fs::read_to_string(&path) {
+            Ok(content) => {
+                print!("{content}");
+                report_issues(&content);
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                eprintln!(
+                    "{}",
+                    t!("cli.config.showing_defaults", path = path.display())
+                );
+                print!("{}", toml::to_string_pretty(&AppConfig::default())?);
+            }
+            Err(e) => return Err(e.into()),
+        },
+
+        ConfigAction::Edit => {
+            if !path.exists() {
+                config_file::ConfigFile::new(Some(path.clone())).save(&AppConfig::default())?;
+            }
+
+            let editor = std::env::var("VISUAL")
+                .or_else(|_| std::env::var("EDITOR"))
+                .ok()
+                .filter(|editor| !editor.trim().is_empty())
+                .unwrap_or_else(|| if cfg!(windows) { "notepad" } else { "vi" }.to_string());
+            // Allow editors with arguments, e.g. "code --wait"
+            let mut words = editor.split_whitespace();
+            let program = words.next().unwrap_or("vi");
+            let status = std::process::Command::new(program)
+                .args(words)
+                .arg(&path)
+                .status();
+            if !status.is_ok_and(|status| status.success()) {
+                eprintln!("{}", t!("cli.config.editor_failed", editor = editor));
+                std::process::exit(1);
+            }
+
+            if report_issues(&fs::read_to_string(&path)?) {
+                println!("{}", t!("cli.config.valid", path = path.display()));
+            }
+        }
+
+        ConfigAction::Reset => {
+            if path.exists() {
+                let mut backup = path.clone().into_os_string();
+                backup.push(".bak");
+                fs::copy(&path, &backup)?;
+                println!(
+                    "{}",
+                    t!(
+                        "cli.config.backed_up",
+                        path = std::path::Path::new(&backup).display()
+                    )
+                );
+            } else if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            config_file::write_atomic(&path, &toml::to_string_pretty(&AppConfig::default())?)?;
+            println!("{}", t!("cli.config.reset", path = path.display()));
+        }
     }
 
     Ok(())
@@ -376,7 +1188,7 @@ fn handle_command(command: Commands) -> Result<(), Box<dyn Error>> {
 
 /// Create App instance with CLI options applied (timezone, theme, format)
 fn create_app_with_options(cli: Cli) -> Result<App, Box<dyn Error>> {
-    let mut app = App::new();
+    let mut app = App::load(cli.no_save, &cli.ics);
 
     if let Some(timezone_name) = cli.timezone {
         let timezones = time::TimeZoneManager::get_all_available_timezones();
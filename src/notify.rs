@@ -0,0 +1,195 @@
+use std::fmt;
+use std::process::{Command, Stdio};
+
+/// Somewhere to send desktop notifications
+pub trait Notifier: fmt::Debug {
+    fn notify(&self, summary: &str, body: &str) -> Result<(), String>;
+}
+
+/// Sends notifications through the freedesktop `org.freedesktop.Notifications` D-Bus interface
+///
+/// Calls go through `gdbus` so alltz needs no D-Bus library. They use the session bus, or
+/// `address` when set (a private bus standing in for the session bus, for example).
+#[derive(Debug, Default)]
+pub struct DbusNotifier {
+    pub address: Option<String>,
+}
+
+impl DbusNotifier {
+    fn command(&self, summary: &str, body: &str) -> Command {
+        let mut command = Command::new("gdbus");
+        command.arg("call");
+        match &self.address {
+            Some(address) => command.args(["--address", address]),
+            None => command.arg("--session"),
+        };
+        command.args([
+            "--dest",
+            "org.freedesktop.Notifications",
+            "--object-path",
+            "/org/freedesktop/Notifications",
+            "--method",
+            "org.freedesktop.Notifications.Notify",
+        ]);
+        // app_name, replaces_id, app_icon, summary, body, actions, hints, expire_timeout
+        command.args([
+            gvariant_string("alltz"),
+            "uint32 0".to_string(),
+            gvariant_string(""),
+            gvariant_string(summary),
+            gvariant_string(body),
+            "@as []".to_string(),
+            "@a{sv} {}".to_string(),
+            "int32 -1".to_string(),
+        ]);
+        command
+    }
+}
+
+impl Notifier for DbusNotifier {
+    fn notify(&self, summary: &str, body: &str) -> Result<(), String> {
+        let mut child = self
+            .command(summary, body)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("could not run gdbus: {e}"))?;
+        // Don't hold up the UI waiting for the notification daemon
+        std::thread::spawn(move || child.wait());
+        Ok(())
+    }
+}
+
+/// Quotes text as a GVariant string literal, the argument format `gdbus call` expects
+fn gvariant_string(text: &str) -> String {
+    format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dbus_notify_command() {
+        let notifier = DbusNotifier {
+            address: Some("unix:path=/tmp/alltz-test-bus".to_string()),
+        };
+        let command = notifier.command("Standup", "It's 09:55 in London");
+        let args: Vec<_> = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+
+        assert_eq!(command.get_program(), "gdbus");
+        assert_eq!(
+            args[..3],
+            ["call", "--address", "unix:path=/tmp/alltz-test-bus"]
+        );
+        assert!(args.contains(&"org.freedesktop.Notifications.Notify".to_string()));
+        assert_eq!(
+            args[args.len() - 8..],
+            [
+                "'alltz'",
+                "uint32 0",
+                "''",
+                "'Standup'",
+                "'It\\'s 09:55 in London'",
+                "@as []",
+                "@a{sv} {}",
+                "int32 -1",
+            ]
+        );
+
+        let session = DbusNotifier::default().command("a", "b");
+        assert_eq!(session.get_args().nth(1).unwrap(), "--session");
+    }
+
+    /// Kills the wrapped process when the test ends, pass or fail
+    struct Reaper(std::process::Child);
+
+    impl Drop for Reaper {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+            let _ = self.0.wait();
+        }
+    }
+
+    #[test]
+    fn test_dbus_notify_on_private_bus() {
+        use std::io::{BufRead, BufReader};
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        // A private bus stands in for the session bus; skip where D-Bus isn't installed
+        let Ok(daemon) = Command::new("dbus-daemon")
+            .args(["--session", "--print-address", "--nofork"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            eprintln!("dbus-daemon not available, skipping");
+            return;
+        };
+        let mut daemon = Reaper(daemon);
+        let mut address = String::new();
+        BufReader::new(daemon.0.stdout.take().unwrap())
+            .read_line(&mut address)
+            .unwrap();
+        let address = address.trim().to_string();
+
+        let Ok(monitor) = Command::new("dbus-monitor")
+            .args([
+                "--address",
+                &address,
+                "interface='org.freedesktop.Notifications'",
+            ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            eprintln!("dbus-monitor not available, skipping");
+            return;
+        };
+        let mut monitor = Reaper(monitor);
+        let (lines, received) = mpsc::channel();
+        let output = monitor.0.stdout.take().unwrap();
+        std::thread::spawn(move || {
+            for line in BufReader::new(output).lines().map_while(Result::ok) {
+                if lines.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        let next_line = || received.recv_timeout(Duration::from_secs(5)).ok();
+
+        // The monitor is listening once the bus has taken its unique name back
+        while !next_line()
+            .expect("monitor never attached")
+            .contains("NameLost")
+        {}
+
+        let notifier = DbusNotifier {
+            address: Some(address),
+        };
+        notifier.notify("Standup", "It's 09:55 in London").unwrap();
+
+        while !next_line()
+            .expect("no Notify call on the bus")
+            .contains("member=Notify")
+        {}
+        let strings: Vec<String> = std::iter::from_fn(next_line)
+            .take_while(|line| !line.contains("array"))
+            .collect();
+        assert_eq!(
+            strings.iter().map(|line| line.trim()).collect::<Vec<_>>(),
+            [
+                "string \"alltz\"",
+                "uint32 0",
+                "string \"\"",
+                "string \"Standup\"",
+                "string \"It's 09:55 in London\"",
+            ]
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use chrono::{DateTime, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::bookmark::local_occurrences;
+use crate::time::TimeZoneManager;
+
+const WEEKDAYS: [Weekday; 5] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+];
+const WEEKEND: [Weekday; 2] = [Weekday::Sat, Weekday::Sun];
+
+/// A message due at a wall-clock time in a zone, written in the config as e.g.
+/// `"every weekday 09:55 in Europe/London: standup"`
+///
+/// Days are `day`, `weekday`, `weekend` or a comma-separated list such as `Mon,Thu`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Reminder {
+    pub days: Vec<Weekday>, // Every day when empty
+    pub time: NaiveTime,
+    pub zone: String, // City name or IANA zone
+    pub message: String,
+}
+
+impl Reminder {
+    /// When the reminder is due within `start..=end`
+    pub fn occurrences_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Vec<DateTime<Utc>> {
+        match TimeZoneManager::resolve_zone(&self.zone) {
+            Some(tz) => local_occurrences(self.time, tz, &self.days, start, end),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl FromStr for Reminder {
+    type Err = String;
+
+    fn from_str(definition: &str) -> Result<Self, Self::Err> {
+        let expected = || {
+            format!(
+                "reminder '{definition}' should look like 'every weekday 09:55 in Europe/London: standup'"
+            )
+        };
+        let rest = definition
+            .trim()
+            .strip_prefix("every ")
+            .ok_or_else(expected)?;
+        let (when, rest) = rest.split_once(" in ").ok_or_else(expected)?;
+        let (zone, message) = rest.split_once(':').ok_or_else(expected)?;
+        let (days, time) = match when.trim().rsplit_once(' ') {
+            Some((days, time)) => (days.trim(), time),
+            None => ("day", when.trim()),
+        };
+
+        let time = NaiveTime::parse_from_str(time, "%H:%M")
+            .map_err(|_| format!("reminder '{definition}' has an invalid time '{time}'"))?;
+        let days = match days {
+            "day" => Vec::new(),
+            "weekday" => WEEKDAYS.to_vec(),
+            "weekend" => WEEKEND.to_vec(),
+            list => list
+                .split(',')
+                .map(|day| {
+                    day.trim()
+                        .parse::<Weekday>()
+                        .map_err(|_| format!("reminder '{definition}' has an unknown day '{day}'"))
+                })
+                .collect::<Result<_, _>>()?,
+        };
+        let zone = zone.trim().to_string();
+        if TimeZoneManager::resolve_zone(&zone).is_none() {
+            return Err(format!(
+                "reminder '{definition}' has an unknown zone '{zone}'"
+            ));
+        }
+
+        Ok(Self {
+            days,
+            time,
+            zone,
+            message: message.trim().to_string(),
+        })
+    }
+}
+
+impl TryFrom<String> for Reminder {
+    type Error = String;
+
+    fn try_from(definition: String) -> Result<Self, Self::Error> {
+        definition.parse()
+    }
+}
+
+impl From<Reminder> for String {
+    fn from(reminder: Reminder) -> Self {
+        reminder.to_string()
+    }
+}
+
+impl fmt::Display for Reminder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let days = if self.days.is_empty() {
+            "day".to_string()
+        } else if self.days == WEEKDAYS {
+            "weekday".to_string()
+        } else if self.days == WEEKEND {
+            "weekend".to_string()
+        } else {
+            self.days
+                .iter()
+                .map(|day| day.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        write!(
+            f,
+            "every {days} {} in {}: {}",
+            self.time.format("%H:%M"),
+            self.zone,
+            self.message
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instant(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_reminder() {
+        let standup: Reminder = "every weekday 09:55 in Europe/London: standup"
+            .parse()
+            .unwrap();
+        assert_eq!(standup.days, WEEKDAYS);
+        assert_eq!(standup.time, NaiveTime::from_hms_opt(9, 55, 0).unwrap());
+        assert_eq!(standup.zone, "Europe/London");
+        assert_eq!(standup.message, "standup");
+        assert_eq!(
+            standup.to_string(),
+            "every weekday 09:55 in Europe/London: standup"
+        );
+
+        let review: Reminder = "every Mon,Thu 16:30 in Tokyo: review: bring notes"
+            .parse()
+            .unwrap();
+        assert_eq!(review.days, vec![Weekday::Mon, Weekday::Thu]);
+        assert_eq!(review.message, "review: bring notes");
+        assert_eq!(
+            "every 07:00 in UTC: coffee"
+                .parse::<Reminder>()
+                .unwrap()
+                .days,
+            Vec::<Weekday>::new()
+        );
+
+        assert!("at 09:55 in London: standup".parse::<Reminder>().is_err());
+        assert!("every 25:00 in London: standup"
+            .parse::<Reminder>()
+            .is_err());
+        assert!("every Funday 09:00 in London: standup"
+            .parse::<Reminder>()
+            .is_err());
+        assert!("every day 09:00 in Atlantis: standup"
+            .parse::<Reminder>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_reminder_follows_wall_clock_across_dst() {
+        let standup: Reminder = "every weekday 09:55 in Europe/London: standup"
+            .parse()
+            .unwrap();
+        // The week the UK clocks go back (Sunday 26 October 2025)
+        let due = standup.occurrences_between(
+            instant("2025-10-24T00:00:00Z"),
+            instant("2025-10-28T00:00:00Z"),
+        );
+        assert_eq!(
+            due,
+            vec![
+                instant("2025-10-24T08:55:00Z"), // Friday, BST
+                instant("2025-10-27T09:55:00Z"), // Monday, GMT
+            ]
+        );
+
+        // 02:30 doesn't exist in New York on the night the clocks go forward
+        let early: Reminder = "every day 02:30 in America/New_York: backup"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            early.occurrences_between(
+                instant("2025-03-09T00:00:00Z"),
+                instant("2025-03-09T12:00:00Z")
+            ),
+            vec![instant("2025-03-09T07:30:00Z")]
+        );
+    }
+}
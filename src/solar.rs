@@ -0,0 +1,473 @@
+use chrono::{DateTime, Duration, NaiveDate, TimeZone as _, Timelike, Utc};
+use chrono_tz::Tz;
+use serde_json::json;
+use sunrise::{Coordinates, DawnType, SolarDay, SolarEvent};
+
+/// Sun elevation (degrees) at which the upper limb touches the horizon, allowing for refraction
+pub const HORIZON_ELEVATION: f64 = -0.833;
+
+/// How light it is outside, from the sun's elevation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SolarPhase {
+    Night,                // Sun below -18°
+    AstronomicalTwilight, // -18° to -12°
+    NauticalTwilight,     // -12° to -6°
+    CivilTwilight,        // -6° to sunrise/sunset
+    Day,
+}
+
+impl SolarPhase {
+    pub fn from_elevation(elevation: f64) -> Self {
+        if elevation >= HORIZON_ELEVATION {
+            SolarPhase::Day
+        } else if elevation >= -6.0 {
+            SolarPhase::CivilTwilight
+        } else if elevation >= -12.0 {
+            SolarPhase::NauticalTwilight
+        } else if elevation >= -18.0 {
+            SolarPhase::AstronomicalTwilight
+        } else {
+            SolarPhase::Night
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SolarPhase::Night => "Night",
+            SolarPhase::AstronomicalTwilight => "Astro",
+            SolarPhase::NauticalTwilight => "Nautical",
+            SolarPhase::CivilTwilight => "Civil",
+            SolarPhase::Day => "Day",
+        }
+    }
+}
+
+/// The sun's elevation above the horizon in degrees, using the NOAA solar position equations
+///
+/// Accurate to well under a degree, which is plenty for shading a terminal cell.
+pub fn solar_elevation(latitude: f64, longitude: f64, instant: DateTime<Utc>) -> f64 {
+    let (declination, equation_of_time) = solar_position(instant);
+
+    let minutes_utc = instant.num_seconds_from_midnight() as f64 / 60.0;
+    let true_solar_minutes = (minutes_utc + equation_of_time + 4.0 * longitude).rem_euclid(1440.0);
+    let hour_angle = (true_solar_minutes / 4.0 - 180.0).to_radians();
+
+    let latitude = latitude.to_radians();
+    let cos_zenith =
+        latitude.sin() * declination.sin() + latitude.cos() * declination.cos() * hour_angle.cos();
+    90.0 - cos_zenith.clamp(-1.0, 1.0).acos().to_degrees()
+}
+
+/// The moment the sun is highest on `date` local to `tz`, which exists even in polar night
+pub fn solar_noon(longitude: f64, date: NaiveDate, tz: Tz) -> DateTime<Tz> {
+    let utc_noon = |utc_date: NaiveDate| {
+        let utc_midnight = Utc.from_utc_datetime(&utc_date.and_time(chrono::NaiveTime::MIN));
+        // Refine once: the equation of time barely moves within a day
+        let estimate = utc_midnight + Duration::minutes((720.0 - 4.0 * longitude) as i64);
+        let (_, equation_of_time) = solar_position(estimate);
+        let noon_minutes = 720.0 - 4.0 * longitude - equation_of_time;
+        utc_midnight + Duration::seconds((noon_minutes * 60.0).round() as i64)
+    };
+
+    // Far from UTC the local day straddles two UTC days, so take the first noon after it
+    // starts; a day the zone skipped entirely (Apia, 2011-12-30) falls back to the UTC day
+    let Some(day_start) = local_day_start(date, tz) else {
+        return utc_noon(date).with_timezone(&tz);
+    };
+    let start_date = day_start.date_naive();
+    (-1..=1)
+        .map(|offset| utc_noon(start_date + Duration::days(offset)))
+        .find(|noon| *noon >= day_start)
+        .unwrap_or_else(|| utc_noon(start_date))
+        .with_timezone(&tz)
+}
+
+/// Sun declination (radians) and equation of time (minutes) at an instant
+fn solar_position(instant: DateTime<Utc>) -> (f64, f64) {
+    let julian_day = instant.timestamp() as f64 / 86400.0 + 2440587.5;
+    let t = (julian_day - 2451545.0) / 36525.0; // Julian centuries since J2000
+
+    // Sun's mean longitude and anomaly, orbit eccentricity
+    let mean_longitude = (280.46646 + t * (36000.76983 + t * 0.0003032)).rem_euclid(360.0);
+    let mean_anomaly = 357.52911 + t * (35999.05029 - 0.0001537 * t);
+    let eccentricity = 0.016708634 - t * (0.000042037 + 0.0000001267 * t);
+
+    let m = mean_anomaly.to_radians();
+    let center = m.sin() * (1.914602 - t * (0.004817 + 0.000014 * t))
+        + (2.0 * m).sin() * (0.019993 - 0.000101 * t)
+        + (3.0 * m).sin() * 0.000289;
+    let omega = (125.04 - 1934.136 * t).to_radians();
+    let apparent_longitude =
+        (mean_longitude + center - 0.00569 - 0.00478 * omega.sin()).to_radians();
+
+    let mean_obliquity =
+        23.0 + (26.0 + (21.448 - t * (46.815 + t * (0.00059 - t * 0.001813))) / 60.0) / 60.0;
+    let obliquity = (mean_obliquity + 0.00256 * omega.cos()).to_radians();
+    let declination = (obliquity.sin() * apparent_longitude.sin()).asin();
+
+    // Equation of time in minutes
+    let y = (obliquity / 2.0).tan().powi(2);
+    let l0 = mean_longitude.to_radians();
+    let equation_of_time = 4.0
+        * (y * (2.0 * l0).sin() - 2.0 * eccentricity * m.sin()
+            + 4.0 * eccentricity * y * m.sin() * (2.0 * l0).cos()
+            - 0.5 * y * y * (4.0 * l0).sin()
+            - 1.25 * eccentricity * eccentricity * (2.0 * m).sin())
+        .to_degrees();
+
+    (declination, equation_of_time)
+}
+
+pub fn solar_phase(latitude: f64, longitude: f64, instant: DateTime<Utc>) -> SolarPhase {
+    SolarPhase::from_elevation(solar_elevation(latitude, longitude, instant))
+}
+
+/// Sunrise and sunset for one local day, or why there aren't any
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SunTimes {
+    RiseAndSet {
+        sunrise: DateTime<Tz>,
+        sunset: DateTime<Tz>,
+    },
+    MidnightSun, // Sun never sets on this day
+    PolarNight,  // Sun never rises on this day
+    NoLocation,  // Zone has no coordinates to compute from
+}
+
+/// The UTC instant `date` starts in `tz`: midnight, or the first valid local time when
+/// DST skips midnight
+fn local_day_start(date: NaiveDate, tz: Tz) -> Option<DateTime<Utc>> {
+    (0..=2).find_map(|hour| {
+        tz.from_local_datetime(&date.and_hms_opt(hour, 0, 0)?)
+            .earliest()
+            .map(|start| start.with_timezone(&Utc))
+    })
+}
+
+/// When `event` happens, if it happens at all
+///
+/// The sunrise crate yields the Unix epoch for events that don't occur (the sun never
+/// reaches that elevation), so only times landing near the requested day are trusted.
+fn event_on_day(
+    solar_day: &SolarDay,
+    event: SolarEvent,
+    day_start: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let time = solar_day.event_time(event);
+    ((time - day_start).num_hours().abs() <= 36).then_some(time)
+}
+
+/// Sun elevation that ends the morning golden hour and starts the evening one
+const GOLDEN_HOUR_ELEVATION: f64 = 6.0;
+
+/// Everything a field team planning around daylight wants to know about one day
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolarDetails {
+    pub date: NaiveDate,
+    pub sun: SunTimes,
+    pub solar_noon: DateTime<Tz>,
+    pub day_length: Duration,
+    pub civil: (Option<DateTime<Tz>>, Option<DateTime<Tz>>), // (dawn, dusk)
+    pub nautical: (Option<DateTime<Tz>>, Option<DateTime<Tz>>),
+    pub astronomical: (Option<DateTime<Tz>>, Option<DateTime<Tz>>),
+    pub golden_hours: Vec<(DateTime<Tz>, DateTime<Tz>)>,
+}
+
+impl SolarDetails {
+    pub fn for_day(latitude: f64, longitude: f64, date: NaiveDate, tz: Tz) -> Self {
+        let sun = sun_times(latitude, longitude, date, tz);
+        let solar_noon = solar_noon(longitude, date, tz);
+        let day_length = match sun {
+            SunTimes::RiseAndSet { sunrise, sunset } => sunset - sunrise,
+            SunTimes::MidnightSun => Duration::hours(24),
+            SunTimes::PolarNight | SunTimes::NoLocation => Duration::zero(),
+        };
+
+        let mut details = Self {
+            date,
+            sun,
+            solar_noon,
+            day_length,
+            civil: (None, None),
+            nautical: (None, None),
+            astronomical: (None, None),
+            golden_hours: Vec::new(),
+        };
+        let (Some(coords), Some(day_start)) = (
+            Coordinates::new(latitude, longitude),
+            local_day_start(date, tz),
+        ) else {
+            return details;
+        };
+
+        let solar_day = SolarDay::new(coords, date);
+        let local =
+            |event| event_on_day(&solar_day, event, day_start).map(|t| t.with_timezone(&tz));
+        let twilight = |kind| (local(SolarEvent::Dawn(kind)), local(SolarEvent::Dusk(kind)));
+        details.civil = twilight(DawnType::Civil);
+        details.nautical = twilight(DawnType::Nautical);
+        details.astronomical = twilight(DawnType::Astronomical);
+
+        // Golden hour runs between the horizon and 6° up; on days the sun never climbs
+        // that high it lasts from sunrise to sunset
+        if let SunTimes::RiseAndSet { sunrise, sunset } = sun {
+            let golden = |morning| {
+                // The crate measures this angle downwards from the horizon
+                local(SolarEvent::Elevation {
+                    elevation: -GOLDEN_HOUR_ELEVATION.to_radians(),
+                    morning,
+                })
+            };
+            details.golden_hours = match (golden(true), golden(false)) {
+                (Some(morning_end), Some(evening_start)) => {
+                    vec![(sunrise, morning_end), (evening_start, sunset)]
+                }
+                _ => vec![(sunrise, sunset)],
+            };
+        }
+
+        details
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let time = |t: Option<DateTime<Tz>>| t.map(|t| t.to_rfc3339());
+        let (sunrise, sunset) = match self.sun {
+            SunTimes::RiseAndSet { sunrise, sunset } => (Some(sunrise), Some(sunset)),
+            _ => (None, None),
+        };
+        let state = match self.sun {
+            SunTimes::RiseAndSet { .. } => "rise_and_set",
+            SunTimes::MidnightSun => "midnight_sun",
+            SunTimes::PolarNight => "polar_night",
+            SunTimes::NoLocation => "no_location",
+        };
+
+        json!({
+            "date": self.date.to_string(),
+            "state": state,
+            "sunrise": time(sunrise),
+            "sunset": time(sunset),
+            "solar_noon": self.solar_noon.to_rfc3339(),
+            "day_length_minutes": self.day_length.num_minutes(),
+            "civil_dawn": time(self.civil.0),
+            "civil_dusk": time(self.civil.1),
+            "nautical_dawn": time(self.nautical.0),
+            "nautical_dusk": time(self.nautical.1),
+            "astronomical_dawn": time(self.astronomical.0),
+            "astronomical_dusk": time(self.astronomical.1),
+            "golden_hours": self
+                .golden_hours
+                .iter()
+                .map(|(start, end)| json!({ "start": start.to_rfc3339(), "end": end.to_rfc3339() }))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// How finely a local day is sampled when looking for the sun crossing the horizon
+const SAMPLE_MINUTES: i64 = 10;
+
+/// Sunrise and sunset on `date` (local to `tz`) at the given location
+///
+/// Days where the sun stays above or below the horizon throughout are reported as
+/// midnight sun or polar night rather than as bogus times.
+pub fn sun_times(latitude: f64, longitude: f64, date: NaiveDate, tz: Tz) -> SunTimes {
+    let Some(coords) = Coordinates::new(latitude, longitude) else {
+        return SunTimes::NoLocation;
+    };
+
+    // Sample the local day to see whether the sun crosses the horizon at all
+    let Some(day_start) = local_day_start(date, tz) else {
+        return SunTimes::NoLocation;
+    };
+    let samples: Vec<bool> = (0..=24 * 60 / SAMPLE_MINUTES)
+        .map(|i| {
+            let instant = day_start + Duration::minutes(i * SAMPLE_MINUTES);
+            solar_elevation(latitude, longitude, instant) >= HORIZON_ELEVATION
+        })
+        .collect();
+    if samples.iter().all(|up| *up) {
+        return SunTimes::MidnightSun;
+    }
+    if samples.iter().all(|up| !*up) {
+        return SunTimes::PolarNight;
+    }
+
+    let solar_day = SolarDay::new(coords, date);
+    let sunrise = event_on_day(&solar_day, SolarEvent::Sunrise, day_start);
+    let sunset = event_on_day(&solar_day, SolarEvent::Sunset, day_start);
+    if let (Some(sunrise), Some(sunset)) = (sunrise, sunset) {
+        return SunTimes::RiseAndSet {
+            sunrise: sunrise.with_timezone(&tz),
+            sunset: sunset.with_timezone(&tz),
+        };
+    }
+
+    // Days at the edge of the polar season may only rise or only set; go by local noon
+    let noon = day_start + Duration::hours(12);
+    if solar_elevation(latitude, longitude, noon) >= HORIZON_ELEVATION {
+        SunTimes::MidnightSun
+    } else {
+        SunTimes::PolarNight
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instant(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_solar_elevation() {
+        // London around the June solstice: ~62° at solar noon, well below the horizon at midnight
+        let (lat, lon) = (51.5074, -0.1278);
+        let noon = solar_elevation(lat, lon, instant("2025-06-21T12:02:00Z"));
+        assert!((noon - 62.0).abs() < 0.5, "{noon}");
+        let midnight = solar_elevation(lat, lon, instant("2025-06-21T00:02:00Z"));
+        assert!((midnight + 15.0).abs() < 0.5, "{midnight}");
+
+        // Published sunrise for London on the solstice is 04:43 BST (03:43 UTC)
+        assert_eq!(
+            solar_phase(lat, lon, instant("2025-06-21T03:40:00Z")),
+            SolarPhase::CivilTwilight
+        );
+        assert_eq!(
+            solar_phase(lat, lon, instant("2025-06-21T03:46:00Z")),
+            SolarPhase::Day
+        );
+    }
+
+    #[test]
+    fn test_sun_times() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let tromso = (69.6492, 18.9553);
+        let longyearbyen = (78.2232, 15.6267);
+        let oslo = chrono_tz::Europe::Oslo;
+
+        // Midnight sun and polar night in Tromsø
+        assert_eq!(
+            sun_times(tromso.0, tromso.1, date(2025, 6, 21), oslo),
+            SunTimes::MidnightSun
+        );
+        assert_eq!(
+            sun_times(tromso.0, tromso.1, date(2025, 12, 21), oslo),
+            SunTimes::PolarNight
+        );
+
+        // Longyearbyen's polar night runs well into February
+        assert_eq!(
+            sun_times(longyearbyen.0, longyearbyen.1, date(2026, 2, 1), oslo),
+            SunTimes::PolarNight
+        );
+        assert_eq!(
+            sun_times(longyearbyen.0, longyearbyen.1, date(2025, 5, 1), oslo),
+            SunTimes::MidnightSun
+        );
+
+        // An ordinary equinox day in Tromsø rises and sets around 06:10 / 18:30 local
+        match sun_times(tromso.0, tromso.1, date(2025, 3, 20), oslo) {
+            SunTimes::RiseAndSet { sunrise, sunset } => {
+                assert_eq!(sunrise.date_naive(), date(2025, 3, 20));
+                assert!((5..=6).contains(&sunrise.hour()), "{sunrise}");
+                assert!((18..=19).contains(&sunset.hour()), "{sunset}");
+            }
+            other => panic!("expected sunrise and sunset, got {other:?}"),
+        }
+
+        // Santiago skips midnight on 2025-09-07 but the sun still rises and sets
+        let santiago = (-33.4489, -70.6693);
+        match sun_times(
+            santiago.0,
+            santiago.1,
+            date(2025, 9, 7),
+            chrono_tz::America::Santiago,
+        ) {
+            SunTimes::RiseAndSet { sunrise, sunset } => {
+                assert_eq!(sunrise.date_naive(), date(2025, 9, 7));
+                assert!((7..=8).contains(&sunrise.hour()), "{sunrise}");
+                assert!((19..=20).contains(&sunset.hour()), "{sunset}");
+            }
+            other => panic!("expected sunrise and sunset, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_solar_details() {
+        let date = NaiveDate::from_ymd_opt(2025, 6, 21).unwrap();
+        let london = SolarDetails::for_day(51.5074, -0.1278, date, chrono_tz::Europe::London);
+
+        // Published: sunrise 04:43, sunset 21:21, solar noon 13:02 BST
+        let SunTimes::RiseAndSet { sunrise, sunset } = london.sun else {
+            panic!("expected sunrise and sunset, got {:?}", london.sun);
+        };
+        assert_eq!(sunrise.format("%H").to_string(), "04");
+        assert_eq!(sunset.format("%H").to_string(), "21");
+        assert_eq!(london.solar_noon.format("%H:%M").to_string(), "13:02");
+        assert!((16 * 60..17 * 60).contains(&london.day_length.num_minutes()));
+
+        // Twilight widens outward from sunrise/sunset; midsummer London has no astronomical night
+        let (civil_dawn, civil_dusk) = london.civil;
+        let (nautical_dawn, nautical_dusk) = london.nautical;
+        assert!(nautical_dawn.unwrap() < civil_dawn.unwrap() && civil_dawn.unwrap() < sunrise);
+        assert!(sunset < civil_dusk.unwrap() && civil_dusk.unwrap() < nautical_dusk.unwrap());
+        assert_eq!(london.astronomical, (None, None));
+
+        // Two golden hours hugging sunrise and sunset
+        assert_eq!(london.golden_hours.len(), 2);
+        let (morning_start, morning_end) = london.golden_hours[0];
+        let (evening_start, evening_end) = london.golden_hours[1];
+        assert_eq!((morning_start, evening_end), (sunrise, sunset));
+        assert!(morning_end > sunrise && morning_end - sunrise < Duration::hours(2));
+        assert!(evening_start < sunset && sunset - evening_start < Duration::hours(2));
+
+        // Polar night still has a solar noon and reports zero day length
+        let tromso = SolarDetails::for_day(
+            69.6492,
+            18.9553,
+            NaiveDate::from_ymd_opt(2025, 12, 21).unwrap(),
+            chrono_tz::Europe::Oslo,
+        );
+        assert_eq!(tromso.sun, SunTimes::PolarNight);
+        assert_eq!(tromso.day_length, Duration::zero());
+        assert_eq!(tromso.solar_noon.format("%H").to_string(), "11");
+        assert!(tromso.civil.0.is_some() && tromso.golden_hours.is_empty());
+        assert_eq!(tromso.to_json()["state"], "polar_night");
+        assert_eq!(tromso.to_json()["sunrise"], serde_json::Value::Null);
+
+        // Zones a day away from UTC still get the noon of their own local day
+        for (longitude, tz) in [
+            (-157.4, chrono_tz::Pacific::Kiritimati),
+            (-171.8, chrono_tz::Pacific::Apia),
+            (172.9, chrono_tz::Pacific::Tarawa),
+            (-155.6, chrono_tz::Pacific::Honolulu),
+        ] {
+            let noon = solar_noon(longitude, date, tz);
+            assert_eq!(noon.date_naive(), date, "{tz}");
+        }
+    }
+
+    #[test]
+    fn test_high_latitude_phases() {
+        // Tromsø in December never sees the sun, but midday is still twilight rather than night
+        let (lat, lon) = (69.6492, 18.9553);
+        assert_eq!(
+            solar_phase(lat, lon, instant("2025-12-21T10:45:00Z")),
+            SolarPhase::CivilTwilight
+        );
+        assert_eq!(
+            solar_phase(lat, lon, instant("2025-12-21T22:00:00Z")),
+            SolarPhase::Night
+        );
+
+        // And in June the sun stays up at midnight
+        assert_eq!(
+            solar_phase(lat, lon, instant("2025-06-21T22:45:00Z")),
+            SolarPhase::Day
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use chrono::{DateTime, Duration, NaiveDate, Timelike, Utc};
+use chrono_tz::Tz;
+
+use crate::config::{TimeActivity, TimeDisplayConfig};
+
+/// The hour-by-hour instants covering `date` in `tz`: 24 rows, or 23/25 on DST change days
+pub fn day_hours(tz: Tz, date: NaiveDate) -> Vec<DateTime<Utc>> {
+    // A day starts at midnight, or at the first valid local time when DST skips midnight
+    let start_of = |date: NaiveDate| {
+        (0..=2).find_map(|hour| {
+            date.and_hms_opt(hour, 0, 0)?
+                .and_local_timezone(tz)
+                .earliest()
+                .map(|start| start.with_timezone(&Utc))
+        })
+    };
+    let (Some(start), Some(end)) = (start_of(date), date.succ_opt().and_then(start_of)) else {
+        return Vec::new();
+    };
+    std::iter::successors(Some(start), |hour| Some(*hour + Duration::hours(1)))
+        .take_while(|hour| *hour < end)
+        .collect()
+}
+
+/// One conversion table cell: the local time in `tz`, e.g. "09:00", or "01:00 +1d" when the
+/// zone is already on a different date than the table's `date`
+///
+/// Midnight shows the weekday that starts instead, e.g. "00:00 Wed", marking day boundaries.
+pub fn format_cell(instant: DateTime<Utc>, tz: Tz, date: NaiveDate, use_12_hour: bool) -> String {
+    let local = instant.with_timezone(&tz);
+    let time = if use_12_hour {
+        local.format("%I:%M %p").to_string()
+    } else {
+        local.format("%H:%M").to_string()
+    };
+    if is_day_boundary(instant, tz) {
+        return format!("{time} {}", local.format("%a"));
+    }
+    match (local.date_naive() - date).num_days() {
+        0 => time,
+        days => format!("{time} {days:+}d"),
+    }
+}
+
+/// Whether a table cell falls in work hours
+pub fn is_work_hour(instant: DateTime<Utc>, tz: Tz, time_config: &TimeDisplayConfig) -> bool {
+    time_config.get_time_activity_at(instant.with_timezone(&tz).time()) == TimeActivity::Work
+}
+
+/// Whether a zone's date changes at this row, i.e. it reads midnight
+pub fn is_day_boundary(instant: DateTime<Utc>, tz: Tz) -> bool {
+    let local = instant.with_timezone(&tz);
+    local.hour() == 0 && local.minute() == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_hours() {
+        let date = NaiveDate::from_ymd_opt(2025, 7, 22).unwrap();
+        let hours = day_hours(chrono_tz::America::Los_Angeles, date);
+        assert_eq!(hours.len(), 24);
+        assert_eq!(hours[0].to_rfc3339(), "2025-07-22T07:00:00+00:00");
+
+        // Shorter and longer days when the clocks change
+        let spring = NaiveDate::from_ymd_opt(2025, 3, 30).unwrap();
+        assert_eq!(day_hours(chrono_tz::Europe::London, spring).len(), 23);
+        let autumn = NaiveDate::from_ymd_opt(2025, 10, 26).unwrap();
+        assert_eq!(day_hours(chrono_tz::Europe::London, autumn).len(), 25);
+
+        // Santiago skips midnight on 7 September: that day starts at 01:00
+        let santiago = chrono_tz::America::Santiago;
+        let before = NaiveDate::from_ymd_opt(2025, 9, 6).unwrap();
+        assert_eq!(day_hours(santiago, before).len(), 24);
+        let skipped = day_hours(santiago, before.succ_opt().unwrap());
+        assert_eq!(skipped.len(), 23);
+        assert_eq!(skipped[0].to_rfc3339(), "2025-09-07T04:00:00+00:00");
+    }
+
+    #[test]
+    fn test_format_cell() {
+        let date = NaiveDate::from_ymd_opt(2025, 7, 22).unwrap();
+        let hours = day_hours(chrono_tz::America::Los_Angeles, date);
+        let tokyo = chrono_tz::Asia::Tokyo;
+
+        assert_eq!(format_cell(hours[0], tokyo, date, false), "16:00");
+        assert_eq!(format_cell(hours[8], tokyo, date, false), "00:00 Wed");
+        assert_eq!(format_cell(hours[9], tokyo, date, false), "01:00 +1d");
+        assert_eq!(format_cell(hours[9], tokyo, date, true), "01:00 AM +1d");
+        assert!(is_day_boundary(hours[8], tokyo));
+        assert!(!is_day_boundary(hours[8], chrono_tz::Asia::Kolkata));
+
+        let config = TimeDisplayConfig::default();
+        assert!(is_work_hour(hours[0], tokyo, &config));
+        assert!(!is_work_hour(hours[3], tokyo, &config));
+    }
+}
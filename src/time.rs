@@ -1,13 +1,69 @@
-use chrono::{DateTime, Offset, Utc};
+use chrono::{
+    DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone as ChronoTimeZone, Utc,
+};
 use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
 use std::sync::OnceLock;
-use sunrise::{Coordinates, SolarDay, SolarEvent};
+
+use crate::solar::{sun_times, SolarDetails, SunTimes};
 
 static CITIES_DATA: OnceLock<CitiesData> = OnceLock::new();
 
+/// A UTC offset in seconds as "UTC+1", "UTC+5:30" or "UTC-3:30"
+pub fn format_utc_offset(offset_seconds: i32) -> String {
+    let sign = if offset_seconds < 0 { '-' } else { '+' };
+    let minutes = offset_seconds.abs() / 60;
+    match minutes % 60 {
+        0 => format!("UTC{sign}{}", minutes / 60),
+        rest => format!("UTC{sign}{}:{rest:02}", minutes / 60),
+    }
+}
+
+/// The instant the clocks in `tz` read `local`
+///
+/// A time repeated when the clocks go back resolves to its first occurrence. A time skipped
+/// when they go forward resolves an hour later, as the clock reads after the jump (02:30
+/// becomes 03:30), so a wall-clock time always lands somewhere.
+pub fn resolve_local<Z: ChronoTimeZone>(tz: Z, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+    tz.from_local_datetime(&local)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(local + chrono::Duration::hours(1)))
+                .earliest()
+        })
+        .map(|instant| instant.with_timezone(&Utc))
+}
+
+/// Parses a user-entered date/time in a zone (or the system local zone when `tz` is None)
+///
+/// Accepts RFC 3339, "YYYY-MM-DD HH:MM", "YYYY-MM-DDTHH:MM" and "HH:MM" (on `today`).
+pub fn parse_datetime_in_zone(
+    input: &str,
+    tz: Option<Tz>,
+    today: NaiveDate,
+) -> Option<DateTime<Utc>> {
+    let input = input.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(input) {
+        return Some(datetime.with_timezone(&Utc));
+    }
+
+    let naive = ["%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(input, format).ok())
+        .or_else(|| {
+            NaiveTime::parse_from_str(input, "%H:%M")
+                .ok()
+                .map(|time| today.and_time(time))
+        })?;
+
+    match tz {
+        Some(tz) => resolve_local(tz, naive),
+        None => resolve_local(Local, naive),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CityData {
     pub name: String,
@@ -103,9 +159,13 @@ impl TimeZone {
     }
 
     pub fn get_timezone_abbreviation(&self) -> String {
+        self.get_timezone_abbreviation_at(Utc::now())
+    }
+
+    /// The zone's abbreviation in effect at `instant`, e.g. "BST" in summer and "GMT" in winter
+    pub fn get_timezone_abbreviation_at(&self, instant: DateTime<Utc>) -> String {
         // Use chrono's built-in timezone formatting for accurate, DST-aware abbreviations
-        let now = Utc::now();
-        let local_time = now.with_timezone(&self.tz);
+        let local_time = instant.with_timezone(&self.tz);
         let chrono_abbrev = local_time.format("%Z").to_string();
 
         // If chrono returns a numeric offset (like "-03" or "+05:30"),
@@ -210,6 +270,36 @@ impl TimeZone {
         local_time.offset().fix().local_minus_utc() / 3600
     }
 
+    /// The zone's next UTC offset change after `after` (within about a year and a half), as
+    /// `(instant, offset before, offset after)` with offsets in seconds east of UTC
+    pub fn next_offset_change(&self, after: DateTime<Utc>) -> Option<(DateTime<Utc>, i32, i32)> {
+        let offset_at = |instant: DateTime<Utc>| {
+            instant
+                .with_timezone(&self.tz)
+                .offset()
+                .fix()
+                .local_minus_utc()
+        };
+        let before = offset_at(after);
+
+        // Find the first day whose end has a different offset, then narrow down to the second
+        let seconds = |timestamp: i64| DateTime::from_timestamp(timestamp, 0).unwrap_or(after);
+        let mut low = after.timestamp();
+        let mut high = (1..=550)
+            .map(|day| low + day * 86_400)
+            .find(|timestamp| offset_at(seconds(*timestamp)) != before)?;
+        while high - low > 1 {
+            let middle = low + (high - low) / 2;
+            if offset_at(seconds(middle)) == before {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        let change = seconds(high);
+        Some((change, before, offset_at(change)))
+    }
+
     pub fn offset_string(&self) -> String {
         let offset_hours = self.utc_offset_hours();
         if offset_hours >= 0 {
@@ -243,6 +333,28 @@ impl TimeZone {
         self.display_name.clone()
     }
 
+    /// Whether the zone's label, city, code, country or IANA id contains `query`, ignoring case
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        let city = self.get_city_name();
+        [
+            self.effective_display_name().to_string(),
+            self.display_name.clone(),
+            TimeZoneManager::get_country_for_city(&city),
+            city,
+            self.tz.name().to_string(),
+        ]
+        .iter()
+        .any(|field| field.to_lowercase().contains(&query))
+    }
+
+    /// How config entries tied to this zone (bookmarks, countdowns) refer to it
+    pub fn config_zone_name(&self) -> String {
+        self.source_city
+            .clone()
+            .unwrap_or_else(|| self.tz.name().to_string())
+    }
+
     pub fn get_coordinates(&self) -> Option<(f64, f64)> {
         let cities_data = TimeZoneManager::load_cities_data();
 
@@ -265,31 +377,26 @@ impl TimeZone {
         None
     }
 
-    pub fn get_sunrise_sunset(&self, date: DateTime<Utc>) -> Option<(DateTime<Tz>, DateTime<Tz>)> {
-        let (lat, lng) = self.get_coordinates()?;
-        let coords = Coordinates::new(lat, lng)?;
+    /// Sunrise and sunset on the zone's local day containing `date`
+    pub fn get_sunrise_sunset(&self, date: DateTime<Utc>) -> SunTimes {
+        let Some((lat, lng)) = self.get_coordinates() else {
+            return SunTimes::NoLocation;
+        };
 
         // Convert UTC date to local date for calculation
         let local_date = date.with_timezone(&self.tz).date_naive();
+        sun_times(lat, lng, local_date, self.tz)
+    }
 
-        // Create solar day for calculations
-        let solar_day = SolarDay::new(coords, local_date);
-
-        // Calculate sunrise and sunset times (returns UTC times)
-        let sunrise_utc = solar_day.event_time(SolarEvent::Sunrise);
-        let sunset_utc = solar_day.event_time(SolarEvent::Sunset);
-
-        // Convert UTC times to local timezone
-        let sunrise_tz = sunrise_utc.with_timezone(&self.tz);
-        let sunset_tz = sunset_utc.with_timezone(&self.tz);
-
-        Some((sunrise_tz, sunset_tz))
+    /// Full solar breakdown for one of the zone's local dates, if the zone has a location
+    pub fn get_solar_details(&self, date: NaiveDate) -> Option<SolarDetails> {
+        let (lat, lng) = self.get_coordinates()?;
+        Some(SolarDetails::for_day(lat, lng, date, self.tz))
     }
 
-    pub fn format_sun_times(&self, date: DateTime<Utc>, use_12_hour: bool) -> Option<String> {
-        let (sunrise, sunset) = self.get_sunrise_sunset(date)?;
-        if use_12_hour {
-            Some(format!(
+    pub fn format_sun_times(&self, date: DateTime<Utc>, use_12_hour: bool) -> String {
+        match self.get_sunrise_sunset(date) {
+            SunTimes::RiseAndSet { sunrise, sunset } if use_12_hour => format!(
                 "☀ {}:{}{} ☽ {}:{}{}",
                 sunrise.format("%I"),
                 sunrise.format("%M"),
@@ -297,13 +404,13 @@ impl TimeZone {
                 sunset.format("%I"),
                 sunset.format("%M"),
                 sunset.format("%P")
-            ))
-        } else {
-            Some(format!(
-                "☀ {} ☽ {}",
-                sunrise.format("%H:%M"),
-                sunset.format("%H:%M")
-            ))
+            ),
+            SunTimes::RiseAndSet { sunrise, sunset } => {
+                format!("☀ {} ☽ {}", sunrise.format("%H:%M"), sunset.format("%H:%M"))
+            }
+            SunTimes::MidnightSun => "☀ Midnight sun".to_string(),
+            SunTimes::PolarNight => "☽ Polar night".to_string(),
+            SunTimes::NoLocation => "☀ No location".to_string(),
         }
     }
 }
@@ -442,8 +549,8 @@ impl TimeZoneManager {
         self.add_timezone_with_label(name, None)
     }
 
-    pub fn add_timezone_with_label(&mut self, name: &str, custom_label: Option<String>) -> bool {
-        // Handle "City, Country" format from search results
+    /// Looks up a city by name, accepting the "City, Country" form from search results
+    fn find_city(name: &str) -> Option<&'static CityData> {
         let (city_name, country) = if name.contains(", ") {
             let parts: Vec<&str> = name.splitn(2, ", ").collect();
             (parts[0], Some(parts[1]))
@@ -453,7 +560,7 @@ impl TimeZoneManager {
 
         // Find city, considering country if provided
         let cities_data = Self::load_cities_data();
-        let city = if let Some(country_name) = country {
+        if let Some(country_name) = country {
             // Look for exact match with city name and country
             cities_data.cities.iter().find(|c| {
                 c.name.eq_ignore_ascii_case(city_name)
@@ -465,27 +572,53 @@ impl TimeZoneManager {
                 .cities
                 .iter()
                 .find(|c| c.name.eq_ignore_ascii_case(city_name))
-        };
+        }
+    }
 
-        if let Some(city) = city {
-            if let Ok(tz) = Tz::from_str(&city.timezone) {
-                let timezone = TimeZone::with_source_city(
-                    tz,
-                    city.code.clone(),
-                    custom_label,
-                    Some(city.name.clone()),
-                );
-
-                // Check if we already have this exact city (by airport code)
-                if !self.zones.iter().any(|z| z.display_name == city.code) {
-                    self.add_zone(timezone);
-                    return true;
-                }
+    /// Whether a config zone name resolves to a city with a valid timezone
+    pub fn is_known_city(name: &str) -> bool {
+        Self::city_timezone(name).is_some()
+    }
+
+    /// The timezone of a city named in the config
+    pub fn city_timezone(name: &str) -> Option<Tz> {
+        Self::find_city(name).and_then(|city| Tz::from_str(&city.timezone).ok())
+    }
+
+    /// The timezone for a config value that names either an IANA zone or a city
+    pub fn resolve_zone(name: &str) -> Option<Tz> {
+        Tz::from_str(name)
+            .ok()
+            .or_else(|| Self::city_timezone(name))
+    }
+
+    pub fn add_timezone_with_label(&mut self, name: &str, custom_label: Option<String>) -> bool {
+        if let Some(timezone) = Self::city_zone(name, custom_label) {
+            // Check if we already have this exact city (by airport code)
+            if !self
+                .zones
+                .iter()
+                .any(|z| z.display_name == timezone.display_name)
+            {
+                self.add_zone(timezone);
+                return true;
             }
         }
         false
     }
 
+    /// Builds the zone for a city without adding it, for callers that keep their own order
+    pub fn city_zone(name: &str, custom_label: Option<String>) -> Option<TimeZone> {
+        let city = Self::find_city(name)?;
+        let tz = Tz::from_str(&city.timezone).ok()?;
+        Some(TimeZone::with_source_city(
+            tz,
+            city.code.clone(),
+            custom_label,
+            Some(city.name.clone()),
+        ))
+    }
+
     pub fn with_default_zones() -> Self {
         let cities_data = Self::load_cities_data();
         let default_city_names = vec![
@@ -549,6 +682,35 @@ impl TimeZoneManager {
             false
         }
     }
+
+    /// One-line summary of an instant across every zone, e.g. "Tue 17:00 LON / 09:00 LAX / Wed 01:00 TYO"
+    ///
+    /// The weekday is only repeated when it differs from the previous zone's local day.
+    pub fn format_multi_zone_summary(&self, instant: DateTime<Utc>, use_12_hour: bool) -> String {
+        let time_format = if use_12_hour { "%I:%M %p" } else { "%H:%M" };
+        let mut previous_day = None;
+
+        self.zones
+            .iter()
+            .map(|zone| {
+                let local = zone.convert_time(instant);
+                let day = local.date_naive();
+                let time = local.format(time_format);
+                let entry = if previous_day == Some(day) {
+                    format!("{time} {}", zone.effective_display_name())
+                } else {
+                    format!(
+                        "{} {time} {}",
+                        local.format("%a"),
+                        zone.effective_display_name()
+                    )
+                };
+                previous_day = Some(day);
+                entry
+            })
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
 }
 
 impl Default for TimeZoneManager {
@@ -744,4 +906,141 @@ mod tests {
         assert!(uk_zone.is_some(), "Should find London, UK zone");
         assert!(canada_zone.is_some(), "Should find London, Canada zone");
     }
+
+    #[test]
+    fn test_multi_zone_summary() {
+        let mut manager = TimeZoneManager::new();
+        manager.add_timezone_by_name("Los Angeles");
+        manager.add_timezone_by_name("London");
+        manager.add_timezone_with_label("Tokyo", Some("Aiko".to_string()));
+
+        let instant = DateTime::parse_from_rfc3339("2025-07-22T16:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            manager.format_multi_zone_summary(instant, false),
+            "Tue 09:00 LAX / 17:00 LON / Wed 01:00 Aiko"
+        );
+        assert_eq!(
+            manager.format_multi_zone_summary(instant, true),
+            "Tue 09:00 AM LAX / 05:00 PM LON / Wed 01:00 AM Aiko"
+        );
+    }
+
+    #[test]
+    fn test_parse_datetime_in_zone() {
+        let today = NaiveDate::from_ymd_opt(2025, 7, 22).unwrap();
+        let expected = DateTime::parse_from_rfc3339("2025-07-22T16:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let london = Some(chrono_tz::Europe::London);
+
+        assert_eq!(
+            parse_datetime_in_zone("2025-07-22 17:00", london, today),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_datetime_in_zone("2025-07-22T17:00", london, today),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_datetime_in_zone("17:00", london, today),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_datetime_in_zone("2025-07-22T16:00:00Z", None, today),
+            Some(expected)
+        );
+        assert_eq!(parse_datetime_in_zone("tomorrow", london, today), None);
+
+        // 02:30 doesn't exist in New York on the night the clocks go forward
+        assert_eq!(
+            parse_datetime_in_zone(
+                "2025-03-09 02:30",
+                Some(chrono_tz::America::New_York),
+                today
+            ),
+            Some(
+                DateTime::parse_from_rfc3339("2025-03-09T07:30:00Z")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )
+        );
+    }
+
+    #[test]
+    fn test_format_sun_times_states() {
+        let instant = |rfc3339| {
+            DateTime::parse_from_rfc3339(rfc3339)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let tromso = TimeZone::with_source_city(
+            chrono_tz::Europe::Oslo,
+            "TOS".to_string(),
+            None,
+            Some("Tromsø".to_string()),
+        );
+
+        assert_eq!(
+            tromso.format_sun_times(instant("2025-06-21T12:00:00Z"), false),
+            "☀ Midnight sun"
+        );
+        assert_eq!(
+            tromso.format_sun_times(instant("2025-12-21T12:00:00Z"), false),
+            "☽ Polar night"
+        );
+        assert!(tromso
+            .format_sun_times(instant("2025-03-20T12:00:00Z"), false)
+            .starts_with("☀ 0"));
+
+        // A zone that doesn't come from a known city has nothing to compute from
+        let unknown = TimeZone::new(
+            chrono_tz::Etc::GMTPlus5,
+            "Etc/GMT+5".to_string(),
+            "XYZ".to_string(),
+        );
+        assert_eq!(unknown.get_sunrise_sunset(Utc::now()), SunTimes::NoLocation);
+        assert_eq!(unknown.format_sun_times(Utc::now(), true), "☀ No location");
+    }
+
+    #[test]
+    fn test_next_offset_change() {
+        let london = TimeZone::from_tz(chrono_tz::Europe::London);
+        let summer = DateTime::parse_from_rfc3339("2025-07-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let (at, before, after) = london.next_offset_change(summer).unwrap();
+        assert_eq!(at.to_rfc3339(), "2025-10-26T01:00:00+00:00");
+        assert_eq!((before, after), (3600, 0));
+        assert_eq!(london.get_timezone_abbreviation_at(summer), "BST");
+        assert_eq!(london.get_timezone_abbreviation_at(at), "GMT");
+
+        let tokyo = TimeZone::from_tz(chrono_tz::Asia::Tokyo);
+        assert_eq!(tokyo.next_offset_change(summer), None);
+
+        assert_eq!(format_utc_offset(3600), "UTC+1");
+        assert_eq!(format_utc_offset(0), "UTC+0");
+        assert_eq!(format_utc_offset(19_800), "UTC+5:30");
+        assert_eq!(format_utc_offset(-12_600), "UTC-3:30");
+    }
+
+    #[test]
+    fn test_zone_matches() {
+        let mut manager = TimeZoneManager::new();
+        manager.add_timezone_with_label("London", Some("Ana".to_string()));
+        let london = &manager.zones()[0];
+        for query in ["ana", "LONDON", "lon", "uk", "europe/"] {
+            assert!(london.matches(query), "{query}");
+        }
+        assert!(!london.matches("tokyo"));
+    }
+
+    #[test]
+    fn test_city_zone() {
+        let zone = TimeZoneManager::city_zone("tokyo", None).unwrap();
+        assert_eq!(zone.tz, chrono_tz::Asia::Tokyo);
+        assert_eq!(zone.get_city_name(), "Tokyo");
+        assert!(TimeZoneManager::city_zone("Atlantis", None).is_none());
+    }
 }
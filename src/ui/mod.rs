@@ -1,3 +1,7 @@
+pub mod ruler;
 pub mod timeline;
+pub mod week_grid;
 
-pub use timeline::TimelineWidget;
+pub use ruler::HourRuler;
+pub use timeline::{OffsetReference, TimelineMarker, TimelineScale, TimelineWidget};
+pub use week_grid::WeekGrid;
@@ -0,0 +1,184 @@
+use chrono::{DateTime, Duration, Timelike};
+use chrono_tz::Tz;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::Widget,
+};
+
+use crate::app::TimeFormat;
+use crate::config::ColorTheme;
+use crate::ui::TimelineScale;
+
+/// Hour labels above the zone list, aligned with the timeline columns
+pub struct HourRuler {
+    pub scale: TimelineScale,
+    pub tz: Tz,
+    pub tag: String, // Whose hours these are, e.g. "LON" or "UTC"
+    pub display_format: TimeFormat,
+    pub color_theme: ColorTheme,
+}
+
+impl HourRuler {
+    pub fn new(
+        scale: TimelineScale,
+        tz: Tz,
+        tag: String,
+        display_format: TimeFormat,
+        color_theme: ColorTheme,
+    ) -> Self {
+        Self {
+            scale,
+            tz,
+            tag,
+            display_format,
+            color_theme,
+        }
+    }
+
+    /// Every how many hours to label, so labels keep at least a column between them
+    fn label_stride(&self) -> u32 {
+        let hours = self
+            .scale
+            .end
+            .signed_duration_since(self.scale.start)
+            .num_minutes() as f64
+            / 60.0;
+        let columns_per_hour = self.scale.width as f64 / hours.max(1.0);
+        let label_width = match self.display_format {
+            TimeFormat::TwentyFourHour => 2.0,
+            TimeFormat::TwelveHour => 3.0,
+        };
+
+        [1, 2, 3, 4, 6, 12, 24]
+            .into_iter()
+            .find(|stride| columns_per_hour * *stride as f64 >= label_width + 1.0)
+            .unwrap_or(24)
+    }
+
+    fn format_hour(&self, time: DateTime<Tz>) -> String {
+        if time.hour() == 0 {
+            // Day boundaries show the weekday instead
+            return time.format("%a").to_string();
+        }
+        match self.display_format {
+            TimeFormat::TwentyFourHour => time.format("%H").to_string(),
+            TimeFormat::TwelveHour => {
+                let (pm, hour) = time.hour12();
+                format!("{hour}{}", if pm { 'p' } else { 'a' })
+            }
+        }
+    }
+
+    /// Local hour boundaries in view as (column, label)
+    pub fn get_labels(&self) -> Vec<(u16, String)> {
+        let stride = self.label_stride();
+        let mut labels = Vec::new();
+
+        // Find the first local :00 at or after the start (handles :30/:45 offsets)
+        let start = self.scale.start;
+        let local_start = start.with_timezone(&self.tz);
+        let mut current = start
+            - Duration::seconds(local_start.second() as i64)
+            - Duration::nanoseconds(local_start.nanosecond() as i64);
+        if local_start.minute() != 0 || current < start {
+            current += Duration::minutes(60 - local_start.minute() as i64);
+        }
+
+        while current <= self.scale.end {
+            let local = current.with_timezone(&self.tz);
+            if local.minute() == 0 && local.hour().is_multiple_of(stride) {
+                labels.push((self.scale.position_of(current), self.format_hour(local)));
+            }
+            current += Duration::hours(1);
+        }
+
+        labels
+    }
+}
+
+impl Widget for HourRuler {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 3 || area.height == 0 {
+            return;
+        }
+        // Columns line up with the inside of the zone borders
+        let inner_x = area.x + 1;
+        let inner_width = area.width - 2;
+        let y = area.y;
+
+        let tag = format!("{} ", self.tag);
+        let mut next_free = 0;
+        for (i, ch) in tag.chars().enumerate().take(inner_width as usize) {
+            buf[(inner_x + i as u16, y)]
+                .set_char(ch)
+                .set_style(Style::default().fg(self.color_theme.get_selected_border_color()));
+            next_free = i as u16 + 1;
+        }
+
+        for (column, label) in self.get_labels() {
+            let label_width = label.chars().count() as u16;
+            if column < next_free || column + label_width > inner_width {
+                continue;
+            }
+            let style = if label.chars().all(|c| c.is_ascii_alphabetic()) {
+                Style::default().fg(Color::White)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            for (i, ch) in label.chars().enumerate() {
+                buf[(inner_x + column + i as u16, y)]
+                    .set_char(ch)
+                    .set_style(style);
+            }
+            next_free = column + label_width + 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TimelineZoom;
+    use chrono::Utc;
+
+    #[test]
+    fn test_ruler_labels_align_with_timeline() {
+        let position = DateTime::parse_from_rfc3339("2025-07-22T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let scale = TimelineScale::new(position, TimelineZoom::Hours12, 120);
+        let ruler = HourRuler::new(
+            scale,
+            chrono_tz::Europe::London,
+            "LON".to_string(),
+            TimeFormat::TwentyFourHour,
+            ColorTheme::default(),
+        );
+
+        // 10 columns per hour: every local hour is labelled, at its timeline column
+        let labels = ruler.get_labels();
+        assert_eq!(labels.len(), 13);
+        assert_eq!(labels[0], (0, "07".to_string()));
+        assert_eq!(labels[6], (60, "13".to_string()));
+        assert_eq!(labels[6].0, scale.position_of(position));
+
+        // A week squeezed into 120 columns only labels every 6 hours, with weekdays at midnight
+        let scale = TimelineScale::new(position, TimelineZoom::Week, 120);
+        let ruler = HourRuler::new(
+            scale,
+            chrono_tz::Asia::Kolkata, // UTC+5:30
+            "DEL".to_string(),
+            TimeFormat::TwelveHour,
+            ColorTheme::default(),
+        );
+        let labels = ruler.get_labels();
+        assert!(labels
+            .iter()
+            .all(|(_, label)| ["6a", "12p", "6p"].contains(&label.as_str())
+                || label.chars().all(|c| c.is_ascii_alphabetic())));
+        assert!(labels.iter().any(|(_, label)| label == "Wed"));
+        assert_eq!(labels.len(), 28);
+    }
+}
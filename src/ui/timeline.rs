@@ -1,4 +1,6 @@
-use chrono::{DateTime, Days, Duration, Offset, TimeZone as ChronoTimeZone, Timelike, Utc};
+use chrono::{
+    DateTime, Days, Duration, NaiveDate, Offset, TimeZone as ChronoTimeZone, Timelike, Utc,
+};
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Margin, Rect},
@@ -8,7 +10,9 @@ use ratatui::{
 };
 
 use crate::app::{TimeFormat, TimezoneDisplayMode};
-use crate::config::{ColorTheme, TimeDisplayConfig};
+use crate::calendar::EventOccurrence;
+use crate::config::{ClockTime, ColorTheme, ShadingMode, TimeDisplayConfig, TimelineZoom};
+use crate::solar::solar_phase;
 use crate::time::TimeZone;
 
 pub struct TimelineWidget<'a> {
@@ -23,6 +27,83 @@ pub struct TimelineWidget<'a> {
     pub show_date: bool,
     pub show_dst: bool,
     pub show_sun_times: bool,
+    pub events: &'a [EventOccurrence],
+    pub markers: &'a [TimelineMarker],
+    pub zoom: TimelineZoom,
+    pub shading: ShadingMode,
+    pub relative_to: Option<OffsetReference>,
+}
+
+/// The zone that titles show their offset relative to, as it is at the scrubbed instant
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OffsetReference {
+    pub offset_seconds: i32,
+    pub date: NaiveDate,
+}
+
+/// Describes an offset and local date relative to a reference, e.g. "+8h", "-3h30m +1 day"
+pub fn relative_offset_label(
+    offset_seconds: i32,
+    date: NaiveDate,
+    reference: OffsetReference,
+) -> String {
+    let minutes = (offset_seconds - reference.offset_seconds) / 60;
+    let sign = if minutes < 0 { '-' } else { '+' };
+    let (hours, minutes) = (minutes.abs() / 60, minutes.abs() % 60);
+    let mut label = match (hours, minutes) {
+        (0, 0) => "same time".to_string(),
+        (hours, 0) => format!("{sign}{hours}h"),
+        (hours, minutes) => format!("{sign}{hours}h{minutes:02}m"),
+    };
+    match (date - reference.date).num_days() {
+        0 => {}
+        1 => label.push_str(" +1 day"),
+        -1 => label.push_str(" -1 day"),
+        days => label.push_str(&format!(" {days:+} days")),
+    }
+    label
+}
+
+/// A single instant flagged on the bar, such as a bookmark
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimelineMarker {
+    pub at: DateTime<Utc>,
+    pub symbol: char,
+    pub color: Color,
+}
+
+/// Maps instants to columns of a timeline centred on the scrub position
+///
+/// Shared by every zone block and the hour ruler so their columns line up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimelineScale {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub width: u16,
+}
+
+impl TimelineScale {
+    pub fn new(timeline_position: DateTime<Utc>, zoom: TimelineZoom, width: u16) -> Self {
+        let half_minutes = (zoom.hours(width) / 2.0 * 60.0) as i64;
+        Self {
+            start: timeline_position - Duration::minutes(half_minutes),
+            end: timeline_position + Duration::minutes(half_minutes),
+            width,
+        }
+    }
+
+    pub fn position_of(&self, time: DateTime<Utc>) -> u16 {
+        let total_duration = self.end.signed_duration_since(self.start);
+        let time_duration = time.signed_duration_since(self.start);
+
+        if total_duration.num_seconds() == 0 {
+            return 0;
+        }
+
+        let ratio = time_duration.num_seconds() as f64 / total_duration.num_seconds() as f64;
+        let position = (ratio * (self.width as f64)).round() as u16;
+        position.min(self.width.saturating_sub(1))
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -58,52 +139,97 @@ impl<'a> TimelineWidget<'a> {
             show_date,
             show_dst,
             show_sun_times,
+            events: &[],
+            markers: &[],
+            relative_to: None,
+            zoom: TimelineZoom::default(),
+            shading: ShadingMode::default(),
         }
     }
 
-    fn get_timeline_hours(&self, width: u16) -> f64 {
-        // Optimal display: approximately 2 characters per hour for dense but readable display
-        // This means 48 hours fits in ~96 characters, allowing expansion on wider screens
-        const OPTIMAL_CHARS_PER_HOUR: f64 = 2.0;
-        const MIN_HOURS: f64 = 48.0; // Minimum 48-hour window (24h before + 24h after)
-        const MAX_HOURS: f64 = 168.0; // Maximum 1 week window
+    /// Sets whether the bar shows the configured schedule or real daylight
+    pub fn with_shading(mut self, shading: ShadingMode) -> Self {
+        self.shading = shading;
+        self
+    }
 
-        // Calculate how many hours we can display optimally with current width
-        let optimal_hours = (width as f64) / OPTIMAL_CHARS_PER_HOUR;
+    /// Sets how much time the timeline spans
+    pub fn with_zoom(mut self, zoom: TimelineZoom) -> Self {
+        self.zoom = zoom;
+        self
+    }
 
-        // Clamp between minimum and maximum
-        optimal_hours.clamp(MIN_HOURS, MAX_HOURS)
+    /// Overlays calendar event occurrences on this timeline
+    pub fn with_events(mut self, events: &'a [EventOccurrence]) -> Self {
+        self.events = events;
+        self
     }
 
-    fn get_timeline_start(&self, width: u16) -> DateTime<Utc> {
-        let total_hours = self.get_timeline_hours(width);
-        let hours_before = total_hours / 2.0;
-        self.timeline_position - Duration::minutes((hours_before * 60.0) as i64)
+    /// Shows this zone's offset relative to another zone in the title
+    pub fn with_relative_to(mut self, reference: Option<OffsetReference>) -> Self {
+        self.relative_to = reference;
+        self
     }
 
-    fn get_timeline_end(&self, width: u16) -> DateTime<Utc> {
-        let total_hours = self.get_timeline_hours(width);
-        let hours_after = total_hours / 2.0;
-        self.timeline_position + Duration::minutes((hours_after * 60.0) as i64)
+    /// Flags instants (bookmarks and the like) on this timeline
+    pub fn with_markers(mut self, markers: &'a [TimelineMarker]) -> Self {
+        self.markers = markers;
+        self
     }
 
-    fn time_to_position(&self, time: DateTime<Utc>, width: u16) -> u16 {
+    /// Titles of the events the timeline position currently falls within
+    fn events_at_timeline_position(&self) -> Vec<&str> {
+        self.events
+            .iter()
+            .filter(|event| event.contains(self.timeline_position))
+            .map(|event| event.summary.as_str())
+            .collect()
+    }
+
+    /// Whether each column falls inside a timed event (all-day events would cover whole days)
+    fn get_event_cells(&self, width: u16) -> Vec<bool> {
+        let mut cells = vec![false; width as usize];
         let start = self.get_timeline_start(width);
         let end = self.get_timeline_end(width);
-        let total_duration = end.signed_duration_since(start);
-        let time_duration = time.signed_duration_since(start);
 
-        if total_duration.num_seconds() == 0 {
-            return 0;
+        for event in self.events.iter().filter(|event| !event.all_day) {
+            if event.end <= start || event.start >= end {
+                continue;
+            }
+            let from = self.time_to_position(event.start.max(start), width);
+            let to = self.time_to_position(event.end.min(end), width);
+            // Short events still get at least one cell
+            for cell in cells
+                .iter_mut()
+                .take(to.max(from + 1) as usize)
+                .skip(from as usize)
+            {
+                *cell = true;
+            }
         }
 
-        let ratio = time_duration.num_seconds() as f64 / total_duration.num_seconds() as f64;
-        let position = (ratio * (width as f64)).round() as u16;
-        position.min(width.saturating_sub(1))
+        cells
+    }
+
+    fn get_timeline_hours(&self, width: u16) -> f64 {
+        self.zoom.hours(width)
+    }
+
+    fn get_timeline_start(&self, width: u16) -> DateTime<Utc> {
+        TimelineScale::new(self.timeline_position, self.zoom, width).start
+    }
+
+    fn get_timeline_end(&self, width: u16) -> DateTime<Utc> {
+        TimelineScale::new(self.timeline_position, self.zoom, width).end
+    }
+
+    fn time_to_position(&self, time: DateTime<Utc>, width: u16) -> u16 {
+        TimelineScale::new(self.timeline_position, self.zoom, width).position_of(time)
     }
 
-    fn get_hour_display(&self, hour: u32) -> (char, Color) {
-        let activity = self.time_config.get_time_activity(hour);
+    /// Shade and colour for a time of day, to the minute
+    fn get_activity_display(&self, time: impl Into<ClockTime>) -> (char, Color) {
+        let activity = self.time_config.get_time_activity_at(time);
         let char = self.time_config.get_activity_char(activity);
         let color = self
             .time_config
@@ -149,6 +275,17 @@ impl<'a> TimelineWidget<'a> {
         transitions
     }
 
+    /// The UTC instant this zone's `date` starts (first valid local time if midnight is skipped)
+    fn local_midnight_utc(&self, date: chrono::NaiveDate) -> Option<DateTime<Utc>> {
+        (0..=2).find_map(|hour| {
+            self.timezone
+                .tz
+                .from_local_datetime(&date.and_hms_opt(hour, 0, 0)?)
+                .earliest()
+                .map(|dt| dt.with_timezone(&Utc))
+        })
+    }
+
     fn get_midnight_markers_in_range(&self, width: u16) -> Vec<DateTime<Utc>> {
         let mut midnight_markers = Vec::new();
         let start = self.get_timeline_start(width);
@@ -190,21 +327,74 @@ impl<'a> TimelineWidget<'a> {
         midnight_markers
     }
 
+    /// Local cell boundaries within the range, flagged when they fall on the hour
+    ///
+    /// Only produced for fixed zooms where a cell is at least three columns wide,
+    /// so the ticks never crowd the bar.
+    fn get_cell_ticks_in_range(&self, width: u16) -> Vec<(DateTime<Utc>, bool)> {
+        let cell_minutes = self.zoom.cell_minutes();
+        let columns_per_cell =
+            width as f64 / (self.get_timeline_hours(width) * 60.0) * cell_minutes as f64;
+        if self.zoom == TimelineZoom::Auto || columns_per_cell < 3.0 {
+            return Vec::new();
+        }
+
+        let start = self.get_timeline_start(width);
+        let end = self.get_timeline_end(width);
+        let mut ticks = Vec::new();
+
+        // Walk whole minutes so zones with :30/:45 offsets tick on their own local boundaries
+        let mut current = start
+            .with_second(0)
+            .and_then(|t| t.with_nanosecond(0))
+            .unwrap_or(start);
+        while current <= end {
+            let local = current.with_timezone(&self.timezone.tz);
+            let minute = local.minute() as i64;
+            if minute % cell_minutes == 0 {
+                if current >= start {
+                    ticks.push((current, minute == 0));
+                }
+                current += Duration::minutes(cell_minutes);
+            } else {
+                current += Duration::minutes(cell_minutes - minute % cell_minutes);
+            }
+        }
+
+        ticks
+    }
+
     fn get_timeline_display(&self, width: u16) -> Vec<(char, Color)> {
         let mut display = vec![('░', Color::DarkGray); width as usize];
         let start_time = self.get_timeline_start(width);
         let total_hours = self.get_timeline_hours(width);
 
+        // Daylight shading needs the city's location; zones without one keep the schedule
+        if self.shading == ShadingMode::Daylight {
+            if let Some((lat, lon)) = self.timezone.get_coordinates() {
+                for (i, cell) in display.iter_mut().enumerate() {
+                    let hours_offset = (i as f64 / width as f64) * total_hours;
+                    let time_at_position =
+                        start_time + Duration::minutes((hours_offset * 60.0) as i64);
+                    *cell = self.color_theme.get_solar_phase_display(solar_phase(
+                        lat,
+                        lon,
+                        time_at_position,
+                    ));
+                }
+                return display;
+            }
+        }
+
         // Convert timeline to local timezone for this zone
         let local_start = start_time.with_timezone(&self.timezone.tz);
 
         for i in 0..width {
-            // Calculate what time this position represents in the local timezone
-            let hours_offset = (i as f64 / width as f64) * total_hours;
+            // Each cell shows the activity at its middle, so ranges starting off the hour land
+            // in the cell they mostly cover
+            let hours_offset = ((i as f64 + 0.5) / width as f64) * total_hours;
             let time_at_position = local_start + Duration::minutes((hours_offset * 60.0) as i64);
-            let hour = time_at_position.hour();
-
-            display[i as usize] = self.get_hour_display(hour);
+            display[i as usize] = self.get_activity_display(time_at_position.time());
         }
 
         display
@@ -264,6 +454,19 @@ impl<'a> Widget for TimelineWidget<'a> {
             }
         };
 
+        let title = match self.relative_to {
+            Some(reference) => {
+                let local = self.timeline_position.with_timezone(&self.timezone.tz);
+                let label = relative_offset_label(
+                    local.offset().fix().local_minus_utc(),
+                    local.date_naive(),
+                    reference,
+                );
+                format!("{title} · {label}")
+            }
+            None => title,
+        };
+
         let mut block = Block::default()
             .borders(Borders::ALL)
             .title(title)
@@ -272,22 +475,32 @@ impl<'a> Widget for TimelineWidget<'a> {
         // Add sunrise/sunset times to bottom right if enabled
         if self.show_sun_times {
             let use_12_hour = matches!(self.display_format, TimeFormat::TwelveHour);
-            if let Some(sun_times) = self
+            let sun_times = self
                 .timezone
-                .format_sun_times(self.current_time, use_12_hour)
-            {
-                let sun_color = if self.selected {
-                    self.color_theme.get_selected_border_color()
-                } else {
-                    Color::Gray
-                };
-                let sun_line = Line::from(vec![Span::styled(
-                    sun_times,
-                    Style::default().fg(sun_color),
-                )])
-                .alignment(Alignment::Right);
-                block = block.title_top(sun_line);
-            }
+                .format_sun_times(self.timeline_position, use_12_hour);
+            let sun_color = if self.selected {
+                self.color_theme.get_selected_border_color()
+            } else {
+                Color::Gray
+            };
+            let sun_line = Line::from(vec![Span::styled(
+                sun_times,
+                Style::default().fg(sun_color),
+            )])
+            .alignment(Alignment::Right);
+            block = block.title_top(sun_line);
+        }
+
+        // Show the titles of events under the scrubber along the bottom border
+        let current_events = self.events_at_timeline_position();
+        if !current_events.is_empty() {
+            let event_line = Line::from(vec![Span::styled(
+                format!(" 📅 {} ", current_events.join(", ")),
+                Style::default()
+                    .fg(Color::White)
+                    .bg(self.color_theme.get_event_color()),
+            )]);
+            block = block.title_bottom(event_line);
         }
 
         block.render(area, buf);
@@ -308,6 +521,16 @@ impl<'a> Widget for TimelineWidget<'a> {
             buf[(x, timeline_y)].set_char(ch).set_style(style);
         }
 
+        // Render calendar events as bands behind the timeline bar
+        if !self.events.is_empty() {
+            let event_color = self.color_theme.get_event_color();
+            for (i, in_event) in self.get_event_cells(inner.width).into_iter().enumerate() {
+                if in_event {
+                    buf[(inner.x + i as u16, timeline_y)].set_bg(event_color);
+                }
+            }
+        }
+
         // Render current time indicator (now line)
         let now_pos = self.time_to_position(self.current_time, inner.width);
         if now_pos < inner.width {
@@ -358,14 +581,31 @@ impl<'a> Widget for TimelineWidget<'a> {
             }
         }
 
+        // Render markers over everything but the now and scrub lines
+        let (start, end) = (
+            self.get_timeline_start(inner.width),
+            self.get_timeline_end(inner.width),
+        );
+        for marker in self
+            .markers
+            .iter()
+            .filter(|m| (start..=end).contains(&m.at))
+        {
+            let marker_pos = self.time_to_position(marker.at, inner.width);
+            if marker_pos != now_pos && marker_pos != timeline_pos {
+                buf[(inner.x + marker_pos, timeline_y)]
+                    .set_char(marker.symbol)
+                    .set_style(Style::default().fg(marker.color));
+            }
+        }
+
         // Render dates in middle of each day's work hours if enabled
         if self.show_date {
             let start_time = self.get_timeline_start(inner.width);
             let end_time = self.get_timeline_end(inner.width);
 
             // Find the middle of work hours (default 8 AM to 6 PM, so middle is 1 PM)
-            let work_middle_hour =
-                (self.time_config.work_hours_start + self.time_config.work_hours_end) / 2;
+            let work_middle = self.time_config.work_midday();
 
             // Convert timeline to local timezone for this specific timezone
             let local_start = start_time.with_timezone(&self.timezone.tz);
@@ -375,7 +615,9 @@ impl<'a> Widget for TimelineWidget<'a> {
             // Iterate through each day visible in this timezone's local time
             while current_date <= local_end.date_naive() {
                 // Create a time for the middle of work hours on this day IN THIS TIMEZONE
-                if let Some(work_middle_local) = current_date.and_hms_opt(work_middle_hour, 0, 0) {
+                if let Some(work_middle_local) =
+                    current_date.and_hms_opt(work_middle.hour(), work_middle.minute(), 0)
+                {
                     // Create the datetime in this timezone, then convert to UTC for position calculation
                     if let Some(work_middle_tz) = self
                         .timezone
@@ -383,7 +625,24 @@ impl<'a> Widget for TimelineWidget<'a> {
                         .from_local_datetime(&work_middle_local)
                         .single()
                     {
-                        let work_middle_utc = work_middle_tz.with_timezone(&chrono::Utc);
+                        let mut work_middle_utc = work_middle_tz.with_timezone(&chrono::Utc);
+
+                        // When zoomed in the work-hours middle may be off screen; label the
+                        // middle of whatever part of the day is visible instead
+                        if work_middle_utc < start_time || work_middle_utc > end_time {
+                            let day_start = self.local_midnight_utc(current_date);
+                            let day_end = self.local_midnight_utc(current_date + Days::new(1));
+                            if let (Some(day_start), Some(day_end)) = (day_start, day_end) {
+                                let visible_start = day_start.max(start_time);
+                                let visible_end = day_end.min(end_time);
+                                if visible_start >= visible_end {
+                                    current_date = current_date + Days::new(1);
+                                    continue;
+                                }
+                                work_middle_utc = visible_start + (visible_end - visible_start) / 2;
+                            }
+                        }
+
                         let date_pos = self.time_to_position(work_middle_utc, inner.width);
 
                         // Only render if this position is within the visible timeline
@@ -421,6 +680,16 @@ impl<'a> Widget for TimelineWidget<'a> {
             }
         }
 
+        // Render cell ticks under the bar when zoomed in
+        if inner.height > 1 {
+            for (tick_time, on_hour) in self.get_cell_ticks_in_range(inner.width) {
+                let tick_pos = self.time_to_position(tick_time, inner.width);
+                buf[(inner.x + tick_pos, inner.y + 1)]
+                    .set_char(if on_hour { '╵' } else { '·' })
+                    .set_style(Style::default().fg(Color::DarkGray));
+            }
+        }
+
         // Render time display under the scrubber position
         if inner.height > 1 {
             let zone_time = self.timezone.convert_time(self.timeline_position);
@@ -431,18 +700,21 @@ impl<'a> Widget for TimelineWidget<'a> {
 
             let time_y = inner.y + 1;
 
-            // Position the time display under the timeline position indicator
+            // Continue the scrub line down through this row, with the time beside it
             let timeline_pos = self.time_to_position(self.timeline_position, inner.width);
-            let time_start_x = if timeline_pos >= (time_str.chars().count() as u16 / 2) {
-                timeline_pos - (time_str.chars().count() as u16 / 2)
+            buf[(inner.x + timeline_pos, time_y)]
+                .set_char('┃')
+                .set_style(Style::default().fg(self.color_theme.get_timeline_position_color()));
+
+            // Prefer the right of the line, flipping to the left near the right edge
+            let time_str = format!(" {time_str} ");
+            let time_len = time_str.chars().count() as u16;
+            let time_start_x = if timeline_pos + 1 + time_len <= inner.width {
+                timeline_pos + 1
             } else {
-                0
+                timeline_pos.saturating_sub(time_len)
             };
 
-            // Ensure we don't go beyond the right edge
-            let time_start_x =
-                time_start_x.min(inner.width.saturating_sub(time_str.chars().count() as u16));
-
             for (i, ch) in time_str.chars().enumerate() {
                 let x = inner.x + time_start_x + i as u16;
                 if x < inner.x + inner.width {
@@ -456,6 +728,7 @@ impl<'a> Widget for TimelineWidget<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::TimeActivity;
     use chrono_tz;
 
     #[test]
@@ -527,15 +800,15 @@ mod tests {
         );
 
         // Test work hours get dark shade block
-        let (char, _) = widget.get_hour_display(14); // 2 PM
+        let (char, _) = widget.get_activity_display(ClockTime::new(14, 0)); // 2 PM
         assert_eq!(char, '▓'); // Work hours = dark shade block
 
         // Test awake hours get medium shade
-        let (char, _) = widget.get_hour_display(7); // 7 AM
+        let (char, _) = widget.get_activity_display(ClockTime::new(7, 0)); // 7 AM
         assert_eq!(char, '▒'); // Awake hours = medium shade
 
         // Test night hours get light shade
-        let (char, _) = widget.get_hour_display(2); // 2 AM
+        let (char, _) = widget.get_activity_display(ClockTime::new(2, 0)); // 2 AM
         assert_eq!(char, '░'); // Night hours = light shade
     }
 
@@ -802,4 +1075,272 @@ mod tests {
         assert_eq!(tz.custom_label, None);
         assert_eq!(tz.effective_display_name(), &tz.display_name);
     }
+
+    #[test]
+    fn test_event_overlay() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::UTC);
+        let config = crate::config::TimeDisplayConfig::default();
+        let base_time = chrono::DateTime::parse_from_rfc3339("2025-07-22T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let events = vec![
+            EventOccurrence {
+                summary: "Planning".to_string(),
+                start: base_time - Duration::minutes(30),
+                end: base_time + Duration::hours(2),
+                all_day: false,
+            },
+            EventOccurrence {
+                summary: "Holiday".to_string(),
+                start: base_time - Duration::hours(12),
+                end: base_time + Duration::hours(12),
+                all_day: true,
+            },
+        ];
+
+        let widget = TimelineWidget::new(
+            base_time,
+            base_time,
+            &tz,
+            true,
+            TimeFormat::TwentyFourHour,
+            TimezoneDisplayMode::Short,
+            &config,
+            ColorTheme::default(),
+            false,
+            false,
+            false,
+        )
+        .with_events(&events);
+
+        // Both events contain the timeline position
+        assert_eq!(
+            widget.events_at_timeline_position(),
+            vec!["Planning", "Holiday"]
+        );
+
+        // Only the timed event is drawn as a band: 2.5 hours at 2 chars per hour on a 48h window
+        const TEST_WIDTH: u16 = 96;
+        let cells = widget.get_event_cells(TEST_WIDTH);
+        assert_eq!(cells.iter().filter(|&&c| c).count(), 5);
+        assert!(cells[widget.time_to_position(base_time, TEST_WIDTH) as usize]);
+    }
+
+    #[test]
+    fn test_zoomed_timeline() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::Asia::Kathmandu); // UTC+5:45
+        let position = DateTime::parse_from_rfc3339("2025-07-22T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let config = crate::config::TimeDisplayConfig::default();
+        let widget = TimelineWidget::new(
+            position,
+            position,
+            &tz,
+            false,
+            TimeFormat::TwentyFourHour,
+            TimezoneDisplayMode::Short,
+            &config,
+            ColorTheme::default(),
+            false,
+            true,
+            false,
+        )
+        .with_zoom(TimelineZoom::Hours6);
+
+        // 6 hours across 120 columns: 20 columns per hour, scrubber stays centered
+        assert_eq!(
+            widget.get_timeline_start(120),
+            position - Duration::hours(3)
+        );
+        assert_eq!(widget.get_timeline_end(120), position + Duration::hours(3));
+        assert_eq!(widget.time_to_position(position, 120), 60);
+        assert_eq!(
+            widget.time_to_position(position + Duration::minutes(15), 120),
+            65
+        );
+
+        // 15-minute ticks on local (:45 offset) boundaries, hourly ones flagged
+        let ticks = widget.get_cell_ticks_in_range(120);
+        assert_eq!(ticks.len(), 25);
+        for (tick, on_hour) in &ticks {
+            let local = tick.with_timezone(&tz.tz);
+            assert_eq!(local.minute() % 15, 0);
+            assert_eq!(*on_hour, local.minute() == 0);
+        }
+
+        // Local midnight (18:15 UTC) is outside this window, but the day still has a start
+        assert!(widget.get_midnight_markers_in_range(120).is_empty());
+        let widget = widget.with_zoom(TimelineZoom::Hours24);
+        let midnights = widget.get_midnight_markers_in_range(120);
+        assert_eq!(midnights.len(), 1);
+        assert_eq!(
+            midnights[0],
+            DateTime::parse_from_rfc3339("2025-07-22T18:15:00Z")
+                .unwrap()
+                .with_timezone(&Utc)
+        );
+        assert_eq!(widget.time_to_position(midnights[0], 120), 91);
+
+        // Widths too narrow for readable ticks get none
+        assert!(widget.get_cell_ticks_in_range(48).is_empty());
+    }
+
+    #[test]
+    fn test_split_shift_cells() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::UTC);
+        let config = TimeDisplayConfig {
+            work_hours: vec![
+                "09:00-12:00".parse().unwrap(),
+                "13:00-17:30".parse().unwrap(),
+            ],
+            ..TimeDisplayConfig::default()
+        };
+        let position = DateTime::parse_from_rfc3339("2025-07-22T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let widget = TimelineWidget::new(
+            position,
+            position,
+            &tz,
+            false,
+            TimeFormat::TwentyFourHour,
+            TimezoneDisplayMode::Short,
+            &config,
+            ColorTheme::default(),
+            false,
+            false,
+            false,
+        )
+        .with_zoom(TimelineZoom::Hours24);
+
+        // 96 columns over the day: each cell is a quarter of an hour, from midnight
+        let cells = widget.get_timeline_display(96);
+        let work = config.get_activity_char(TimeActivity::Work);
+        let awake = config.get_activity_char(TimeActivity::Awake);
+        assert_eq!(cells[47].0, work); // 11:45
+        assert_eq!(cells[48].0, awake); // 12:00, lunch
+        assert_eq!(cells[51].0, awake);
+        assert_eq!(cells[52].0, work); // 13:00
+        assert_eq!(cells[69].0, work); // 17:15
+        assert_eq!(cells[70].0, awake); // 17:30
+    }
+
+    #[test]
+    fn test_daylight_shading() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::Europe::Oslo);
+        let config = crate::config::TimeDisplayConfig::default();
+        let theme = ColorTheme::default();
+
+        // Midwinter noon in Oslo: a short day in the middle of a 24h window
+        let position = DateTime::parse_from_rfc3339("2025-12-21T11:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let widget = |zone| {
+            TimelineWidget::new(
+                position,
+                position,
+                zone,
+                false,
+                TimeFormat::TwentyFourHour,
+                TimezoneDisplayMode::Short,
+                &config,
+                theme,
+                false,
+                false,
+                false,
+            )
+            .with_zoom(TimelineZoom::Hours24)
+            .with_shading(ShadingMode::Daylight)
+        };
+
+        let day = theme.get_solar_phase_display(crate::solar::SolarPhase::Day);
+        let oslo = widget(&tz).get_timeline_display(96);
+        let day_cells = oslo.iter().filter(|cell| **cell == day).count();
+        // Roughly six hours of daylight at 4 columns per hour
+        assert!((20..=28).contains(&day_cells), "{day_cells}");
+        assert_eq!(oslo[48], day);
+
+        // Zones without a known city keep the schedule shading
+        let unknown = crate::time::TimeZone::new(
+            chrono_tz::Etc::GMTMinus1,
+            "Etc/GMT-1".to_string(),
+            "XYZ".to_string(),
+        );
+        let schedule = widget(&unknown).get_timeline_display(96);
+        assert_eq!(
+            schedule[48],
+            widget(&unknown).get_activity_display(ClockTime::new(12, 0))
+        );
+    }
+
+    #[test]
+    fn test_markers_drawn_on_bar() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::UTC);
+        let position = DateTime::parse_from_rfc3339("2025-07-22T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let config = crate::config::TimeDisplayConfig::default();
+        let markers = [
+            TimelineMarker {
+                at: position + Duration::hours(1),
+                symbol: '◆',
+                color: Color::Magenta,
+            },
+            // Off the visible window
+            TimelineMarker {
+                at: position + Duration::days(2),
+                symbol: '◆',
+                color: Color::Magenta,
+            },
+        ];
+        let widget = TimelineWidget::new(
+            position,
+            position,
+            &tz,
+            false,
+            TimeFormat::TwentyFourHour,
+            TimezoneDisplayMode::Short,
+            &config,
+            ColorTheme::default(),
+            false,
+            false,
+            false,
+        )
+        .with_zoom(TimelineZoom::Hours6)
+        .with_markers(&markers);
+
+        let area = Rect::new(0, 0, 122, 4);
+        let mut buf = Buffer::empty(area);
+        widget.render(area, &mut buf);
+        // 20 columns per hour, right of the centred scrub line, inside the border
+        assert_eq!(buf[(1 + 80, 1)].symbol(), "◆");
+        let drawn = (1..121).filter(|x| buf[(*x, 1)].symbol() == "◆").count();
+        assert_eq!(drawn, 1);
+    }
+
+    #[test]
+    fn test_relative_offset_label() {
+        let date = NaiveDate::from_ymd_opt(2025, 7, 22).unwrap();
+        let london = OffsetReference {
+            offset_seconds: 3600,
+            date,
+        };
+        assert_eq!(relative_offset_label(3600, date, london), "same time");
+        assert_eq!(relative_offset_label(9 * 3600, date, london), "+8h");
+        assert_eq!(relative_offset_label(-7 * 3600, date, london), "-8h");
+        // Kolkata and Kathmandu: half and three-quarter hours
+        assert_eq!(relative_offset_label(19_800, date, london), "+4h30m");
+        assert_eq!(relative_offset_label(20_700, date, london), "+4h45m");
+        // St John's (UTC-2:30 in summer) behind, and Tokyo already on tomorrow
+        assert_eq!(relative_offset_label(-9000, date, london), "-3h30m");
+        assert_eq!(
+            relative_offset_label(9 * 3600, date.succ_opt().unwrap(), london),
+            "+8h +1 day"
+        );
+        assert_eq!(
+            relative_offset_label(-7 * 3600, date.pred_opt().unwrap(), london),
+            "-8h -1 day"
+        );
+    }
 }
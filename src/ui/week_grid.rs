@@ -0,0 +1,268 @@
+use chrono::{DateTime, Datelike, Days, Duration, NaiveDate, NaiveDateTime, Timelike, Utc};
+use chrono_tz::Tz;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+
+use crate::app::TimeFormat;
+use crate::config::{ClockTime, ColorTheme, TimeActivity, TimeDisplayConfig};
+use crate::time::resolve_local;
+
+/// Width of the weekday/date column, e.g. "Mon 20 Oct "
+const DAY_LABEL_WIDTH: u16 = 11;
+
+/// A Monday-to-Sunday grid of one zone's local hours, each cell counting how many of the
+/// other zones are inside work hours at that time
+pub struct WeekGrid {
+    pub tz: Tz,
+    pub others: Vec<Tz>,
+    pub cursor: DateTime<Utc>, // The timeline position, highlighted as a cell
+    pub time_config: TimeDisplayConfig,
+    pub display_format: TimeFormat,
+    pub color_theme: ColorTheme,
+}
+
+impl WeekGrid {
+    /// Monday of the week holding the cursor, in the grid's zone
+    pub fn week_start(&self) -> NaiveDate {
+        let date = self.cursor.with_timezone(&self.tz).date_naive();
+        date - Days::new(date.weekday().num_days_from_monday() as u64)
+    }
+
+    /// The cursor's cell as (day from Monday, local hour)
+    pub fn cursor_cell(&self) -> (usize, usize) {
+        let local = self.cursor.with_timezone(&self.tz);
+        (
+            local.weekday().num_days_from_monday() as usize,
+            local.hour() as usize,
+        )
+    }
+
+    /// How many other zones are working during each local hour of the week, or None for
+    /// hours skipped by a DST change
+    pub fn work_counts(&self) -> [[Option<usize>; 24]; 7] {
+        let monday = self.week_start();
+        let mut counts = [[None; 24]; 7];
+        for (day, row) in counts.iter_mut().enumerate() {
+            let date = monday + Days::new(day as u64);
+            for (hour, count) in row.iter_mut().enumerate() {
+                let Some(start) = exact_local(self.tz, date.and_hms_opt(hour as u32, 0, 0)) else {
+                    continue;
+                };
+                // Sample mid-hour so zones on :30 and :45 offsets count for the hour they mostly overlap
+                let middle = start + Duration::minutes(30);
+                *count = Some(
+                    self.others
+                        .iter()
+                        .filter(|tz| {
+                            self.time_config
+                                .get_time_activity_at(middle.with_timezone(*tz).time())
+                                == TimeActivity::Work
+                        })
+                        .count(),
+                );
+            }
+        }
+        counts
+    }
+
+    fn format_hour(&self, hour: u32) -> String {
+        match self.display_format {
+            TimeFormat::TwentyFourHour => format!("{hour:02}"),
+            TimeFormat::TwelveHour => {
+                let (pm, hour) = (hour >= 12, (hour + 11) % 12 + 1);
+                format!("{hour}{}", if pm { 'p' } else { 'a' })
+            }
+        }
+    }
+
+    /// Every other zone at work shows in the work color, some of them in the awake color
+    fn heat_color(&self, count: usize) -> Option<Color> {
+        if count == 0 {
+            None
+        } else if count == self.others.len() {
+            Some(self.color_theme.get_work_color())
+        } else {
+            Some(self.color_theme.get_awake_color())
+        }
+    }
+}
+
+/// A local time in `tz`, or None when DST skips it
+fn exact_local(tz: Tz, local: Option<NaiveDateTime>) -> Option<DateTime<Utc>> {
+    use chrono::TimeZone as _;
+    let local = local?;
+    tz.from_local_datetime(&local)
+        .earliest()
+        .map(|instant| instant.with_timezone(&Utc))
+}
+
+/// Moves `cursor` by whole local hours and days in `tz`, landing on the start of an hour
+///
+/// Days keep the wall-clock hour across DST changes; an hour the clocks skip lands on the
+/// hour after it.
+pub fn shift_local_hour(cursor: DateTime<Utc>, tz: Tz, hours: i64, days: i64) -> DateTime<Utc> {
+    let local = cursor.with_timezone(&tz).naive_local();
+    let Some(hour_start) = local.date().and_hms_opt(local.hour(), 0, 0) else {
+        return cursor;
+    };
+    let target = hour_start + Duration::hours(hours) + Duration::days(days);
+    resolve_local(tz, target).unwrap_or(cursor)
+}
+
+impl Widget for WeekGrid {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width <= DAY_LABEL_WIDTH + 24 || area.height < 8 {
+            return;
+        }
+        let cell_width = (area.width - DAY_LABEL_WIDTH) / 24;
+        let label_width = match self.display_format {
+            TimeFormat::TwentyFourHour => 2,
+            TimeFormat::TwelveHour => 3,
+        };
+        let stride = [1, 2, 3, 4, 6, 12]
+            .into_iter()
+            .find(|stride| cell_width * stride > label_width)
+            .unwrap_or(12);
+
+        // Hour labels across the top, brighter for the zone's own work hours (sampled mid-hour too)
+        for hour in (0..24).step_by(stride as usize) {
+            let x = area.x + DAY_LABEL_WIDTH + hour as u16 * cell_width;
+            let style = if self
+                .time_config
+                .get_time_activity_at(ClockTime::new(hour, 30))
+                == TimeActivity::Work
+            {
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            buf.set_string(x, area.y, self.format_hour(hour), style);
+        }
+
+        let monday = self.week_start();
+        let counts = self.work_counts();
+        let (cursor_day, cursor_hour) = self.cursor_cell();
+        for (day, row) in counts.iter().enumerate() {
+            let y = area.y + 1 + day as u16;
+            let date = monday + Days::new(day as u64);
+            let label_style = if day == cursor_day {
+                Style::default()
+                    .fg(self.color_theme.get_selected_border_color())
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            buf.set_string(area.x, y, date.format("%a %d %b").to_string(), label_style);
+
+            // Leave a gap between neighbouring cells when there is room
+            let width = if cell_width > 1 {
+                cell_width - 1
+            } else {
+                cell_width
+            } as usize;
+            for (hour, count) in row.iter().enumerate() {
+                let x = area.x + DAY_LABEL_WIDTH + hour as u16 * cell_width;
+                let (text, mut style) = match count {
+                    None => (" ".repeat(width), Style::default()),
+                    Some(0) => (
+                        format!("{:>width$}", "·"),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Some(count) => (
+                        format!("{count:>width$}"),
+                        Style::default()
+                            .fg(Color::Black)
+                            .bg(self.heat_color(*count).unwrap_or(Color::Reset)),
+                    ),
+                };
+                if (day, hour) == (cursor_day, cursor_hour) {
+                    style = style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+                }
+                buf.set_string(x, y, text, style);
+            }
+        }
+
+        if area.height > 9 {
+            buf.set_string(
+                area.x,
+                area.y + 9,
+                format!(
+                    "Numbers: how many of the other {} zones are in work hours",
+                    self.others.len()
+                ),
+                Style::default().fg(Color::DarkGray),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instant(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_week_grid_counts_other_zones_at_work() {
+        let grid = WeekGrid {
+            tz: chrono_tz::Europe::London,
+            others: vec![
+                chrono_tz::America::New_York,
+                chrono_tz::Europe::Berlin,
+                chrono_tz::Asia::Kolkata,
+            ],
+            cursor: instant("2025-07-23T14:20:00Z"), // Wednesday 15:20 BST
+            time_config: TimeDisplayConfig::default(),
+            display_format: TimeFormat::TwentyFourHour,
+            color_theme: ColorTheme::default(),
+        };
+        assert_eq!(
+            grid.week_start(),
+            NaiveDate::from_ymd_opt(2025, 7, 21).unwrap()
+        );
+        assert_eq!(grid.cursor_cell(), (2, 15));
+
+        // Work hours are 08-18; cells are sampled mid-hour
+        let counts = grid.work_counts();
+        assert_eq!(counts[0][12], Some(2)); // Berlin 13:30, Kolkata 17:00
+        assert_eq!(counts[0][13], Some(2)); // New York 08:30, Berlin 14:30, Kolkata 18:00
+        assert_eq!(counts[0][3], Some(1)); // Kolkata 08:00
+        assert_eq!(counts[0][23], Some(0));
+
+        // The hour London skips when the clocks go forward has no cell
+        let spring = WeekGrid {
+            cursor: instant("2025-03-30T12:00:00Z"),
+            ..grid
+        };
+        assert_eq!(spring.work_counts()[6][1], None);
+    }
+
+    #[test]
+    fn test_shift_local_hour() {
+        let london = chrono_tz::Europe::London;
+        let cursor = instant("2025-10-25T08:40:00Z"); // Saturday 09:40 BST
+        assert_eq!(
+            shift_local_hour(cursor, london, 1, 0),
+            instant("2025-10-25T09:00:00Z")
+        );
+        assert_eq!(
+            shift_local_hour(cursor, london, -1, 0),
+            instant("2025-10-25T07:00:00Z")
+        );
+        // The next day is GMT, but the cursor stays on 09:00 local
+        assert_eq!(
+            shift_local_hour(cursor, london, 0, 1),
+            instant("2025-10-26T09:00:00Z")
+        );
+    }
+}